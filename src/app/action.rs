@@ -1,23 +1,74 @@
-use crate::app::state::{AppConfig, AvailablePackage, ManifestSummary};
+use crate::app::state::{
+    AppConfig, AvailablePackage, BackupEntry, DiscoveredProject, ManifestSummary, ProjectDetails,
+    RepoEntry, TaskHistoryEntry,
+};
 use crossterm::event::KeyEvent;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OutputStream {
     Stdout,
     Stderr,
 }
 
 #[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum Action {
     Init,
     Tick,
     Key(KeyEvent),
+    Resize {
+        height: u16,
+    },
     ConfigLoaded(Result<AppConfig, String>),
     ConfigSaved(Result<(), String>),
-    FolderScanned(Result<Vec<PathBuf>, String>),
+    AddProject(PathBuf),
+    ProjectPathsChecked(Vec<PathBuf>),
+    ProjectDetailsLoaded(Vec<ProjectDetails>),
+    ScanProgress {
+        scan_id: u64,
+        root: PathBuf,
+        visited: usize,
+        dir: PathBuf,
+    },
+    ProjectDiscovered {
+        scan_id: u64,
+        root: PathBuf,
+        project: DiscoveredProject,
+    },
+    ScanFinished {
+        scan_id: u64,
+        root: PathBuf,
+        result: Result<usize, String>,
+    },
     AvailablePackagesLoaded(Result<Vec<AvailablePackage>, String>),
-    ManifestLoaded(Result<ManifestSummary, String>),
+    ManifestLoaded {
+        project_path: PathBuf,
+        result: Result<ManifestSummary, String>,
+    },
+    RepositoriesLoaded(Result<Vec<RepoEntry>, String>),
+    RepositoriesSaved(Result<(), String>),
+    TaskHistoryLoaded(Result<Vec<TaskHistoryEntry>, String>),
+    TaskHistorySaved(Result<(), String>),
+    HistoryLogLoaded(Result<Vec<String>, String>),
+    LogsExported(Result<PathBuf, String>),
+    ProjectBackedUp {
+        project_path: PathBuf,
+        result: Result<BackupEntry, String>,
+        skipped: Vec<PathBuf>,
+    },
+    BackupsListed {
+        project_path: PathBuf,
+        result: Result<Vec<BackupEntry>, String>,
+    },
+    BackupRestored {
+        backup: PathBuf,
+        result: Result<PathBuf, String>,
+    },
+    UnityOpened {
+        project_path: PathBuf,
+        result: Result<(String, PathBuf), String>,
+    },
     TaskOutput {
         task_id: u64,
         stream: OutputStream,
@@ -1,17 +1,50 @@
-use crate::app::state::AppConfig;
+use crate::app::state::{
+    AppConfig, BackupSettings, RepoEntry, ScanSettings, TaskHistoryEntry, UnityEditor,
+    UnitySettings, VpmSettings,
+};
 use std::path::PathBuf;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Effect {
     LoadConfig,
-    LoadAvailablePackages,
+    ConfigureCommandBackend(VpmSettings),
+    LoadAvailablePackages {
+        repo_priority: Vec<String>,
+        disabled_repos: Vec<String>,
+    },
+    LoadRepositories,
+    SaveRepositories {
+        repos: Vec<RepoEntry>,
+        removed: Vec<String>,
+    },
     SaveConfig(AppConfig),
     ScanProjectsFolder {
+        scan_id: u64,
         root: PathBuf,
+        settings: ScanSettings,
     },
+    CheckProjectPaths(Vec<PathBuf>),
+    ReadProjectDetails(Vec<PathBuf>),
     ReadManifest {
         project_path: PathBuf,
     },
+    BackupProject {
+        project_path: PathBuf,
+        settings: BackupSettings,
+    },
+    ListBackups {
+        project_path: PathBuf,
+        settings: BackupSettings,
+    },
+    RestoreBackup {
+        backup: PathBuf,
+        target: PathBuf,
+    },
+    OpenInUnity {
+        project_path: PathBuf,
+        settings: UnitySettings,
+        editors: Vec<UnityEditor>,
+    },
     RunVpmCommand {
         task_id: u64,
         label: String,
@@ -20,4 +53,18 @@ pub enum Effect {
     CancelTask {
         task_id: u64,
     },
+    AppendTaskLog {
+        session: String,
+        task_id: u64,
+        line: String,
+    },
+    RecordTaskHistory(TaskHistoryEntry),
+    LoadTaskHistory,
+    ReadHistoryLog {
+        session: String,
+        task_id: u64,
+    },
+    ExportLogs {
+        lines: Vec<String>,
+    },
 }
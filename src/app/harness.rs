@@ -0,0 +1,268 @@
+use crate::app::action::{Action, OutputStream};
+use crate::app::effect::Effect;
+use crate::app::reducer::reduce;
+use crate::app::state::{
+    AppState, AvailablePackage, BackupEntry, LockedPackage, LogLevel, LogSource, ManifestSummary,
+    PackageInfo, PackageSource, PackageVersion, ProjectDetails, ProjectKind, ProjectMeta, Screen,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const SNAPSHOT_WIDTH: u16 = 120;
+pub const SNAPSHOT_HEIGHT: u16 = 36;
+
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(label: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("vcc-tui-{label}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).expect("create temp dir");
+        Self(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+pub struct Harness {
+    pub state: AppState,
+    pub effects: Vec<Effect>,
+}
+
+impl Harness {
+    pub fn new(state: AppState) -> Self {
+        Self {
+            state,
+            effects: Vec::new(),
+        }
+    }
+
+    pub fn send(&mut self, action: Action) -> Vec<Effect> {
+        let effects = reduce(&mut self.state, action);
+        self.effects.extend(effects.iter().cloned());
+        effects
+    }
+
+    pub fn press(&mut self, code: KeyCode) -> Vec<Effect> {
+        self.send(key(code))
+    }
+
+    pub fn type_text(&mut self, text: &str) -> Vec<Effect> {
+        text.chars()
+            .flat_map(|c| self.press(KeyCode::Char(c)))
+            .collect()
+    }
+
+    pub fn output(&mut self, task_id: u64, stream: OutputStream, line: &str) -> Vec<Effect> {
+        self.send(Action::TaskOutput {
+            task_id,
+            stream,
+            line: line.to_string(),
+        })
+    }
+
+    pub fn finish(&mut self, task_id: u64, exit_code: i32) -> Vec<Effect> {
+        self.send(Action::TaskDone {
+            task_id,
+            success: exit_code == 0,
+            cancelled: false,
+            exit_code: Some(exit_code),
+            error: None,
+        })
+    }
+
+    pub fn render(&self) -> String {
+        render_state(&self.state)
+    }
+}
+
+pub fn without_history(effects: Vec<Effect>) -> Vec<Effect> {
+    effects
+        .into_iter()
+        .filter(|e| {
+            !matches!(
+                e,
+                Effect::AppendTaskLog { .. } | Effect::RecordTaskHistory(_)
+            )
+        })
+        .collect()
+}
+
+pub fn key(code: KeyCode) -> Action {
+    Action::Key(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+pub fn render_state(state: &AppState) -> String {
+    let backend = TestBackend::new(SNAPSHOT_WIDTH, SNAPSHOT_HEIGHT);
+    let mut terminal = Terminal::new(backend).expect("test terminal");
+    terminal
+        .draw(|frame| crate::ui::render(frame, state))
+        .expect("draw frame");
+
+    let buffer = terminal.backend().buffer();
+    let mut out = String::new();
+    for y in 0..buffer.area.height {
+        let mut line = String::new();
+        for x in 0..buffer.area.width {
+            line.push_str(buffer.get(x, y).symbol());
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("ui")
+        .join("snapshots")
+        .join(format!("{name}.txt"));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().expect("snapshot dir")).expect("create snapshot dir");
+        fs::write(&path, actual).expect("write snapshot");
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing snapshot {}; run with UPDATE_SNAPSHOTS=1 to create it",
+            path.display()
+        )
+    });
+    assert!(
+        expected == actual,
+        "snapshot {name} differs; run with UPDATE_SNAPSHOTS=1 to accept\n--- expected\n{expected}\n--- actual\n{actual}"
+    );
+}
+
+pub fn version(version: &str) -> PackageVersion {
+    PackageVersion {
+        version: version.to_string(),
+        repo_id: "com.vrchat.repos.official".to_string(),
+        unity: Some("2022.3".to_string()),
+        vrchat_version: None,
+        dependencies: BTreeMap::new(),
+        description: None,
+        author: None,
+        changelog_url: None,
+        documentation_url: None,
+        legacy_folders: Vec::new(),
+        keywords: Vec::new(),
+    }
+}
+
+pub fn catalog_package(id: &str, display_name: &str, versions: &[&str]) -> AvailablePackage {
+    let versions = versions.iter().map(|v| version(v)).collect::<Vec<_>>();
+    let latest_version = versions[0].version.clone();
+    AvailablePackage {
+        id: id.to_string(),
+        display_name: display_name.to_string(),
+        latest_version: latest_version.clone(),
+        latest_prerelease: None,
+        versions: versions.clone(),
+        repo_id: "com.vrchat.repos.official".to_string(),
+        sources: vec![PackageSource {
+            repo_id: "com.vrchat.repos.official".to_string(),
+            latest_version,
+            versions,
+        }],
+    }
+}
+
+pub fn sample_state() -> AppState {
+    let mut state = AppState {
+        projects: vec![
+            ProjectMeta::from_path(PathBuf::from("/projects/AvatarProject")),
+            ProjectMeta::from_path(PathBuf::from("/projects/WorldProject")),
+        ],
+        selected_project_manifest: Some(ManifestSummary {
+            exists: true,
+            kind: Some(ProjectKind::Vpm),
+            packages: vec![
+                PackageInfo {
+                    name: "com.vrchat.avatars".to_string(),
+                    version: "3.5.0".to_string(),
+                },
+                PackageInfo {
+                    name: "nadena.dev.modular-avatar".to_string(),
+                    version: "1.9.0".to_string(),
+                },
+            ],
+            locked: vec![
+                LockedPackage {
+                    name: "com.vrchat.avatars".to_string(),
+                    version: "3.5.0".to_string(),
+                    dependencies: [("com.vrchat.base".to_string(), "3.5.0".to_string())].into(),
+                },
+                LockedPackage {
+                    name: "com.vrchat.base".to_string(),
+                    version: "3.5.0".to_string(),
+                    dependencies: BTreeMap::new(),
+                },
+                LockedPackage {
+                    name: "nadena.dev.modular-avatar".to_string(),
+                    version: "1.9.0".to_string(),
+                    dependencies: BTreeMap::new(),
+                },
+            ],
+            message: None,
+        }),
+        available_packages: vec![
+            catalog_package(
+                "com.vrchat.avatars",
+                "VRChat SDK - Avatars",
+                &["3.6.0", "3.5.0"],
+            ),
+            catalog_package("com.vrchat.base", "VRChat SDK - Base", &["3.6.0", "3.5.0"]),
+            catalog_package(
+                "nadena.dev.modular-avatar",
+                "Modular Avatar",
+                &["1.9.0", "1.8.2"],
+            ),
+        ],
+        default_backup_dir: Some(PathBuf::from("/home/creator/.local/share/vcc-tui/backups")),
+        ..AppState::default()
+    };
+    state.push_log(LogSource::Config, LogLevel::Info, "loaded");
+    state
+}
+
+pub fn project_details(versions: &[(&str, &str)]) -> Vec<ProjectDetails> {
+    versions
+        .iter()
+        .map(|(path, version)| ProjectDetails {
+            path: PathBuf::from(path),
+            unity_version: Some(version.to_string()),
+            manifest: None,
+        })
+        .collect()
+}
+
+pub fn backup_entry(path: &str, created: &str, size: u64) -> BackupEntry {
+    BackupEntry {
+        path: PathBuf::from(path),
+        created: chrono::NaiveDateTime::parse_from_str(created, "%Y-%m-%d %H:%M:%S")
+            .expect("backup timestamp"),
+        size,
+    }
+}
+
+pub fn sample_state_on(screen: Screen) -> AppState {
+    AppState {
+        screen,
+        ..sample_state()
+    }
+}
@@ -2,3 +2,6 @@ pub mod action;
 pub mod effect;
 pub mod reducer;
 pub mod state;
+
+#[cfg(test)]
+pub mod harness;
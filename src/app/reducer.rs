@@ -1,101 +1,219 @@
 use crate::app::action::{Action, OutputStream};
 use crate::app::effect::Effect;
-use crate::app::state::{AppConfig, AppState, ProjectMeta, Screen, TaskRecord, TaskState};
+use crate::app::state::{
+    AppState, BackupBrowser, BackupEntry, LogLevel, LogSource, ManifestSummary, Migration,
+    MigrationStage, ProjectColumn, ProjectKind, ProjectMeta, ProjectScan, Screen, TagEdit,
+    TaskHistoryEntry, TaskRecord, TaskScope, TaskState, VersionPicker,
+};
+use crate::services::unity::parse_installed_editors;
+use chrono::{SecondsFormat, Utc};
 use crossterm::event::{KeyCode, KeyModifiers};
-use std::path::PathBuf;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 
 const NADENA_REPO_URL: &str = "https://vpm.nadena.dev/vpm.json";
 const LILTOON_REPO_URL: &str = "https://lilxyzw.github.io/vpm-repos/vpm.json";
+const PROJECT_CHECK_TICKS: u64 = 10;
 
 pub fn reduce(state: &mut AppState, action: Action) -> Vec<Effect> {
     match action {
-        Action::Init => vec![
-            Effect::LoadConfig,
-            Effect::LoadAvailablePackages,
-            enqueue_system_task(state, "vpm --version", ["--version"]),
-        ],
+        Action::Init => {
+            vec![
+                Effect::LoadConfig,
+                Effect::LoadRepositories,
+                Effect::LoadTaskHistory,
+            ]
+        }
         Action::Tick => {
             state.tick_count = state.tick_count.saturating_add(1);
+            if !state.tick_count.is_multiple_of(PROJECT_CHECK_TICKS)
+                || state.project_check_pending
+                || state.projects.is_empty()
+            {
+                return vec![];
+            }
+            check_project_paths(state)
+        }
+        Action::Resize { height } => {
+            state.log_pane_rows = crate::app::state::log_pane_rows(height);
+            state.log_scroll = state.log_scroll.min(state.log_max_scroll());
             vec![]
         }
+        Action::ProjectDetailsLoaded(details) => {
+            let before = state.projects_missing_editor();
+            for project in details {
+                if let Some(version) = project.unity_version {
+                    state.project_versions.insert(project.path.clone(), version);
+                }
+                if let Some(manifest) = project.manifest {
+                    state.project_manifests.insert(project.path, manifest);
+                }
+            }
+            warn_missing_editors(state, &before);
+            vec![]
+        }
+        Action::ProjectPathsChecked(offline) => {
+            state.project_check_pending = false;
+            if !state.project_paths_known {
+                return on_initial_paths_checked(state, offline);
+            }
+            let mut effects = Vec::new();
+            let mut recovered = Vec::new();
+            let selected = state.selected_project().map(|p| p.path.clone());
+            for project in state.projects.clone() {
+                let was_offline = state.offline_projects.contains(&project.path);
+                let is_offline = offline.contains(&project.path);
+                if was_offline && !is_offline {
+                    state.push_log(
+                        LogSource::Projects,
+                        LogLevel::Success,
+                        format!("{} is back online", project.display_name),
+                    );
+                    recovered.push(project.path.clone());
+                    if selected.as_ref() == Some(&project.path) {
+                        effects.push(Effect::ReadManifest {
+                            project_path: project.path.clone(),
+                        });
+                    }
+                } else if !was_offline && is_offline {
+                    state.push_log(
+                        LogSource::Projects,
+                        LogLevel::Warn,
+                        format!("{} went offline", project.display_name),
+                    );
+                }
+            }
+            state.offline_projects = offline
+                .into_iter()
+                .filter(|path| state.projects.iter().any(|p| &p.path == path))
+                .collect();
+            if !recovered.is_empty() {
+                effects.push(Effect::ReadProjectDetails(recovered));
+            }
+            effects
+        }
         Action::Key(key) => on_key(state, key),
         Action::ConfigLoaded(result) => {
             match result {
                 Ok(config) => {
-                    let original_count = config.projects.len();
-                    state.projects = config
-                        .projects
-                        .into_iter()
-                        .filter(|p| p.path.exists())
-                        .collect::<Vec<_>>();
+                    state.repo_priority = config.repo_priority;
+                    state.disabled_repos = config.disabled_repos;
+                    state.vpm_settings = config.vpm;
+                    state.max_concurrent_tasks = config.max_concurrent_tasks;
+                    state.scan_settings = config.scan;
+                    state.watch_folders = config.watch_folders;
+                    state.unity_settings = config.unity;
+                    state.backup_settings = config.backup;
+                    state.project_table = config.projects_table;
+                    state.projects = config.projects;
+                    state.pin_projects_to_top();
+                    state.offline_projects.clear();
+                    state.project_paths_known = false;
                     state.selected_project_clamped();
-                    let removed = original_count.saturating_sub(state.projects.len());
                     state.status_line = format!("Loaded {} project(s)", state.projects.len());
-                    let mut effects = Vec::new();
-                    if removed > 0 {
-                        state.push_log(
-                            None,
-                            format!(
-                                "[config] removed {removed} missing project(s) from local list"
-                            ),
-                        );
-                        effects.push(Effect::SaveConfig(AppConfig {
-                            projects: state.projects.clone(),
-                        }));
-                    }
-                    if let Some(project) = state.selected_project() {
-                        effects.push(Effect::ReadManifest {
-                            project_path: project.path.clone(),
-                        });
+                    let mut effects = on_config_ready(state);
+                    if state.projects.is_empty() {
+                        state.project_paths_known = true;
+                    } else {
+                        effects.extend(check_project_paths(state));
                     }
+                    effects.extend(start_watch_scan(state));
                     return effects;
                 }
                 Err(err) => {
-                    state.push_log(None, format!("[config] load failed: {err}"));
+                    state.push_log(
+                        LogSource::Config,
+                        LogLevel::Error,
+                        format!("load failed: {err}"),
+                    );
                     state.status_line = "Config load failed; using defaults".to_string();
                 }
             }
-            vec![]
+            on_config_ready(state)
         }
         Action::ConfigSaved(result) => {
             if let Err(err) = result {
-                state.push_log(None, format!("[config] save failed: {err}"));
+                state.push_log(
+                    LogSource::Config,
+                    LogLevel::Error,
+                    format!("save failed: {err}"),
+                );
                 state.status_line = "Config save failed".to_string();
             }
             vec![]
         }
-        Action::FolderScanned(result) => match result {
-            Ok(paths) => {
-                let mut added = 0;
-                for path in paths {
-                    if state.projects.iter().any(|p| p.path == path) {
-                        continue;
-                    }
-                    let display_name = path
-                        .file_name()
-                        .map(|v| v.to_string_lossy().to_string())
-                        .filter(|v| !v.is_empty())
-                        .unwrap_or_else(|| path.to_string_lossy().to_string());
-                    state.projects.push(ProjectMeta {
-                        path,
-                        display_name,
-                        tags: vec![],
-                        last_opened: None,
-                    });
-                    added += 1;
+        Action::AddProject(path) => add_project(state, path),
+        Action::ScanProgress {
+            scan_id,
+            root,
+            visited,
+            dir,
+        } => {
+            if let Some(scan) = active_scan(state, scan_id, &root) {
+                scan.visited = visited;
+                scan.current = Some(dir);
+            }
+            vec![]
+        }
+        Action::ProjectDiscovered {
+            scan_id,
+            root,
+            project,
+        } => {
+            let registered = state.projects.iter().any(|p| p.path == project.path);
+            if let Some(scan) = active_scan(state, scan_id, &root) {
+                // Nested watch folders report the same project twice.
+                if scan.seen.contains(&project.path) {
+                    return vec![];
                 }
-                state.selected_project_clamped();
-                state.status_line = format!("Add folder scan complete: {added} project(s) added");
-                vec![Effect::SaveConfig(AppConfig {
-                    projects: state.projects.clone(),
-                })]
+                scan.seen.push(project.path.clone());
+                if scan.watch && registered {
+                    return vec![];
+                }
+                scan.found.push(project);
+                scan.chosen.push(!registered);
             }
-            Err(err) => {
-                state.status_line = "Folder scan failed".to_string();
-                state.push_log(None, format!("[add] folder scan failed: {err}"));
-                vec![]
+            vec![]
+        }
+        Action::ScanFinished {
+            scan_id,
+            root,
+            result,
+        } => {
+            let Some(scan) = active_scan(state, scan_id, &root) else {
+                return vec![];
+            };
+            scan.pending.retain(|r| r != &root);
+            let watch = scan.watch;
+            match result {
+                Ok(found) => {
+                    state.push_log(
+                        LogSource::Projects,
+                        LogLevel::Info,
+                        format!("scan of {} found {found} project(s)", root.display()),
+                    );
+                    if watch {
+                        flag_offline_projects(state, &root);
+                    }
+                }
+                Err(err) if watch => state.push_log(
+                    LogSource::Projects,
+                    LogLevel::Warn,
+                    format!("watch folder unavailable: {err}"),
+                ),
+                Err(err) => {
+                    state.project_scan = None;
+                    state.status_line = "Folder scan failed".to_string();
+                    state.push_log(
+                        LogSource::Projects,
+                        LogLevel::Error,
+                        format!("folder scan failed: {err}"),
+                    );
+                    return start_queued_watch_scan(state);
+                }
             }
-        },
+            on_scan_progressed(state)
+        }
         Action::AvailablePackagesLoaded(result) => {
             match result {
                 Ok(packages) => {
@@ -107,43 +225,167 @@ pub fn reduce(state: &mut AppState, action: Action) -> Vec<Effect> {
                     );
                 }
                 Err(err) => {
-                    state.push_log(None, format!("[packages] load failed: {err}"));
+                    state.push_log(
+                        LogSource::Packages,
+                        LogLevel::Error,
+                        format!("load failed: {err}"),
+                    );
                     state.status_line = "Available package load failed".to_string();
                 }
             }
             vec![]
         }
-        Action::ManifestLoaded(result) => {
+        Action::ManifestLoaded {
+            project_path,
+            result,
+        } => {
+            let selected = state
+                .selected_project()
+                .is_some_and(|p| p.path == project_path);
             match result {
                 Ok(summary) => {
-                    state.selected_project_manifest = Some(summary);
-                    state.selected_manifest_package_clamped();
+                    state
+                        .project_manifests
+                        .insert(project_path.clone(), summary.clone());
+                    verify_migration(state, &project_path, Some(&summary));
+                    if selected {
+                        state.selected_project_manifest = Some(summary);
+                        state.selected_manifest_package_clamped();
+                    }
+                }
+                Err(err) => {
+                    verify_migration(state, &project_path, None);
+                    if selected {
+                        state.selected_project_manifest = None;
+                        state.selected_manifest_package = 0;
+                    }
+                    state.push_log(
+                        LogSource::Manifest,
+                        LogLevel::Error,
+                        format!("failed: {err}"),
+                    );
+                }
+            }
+            vec![]
+        }
+        Action::RepositoriesLoaded(result) => {
+            match result {
+                Ok(repos) => {
+                    state.repositories = repos;
+                    if state.selected_repository >= state.repositories.len() {
+                        state.selected_repository = state.repositories.len().saturating_sub(1);
+                    }
+                }
+                Err(err) => {
+                    state.push_log(
+                        LogSource::Repos,
+                        LogLevel::Error,
+                        format!("load failed: {err}"),
+                    );
+                    state.status_line = "Repository list load failed".to_string();
+                }
+            }
+            vec![]
+        }
+        Action::RepositoriesSaved(result) => {
+            state.repo_save_in_flight = false;
+            if let Err(err) = &result {
+                state.push_log(
+                    LogSource::Repos,
+                    LogLevel::Error,
+                    format!("save failed: {err}"),
+                );
+                state.status_line = "Repository settings save failed".to_string();
+            }
+            if std::mem::take(&mut state.repo_save_queued) {
+                return save_repositories(state);
+            }
+            let mut effects = match result {
+                Ok(()) => vec![Effect::LoadRepositories, load_available_packages(state)],
+                Err(_) => vec![Effect::LoadRepositories],
+            };
+            effects.extend(schedule_tasks(state));
+            effects
+        }
+        Action::TaskHistoryLoaded(result) => {
+            match result {
+                Ok(mut entries) => {
+                    entries.retain(|e| e.session != state.session_id);
+                    entries.append(&mut state.task_history);
+                    state.task_history = entries;
+                }
+                Err(err) => {
+                    state.push_log(
+                        LogSource::History,
+                        LogLevel::Error,
+                        format!("load failed: {err}"),
+                    );
+                }
+            }
+            vec![]
+        }
+        Action::TaskHistorySaved(result) => {
+            match result {
+                Ok(()) => state.push_log(LogSource::History, LogLevel::Info, "writes resumed"),
+                Err(err) => {
+                    state.push_log(
+                        LogSource::History,
+                        LogLevel::Error,
+                        format!("save failed: {err}"),
+                    );
+                    state.status_line = "Task history save failed".to_string();
                 }
+            }
+            vec![]
+        }
+        Action::HistoryLogLoaded(result) => {
+            state.history_log = match result {
+                Ok(lines) if lines.is_empty() => vec!["(no output recorded)".to_string()],
+                Ok(lines) => lines,
+                Err(err) => vec![format!("failed to read log: {err}")],
+            };
+            vec![]
+        }
+        Action::LogsExported(result) => {
+            match result {
+                Ok(path) => state.status_line = format!("Exported logs to {}", path.display()),
                 Err(err) => {
-                    state.selected_project_manifest = None;
-                    state.selected_manifest_package = 0;
-                    state.push_log(None, format!("[manifest] failed: {err}"));
+                    state.status_line = "Log export failed".to_string();
+                    state.push_log(
+                        LogSource::Logs,
+                        LogLevel::Error,
+                        format!("export failed: {err}"),
+                    );
                 }
             }
             vec![]
         }
+        Action::ProjectBackedUp {
+            project_path,
+            result,
+            skipped,
+        } => on_project_backed_up(state, project_path, result, skipped),
+        Action::BackupsListed {
+            project_path,
+            result,
+        } => on_backups_listed(state, project_path, result),
+        Action::BackupRestored { backup, result } => on_backup_restored(state, backup, result),
+        Action::UnityOpened {
+            project_path,
+            result,
+        } => on_unity_opened(state, project_path, result),
         Action::TaskOutput {
             task_id,
             stream,
             line,
         } => {
-            let prefix = match stream {
-                OutputStream::Stdout => "out",
-                OutputStream::Stderr => "err",
-            };
-            state.push_log(Some(task_id), format!("[{task_id}:{prefix}] {line}"));
+            state.push_task_output(task_id, stream, line.clone());
+            let effects = vec![append_last_log(state, task_id)];
 
-            if task_is_label(state, task_id, "vpm --version")
-                && matches!(stream, OutputStream::Stdout)
-            {
+            if task_is_label(state, task_id, "vpm --version") && stream == OutputStream::Stdout {
                 state.system_checks.vpm_version = Some(line);
             }
-            vec![]
+            effects
         }
         Action::TaskDone {
             task_id,
@@ -152,10 +394,14 @@ pub fn reduce(state: &mut AppState, action: Action) -> Vec<Effect> {
             exit_code,
             error,
         } => {
+            let now = state.now;
             let mut next_effects = Vec::new();
             let mut deferred_log: Option<String> = None;
+            let mut config_changed = false;
+            let mut editors_listed = None;
             if let Some(task) = state.tasks.iter_mut().find(|t| t.id == task_id) {
                 task.exit_code = exit_code;
+                task.finished_at = Some(now);
                 task.error = error.clone();
                 task.state = if cancelled {
                     TaskState::Cancelled
@@ -171,17 +417,23 @@ pub fn reduce(state: &mut AppState, action: Action) -> Vec<Effect> {
                 if task.label == "vpm check unity" {
                     state.system_checks.unity_check = Some(task_state_text(task));
                 }
-                if task.label == "vpm list unity" {
+                if task.label == "vpm list unity" && success {
                     let lines = state
                         .logs
                         .iter()
-                        .filter(|l| l.task_id == Some(task_id))
+                        .filter(|l| {
+                            l.task_id() == Some(task_id) && l.stream == Some(OutputStream::Stdout)
+                        })
                         .map(|l| l.text.clone())
                         .collect::<Vec<_>>();
-                    state.system_checks.unity_list = lines;
+                    editors_listed = Some(parse_installed_editors(&lines));
                 }
                 if task.label.starts_with("vpm add repo ") {
-                    next_effects.push(Effect::LoadAvailablePackages);
+                    next_effects.push(Effect::LoadAvailablePackages {
+                        repo_priority: state.repo_priority.clone(),
+                        disabled_repos: state.disabled_repos.clone(),
+                    });
+                    next_effects.push(Effect::LoadRepositories);
                 }
                 if success {
                     if let Some(project) = task.pending_add_project.clone() {
@@ -189,9 +441,7 @@ pub fn reduce(state: &mut AppState, action: Action) -> Vec<Effect> {
                             if !state.projects.iter().any(|p| p.path == project.path) {
                                 state.projects.push(project.clone());
                                 state.selected_project = state.projects.len() - 1;
-                                next_effects.push(Effect::SaveConfig(AppConfig {
-                                    projects: state.projects.clone(),
-                                }));
+                                config_changed = true;
                             }
                         } else {
                             deferred_log = Some(format!(
@@ -206,19 +456,33 @@ pub fn reduce(state: &mut AppState, action: Action) -> Vec<Effect> {
                     next_effects.push(Effect::ReadManifest { project_path: path });
                 }
             }
+            if let Some(editors) = editors_listed {
+                let before = state.projects_missing_editor();
+                state.system_checks.installed_editors = Some(editors);
+                warn_missing_editors(state, &before);
+            }
+            if config_changed {
+                next_effects.push(Effect::SaveConfig(state.app_config()));
+            }
             if let Some(line) = deferred_log {
-                state.push_log(Some(task_id), line);
+                next_effects.push(log_task(state, task_id, LogLevel::Warn, line));
             }
 
-            let message = if cancelled {
-                format!("Task {task_id} cancelled")
+            let (outcome, level) = if cancelled {
+                ("cancelled", LogLevel::Warn)
             } else if success {
-                format!("Task {task_id} done")
+                ("done", LogLevel::Success)
             } else {
-                format!("Task {task_id} failed")
+                ("failed", LogLevel::Error)
             };
-            state.status_line = message.clone();
-            state.push_log(Some(task_id), message);
+            state.status_line = format!("Task {task_id} {outcome}");
+            next_effects.push(log_task(state, task_id, level, outcome));
+            on_migration_task_done(state, task_id, success && !cancelled);
+            next_effects.extend(record_task_history(state, task_id));
+            if std::mem::take(&mut state.repo_save_queued) {
+                next_effects.extend(save_repositories(state));
+            }
+            next_effects.extend(schedule_tasks(state));
             next_effects
         }
     }
@@ -230,18 +494,49 @@ fn on_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect>
         return vec![];
     }
 
+    if state.version_picker.is_some() {
+        return on_version_picker_input(state, key);
+    }
+    if state.backup_browser.is_some() {
+        return on_backup_browser_input(state, key);
+    }
     if state.add_project_mode {
         return on_add_project_input(state, key);
     }
     if state.add_folder_mode {
         return on_add_folder_input(state, key);
     }
+    if state.scan_ignore_mode {
+        return on_scan_ignore_input(state, key);
+    }
     if state.search_mode {
         return on_search_input(state, key);
     }
+    if state.tag_edit.is_some() {
+        return on_tag_input(state, key);
+    }
+    if state.rename_project_mode {
+        return on_rename_project_input(state, key);
+    }
     if state.add_repo_mode {
         return on_add_repo_input(state, key);
     }
+    if state.repo_header_mode {
+        return on_repo_header_input(state, key);
+    }
+    if state.pending_repo_removal.is_some() {
+        return on_repo_removal_confirm(state, key);
+    }
+    if state.pending_project_removal.is_some() {
+        return on_project_removal_confirm(state, key);
+    }
+    if state
+        .migration
+        .as_ref()
+        .is_some_and(|m| m.stage == MigrationStage::Confirm)
+    {
+        return on_migration_confirm(state, key);
+    }
     if state.add_package_mode {
         return on_add_package_input(state, key);
     }
@@ -251,6 +546,12 @@ fn on_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect>
     if state.new_project_mode {
         return on_new_project_input(state, key);
     }
+    if state.log_search_mode {
+        return on_log_search_input(state, key);
+    }
+    if state.log_focus {
+        return on_log_pane_key(state, key);
+    }
 
     match key.code {
         KeyCode::Char('q') => {
@@ -269,13 +570,17 @@ fn on_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect>
             state.screen = state.screen.prev();
             vec![]
         }
-        KeyCode::Up => {
-            state.log_scroll = state.log_scroll.saturating_sub(1);
+        KeyCode::Char('L') => {
+            state.log_focus = true;
             vec![]
         }
-        KeyCode::Down => {
-            let max_scroll = (state.logs.len() as u16).saturating_sub(1);
-            state.log_scroll = state.log_scroll.saturating_add(1).min(max_scroll);
+        KeyCode::Up
+        | KeyCode::Down
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Home
+        | KeyCode::End => {
+            scroll_logs(state, key.code);
             vec![]
         }
         _ => match state.screen {
@@ -283,185 +588,654 @@ fn on_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect>
             Screen::Add => on_add_key(state, key),
             Screen::Projects => on_projects_key(state, key),
             Screen::Manage => on_manage_key(state, key),
+            Screen::Repos => on_repos_key(state, key),
+            Screen::Tasks => on_tasks_key(state, key),
             Screen::Settings => on_settings_key(state, key),
         },
     }
 }
 
-fn on_search_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+fn on_log_pane_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
     match key.code {
-        KeyCode::Esc => state.search_mode = false,
-        KeyCode::Enter => state.search_mode = false,
-        KeyCode::Backspace => {
-            state.search_query.pop();
+        KeyCode::Esc | KeyCode::Char('L') => state.log_focus = false,
+        KeyCode::Char('q') => state.should_quit = true,
+        KeyCode::Char('k') => scroll_logs(state, KeyCode::Up),
+        KeyCode::Char('j') => scroll_logs(state, KeyCode::Down),
+        KeyCode::Up
+        | KeyCode::Down
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Home
+        | KeyCode::End => scroll_logs(state, key.code),
+        KeyCode::Char('f') => {
+            state.log_follow = !state.log_follow;
+            if !state.log_follow {
+                state.log_scroll = state.log_max_scroll();
+            }
         }
-        KeyCode::Char(c) => {
-            if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                state.search_query.push(c);
+        KeyCode::Char('/') => {
+            state.log_search_mode = true;
+            state.log_search_query.clear();
+            state.log_match = 0;
+        }
+        KeyCode::Char('n') => step_log_match(state, true),
+        KeyCode::Char('N') => step_log_match(state, false),
+        KeyCode::Char('t') => {
+            let ids = state.log_task_ids();
+            state.log_filter.task_id = match state.log_filter.task_id {
+                None => ids.first().copied(),
+                Some(current) => ids.into_iter().find(|id| *id > current),
+            };
+            on_log_filter_changed(state);
+        }
+        KeyCode::Char('s') => {
+            state.log_filter.stream = state.log_filter.stream.next();
+            on_log_filter_changed(state);
+        }
+        KeyCode::Char('e') => {
+            state.log_filter.min_level = match state.log_filter.min_level {
+                None => Some(LogLevel::Warn),
+                Some(LogLevel::Warn) => Some(LogLevel::Error),
+                Some(_) => None,
+            };
+            on_log_filter_changed(state);
+        }
+        KeyCode::Char('x') => {
+            let lines = state
+                .visible_logs()
+                .into_iter()
+                .map(|l| l.export_line())
+                .collect::<Vec<_>>();
+            if lines.is_empty() {
+                state.status_line = "No log lines to export".to_string();
+                return vec![];
             }
+            state.status_line = format!("Exporting {} log line(s)...", lines.len());
+            return vec![Effect::ExportLogs { lines }];
         }
         _ => {}
     }
     vec![]
 }
 
-fn on_add_project_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+fn on_log_search_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
     match key.code {
         KeyCode::Esc => {
-            state.add_project_mode = false;
-            state.add_project_input.clear();
-            vec![]
+            state.log_search_mode = false;
+            state.log_search_query.clear();
         }
+        KeyCode::Enter => state.log_search_mode = false,
         KeyCode::Backspace => {
-            state.add_project_input.pop();
-            vec![]
+            state.log_search_query.pop();
+            jump_to_first_log_match(state);
         }
-        KeyCode::Enter => {
-            let input = state.add_project_input.trim();
-            if input.is_empty() {
-                state.status_line = "Project path is empty".to_string();
-                return vec![];
-            }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.log_search_query.push(c);
+            jump_to_first_log_match(state);
+        }
+        _ => {}
+    }
+    vec![]
+}
 
-            let path = PathBuf::from(input);
-            if state.projects.iter().any(|p| p.path == path) {
-                state.status_line = "Project already exists".to_string();
-                state.add_project_mode = false;
-                state.add_project_input.clear();
-                return vec![];
-            }
+fn scroll_logs(state: &mut AppState, code: KeyCode) {
+    let max = state.log_max_scroll();
+    let current = state.log_scroll_offset();
+    let next = match code {
+        KeyCode::Up => current.saturating_sub(1),
+        KeyCode::Down => current + 1,
+        KeyCode::PageUp => current.saturating_sub(state.log_pane_rows),
+        KeyCode::PageDown => current + state.log_pane_rows,
+        KeyCode::Home => 0,
+        _ => max,
+    };
+    state.log_scroll = next.min(max);
+    state.log_follow = state.log_scroll >= max;
+}
 
-            let display_name = path
-                .file_name()
-                .map(|v| v.to_string_lossy().to_string())
-                .filter(|v| !v.is_empty())
-                .unwrap_or_else(|| input.to_string());
+fn on_log_filter_changed(state: &mut AppState) {
+    state.log_match = 0;
+    state.log_scroll = state.log_scroll.min(state.log_max_scroll());
+}
 
-            state.projects.push(ProjectMeta {
-                path: path.clone(),
-                display_name,
-                tags: vec![],
-                last_opened: None,
-            });
-            state.selected_project = state.projects.len() - 1;
-            state.add_project_mode = false;
-            state.add_project_input.clear();
-            state.status_line = "Project added".to_string();
+fn jump_to_first_log_match(state: &mut AppState) {
+    let top = state.log_scroll_offset();
+    let matches = state.log_matches();
+    state.log_match = matches.iter().position(|idx| *idx >= top).unwrap_or(0);
+    if let Some(idx) = matches.get(state.log_match) {
+        reveal_log_line(state, *idx);
+    }
+}
 
-            vec![
-                Effect::SaveConfig(AppConfig {
-                    projects: state.projects.clone(),
-                }),
-                Effect::ReadManifest { project_path: path },
-            ]
+fn step_log_match(state: &mut AppState, forward: bool) {
+    let matches = state.log_matches();
+    if matches.is_empty() {
+        state.status_line = "No log matches".to_string();
+        return;
+    }
+    let current = state.log_match.min(matches.len() - 1);
+    state.log_match = if forward {
+        (current + 1) % matches.len()
+    } else {
+        (current + matches.len() - 1) % matches.len()
+    };
+    reveal_log_line(state, matches[state.log_match]);
+}
+
+fn reveal_log_line(state: &mut AppState, idx: usize) {
+    let top = state.log_scroll_offset();
+    if idx >= top && idx < top + state.log_pane_rows {
+        return;
+    }
+    state.log_scroll = idx
+        .saturating_sub(state.log_pane_rows / 2)
+        .min(state.log_max_scroll());
+    state.log_follow = false;
+}
+
+fn on_search_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    match key.code {
+        KeyCode::Esc => state.search_mode = false,
+        KeyCode::Enter => state.search_mode = false,
+        KeyCode::Backspace => {
+            state.search_query.pop();
         }
-        KeyCode::Char(c) => {
-            if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                state.add_project_input.push(c);
-            }
-            vec![]
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.search_query.push(c);
         }
-        _ => vec![],
+        _ => {}
     }
+    keep_selection_visible(state);
+    vec![]
 }
 
-fn on_add_folder_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+fn on_rename_project_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
     match key.code {
         KeyCode::Esc => {
-            state.add_folder_mode = false;
-            state.add_folder_input.clear();
+            state.rename_project_mode = false;
+            state.rename_project_input.clear();
             vec![]
         }
         KeyCode::Backspace => {
-            state.add_folder_input.pop();
+            state.rename_project_input.pop();
             vec![]
         }
         KeyCode::Enter => {
-            let root = state.add_folder_input.trim().to_string();
-            if root.is_empty() {
-                state.status_line = "Folder path is empty".to_string();
+            let name = state.rename_project_input.trim().to_string();
+            if name.is_empty() {
+                state.status_line = "Project name is empty".to_string();
                 return vec![];
             }
-            state.add_folder_mode = false;
-            state.add_folder_input.clear();
-            vec![Effect::ScanProjectsFolder {
-                root: PathBuf::from(root),
-            }]
+            state.rename_project_mode = false;
+            state.rename_project_input.clear();
+            let Some(project) = state.projects.get_mut(state.selected_project) else {
+                return vec![];
+            };
+            let old = std::mem::replace(&mut project.display_name, name.clone());
+            state.status_line = format!("Renamed {old} to {name}");
+            vec![Effect::SaveConfig(state.app_config())]
         }
-        KeyCode::Char(c) => {
-            if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                state.add_folder_input.push(c);
-            }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.rename_project_input.push(c);
             vec![]
         }
         _ => vec![],
     }
 }
 
-fn on_add_repo_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+fn on_tag_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
     match key.code {
         KeyCode::Esc => {
-            state.add_repo_mode = false;
-            state.add_repo_input.clear();
+            state.tag_edit = None;
+            state.tag_input.clear();
             vec![]
         }
         KeyCode::Backspace => {
-            state.add_repo_input.pop();
+            state.tag_input.pop();
             vec![]
         }
-        KeyCode::Enter => {
-            let repo = state.add_repo_input.trim().to_string();
-            if repo.is_empty() {
-                state.status_line = "Repo URL is empty".to_string();
-                return vec![];
+        KeyCode::Tab => {
+            if let Some(tag) = state.tag_suggestions().into_iter().next() {
+                state.tag_input = tag;
             }
-            state.add_repo_mode = false;
-            state.add_repo_input.clear();
-            vec![enqueue_project_task(
-                state,
-                format!("vpm add repo {repo}"),
-                vec!["add".to_string(), "repo".to_string(), repo],
-                None,
-                None,
-            )]
+            vec![]
         }
-        KeyCode::Char(c) => {
-            if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                state.add_repo_input.push(c);
-            }
+        KeyCode::Enter => apply_tag_edit(state),
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.tag_input.push(c);
             vec![]
         }
         _ => vec![],
     }
 }
 
-fn on_add_package_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
-    match key.code {
-        KeyCode::Esc => {
-            state.add_package_mode = false;
-            state.add_package_input.clear();
-            vec![]
-        }
-        KeyCode::Backspace => {
-            state.add_package_input.pop();
-            vec![]
+fn apply_tag_edit(state: &mut AppState) -> Vec<Effect> {
+    let tag = state.tag_input.trim().to_string();
+    if tag.is_empty() {
+        state.status_line = "Tag is empty".to_string();
+        return vec![];
+    }
+    let Some(edit) = state.tag_edit.take() else {
+        return vec![];
+    };
+    state.tag_input.clear();
+    let Some(project) = state.projects.get_mut(state.selected_project) else {
+        return vec![];
+    };
+    let name = project.display_name.clone();
+    match edit {
+        TagEdit::Add => {
+            if project.tags.contains(&tag) {
+                state.status_line = format!("{name} is already tagged '{tag}'");
+                return vec![];
+            }
+            project.tags.push(tag.clone());
+            state.status_line = format!("Tagged {name} with '{tag}'");
         }
-        KeyCode::Enter => {
-            let package = state.add_package_input.trim().to_string();
-            if package.is_empty() {
-                state.status_line = "Package name is empty".to_string();
+        TagEdit::Remove => {
+            if !project.tags.contains(&tag) {
+                state.status_line = format!("{name} has no tag '{tag}'");
                 return vec![];
             }
+            project.tags.retain(|t| t != &tag);
+            state.status_line = format!("Removed tag '{tag}' from {name}");
+        }
+    }
+    if state
+        .tag_filter
+        .as_ref()
+        .is_some_and(|filter| !state.all_tags().contains(filter))
+    {
+        state.tag_filter = None;
+    }
+    keep_selection_visible(state);
+    vec![Effect::SaveConfig(state.app_config())]
+}
 
-            if let Some(project) = state.selected_project() {
-                let project_path = project.path.clone();
-                state.add_package_mode = false;
-                state.add_package_input.clear();
-                return vec![enqueue_project_task(
-                    state,
-                    format!("vpm add package {package}"),
-                    vec![
-                        "add".to_string(),
+fn keep_selection_visible(state: &mut AppState) {
+    let visible = state
+        .visible_projects()
+        .into_iter()
+        .map(|p| p.path.clone())
+        .collect::<Vec<_>>();
+    let selected = state.selected_project().map(|p| p.path.clone());
+    if selected.is_some_and(|path| visible.contains(&path)) {
+        return;
+    }
+    if let Some(first) = visible.first() {
+        select_project_path(state, first);
+    }
+}
+
+// A search or tag filter can hide the selected project; keys that act on the
+// selection must not reach a row the user cannot see.
+fn selection_visible(state: &AppState) -> bool {
+    state.selected_project().is_some_and(|selected| {
+        state
+            .visible_projects()
+            .iter()
+            .any(|p| p.path == selected.path)
+    })
+}
+
+fn move_project_selection(state: &mut AppState, forward: bool) {
+    let visible = state
+        .visible_projects()
+        .into_iter()
+        .map(|p| p.path.clone())
+        .collect::<Vec<_>>();
+    if visible.is_empty() {
+        return;
+    }
+    let current = state
+        .selected_project()
+        .and_then(|selected| visible.iter().position(|p| p == &selected.path));
+    let next = match current {
+        Some(idx) if forward => (idx + 1).min(visible.len() - 1),
+        Some(idx) => idx.saturating_sub(1),
+        None => 0,
+    };
+    select_project_path(state, &visible[next]);
+}
+
+fn move_selected_project(state: &mut AppState, up: bool) -> Vec<Effect> {
+    if state.group_by_tag {
+        state.status_line = "Reordering is disabled while grouped by tag".to_string();
+        return vec![];
+    }
+    if state.project_table.sort.is_some() {
+        state.status_line = "Reordering needs manual order; press [s] until it is back".to_string();
+        return vec![];
+    }
+    let Some(project) = state.selected_project().cloned() else {
+        state.status_line = "No project selected".to_string();
+        return vec![];
+    };
+    let visible = state
+        .visible_projects()
+        .into_iter()
+        .map(|p| (p.path.clone(), p.pinned))
+        .collect::<Vec<_>>();
+    let Some(pos) = visible.iter().position(|(path, _)| path == &project.path) else {
+        return vec![];
+    };
+    let neighbour = if up {
+        pos.checked_sub(1).and_then(|idx| visible.get(idx))
+    } else {
+        visible.get(pos + 1)
+    };
+    let Some((other, _)) = neighbour.filter(|(_, pinned)| *pinned == project.pinned) else {
+        return vec![];
+    };
+    let Some(other_idx) = state.projects.iter().position(|p| &p.path == other) else {
+        return vec![];
+    };
+    state.projects.swap(state.selected_project, other_idx);
+    state.selected_project = other_idx;
+    vec![Effect::SaveConfig(state.app_config())]
+}
+
+fn select_project_path(state: &mut AppState, path: &Path) {
+    if let Some(idx) = state.projects.iter().position(|p| p.path == path) {
+        state.selected_project = idx;
+    }
+}
+
+fn cycle_tag_filter(state: &mut AppState, forward: bool) {
+    let tags = state.all_tags();
+    if tags.is_empty() {
+        state.status_line = "No tags yet; add one with [t]".to_string();
+        return;
+    }
+    let current = state
+        .tag_filter
+        .as_ref()
+        .and_then(|tag| tags.iter().position(|t| t == tag));
+    state.tag_filter = match (current, forward) {
+        (None, true) => tags.first().cloned(),
+        (None, false) => tags.last().cloned(),
+        (Some(idx), true) => tags.get(idx + 1).cloned(),
+        (Some(0), false) => None,
+        (Some(idx), false) => tags.get(idx - 1).cloned(),
+    };
+    state.status_line = match &state.tag_filter {
+        Some(tag) => format!("Showing projects tagged '{tag}'"),
+        None => "Showing all projects".to_string(),
+    };
+    keep_selection_visible(state);
+}
+
+fn on_add_project_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    match key.code {
+        KeyCode::Esc => {
+            state.add_project_mode = false;
+            state.add_project_input.clear();
+            vec![]
+        }
+        KeyCode::Backspace => {
+            state.add_project_input.pop();
+            vec![]
+        }
+        KeyCode::Enter => {
+            let input = state.add_project_input.trim();
+            if input.is_empty() {
+                state.status_line = "Project path is empty".to_string();
+                return vec![];
+            }
+
+            let path = crate::services::fs::normalize_path(Path::new(input));
+            state.add_project_mode = false;
+            state.add_project_input.clear();
+            add_project(state, path)
+        }
+        KeyCode::Char(c) => {
+            if !key.modifiers.contains(KeyModifiers::CONTROL) {
+                state.add_project_input.push(c);
+            }
+            vec![]
+        }
+        _ => vec![],
+    }
+}
+
+fn add_project(state: &mut AppState, path: PathBuf) -> Vec<Effect> {
+    if state.projects.iter().any(|p| p.path == path) {
+        state.status_line = "Project already exists".to_string();
+        return vec![];
+    }
+
+    state.projects.push(ProjectMeta::from_path(path.clone()));
+    state.selected_project = state.projects.len() - 1;
+    state.status_line = "Project added".to_string();
+
+    vec![
+        Effect::SaveConfig(state.app_config()),
+        Effect::ReadManifest { project_path: path },
+    ]
+}
+
+fn on_scan_ignore_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    match key.code {
+        KeyCode::Esc => {
+            state.scan_ignore_mode = false;
+            state.scan_ignore_input.clear();
+            vec![]
+        }
+        KeyCode::Backspace => {
+            state.scan_ignore_input.pop();
+            vec![]
+        }
+        KeyCode::Enter => {
+            let pattern = state.scan_ignore_input.trim().to_string();
+            if pattern.is_empty() {
+                state.status_line = "Ignore pattern is empty".to_string();
+                return vec![];
+            }
+            state.scan_ignore_mode = false;
+            state.scan_ignore_input.clear();
+            let ignore = &mut state.scan_settings.ignore;
+            if ignore.contains(&pattern) {
+                ignore.retain(|p| p != &pattern);
+                state.status_line = format!("No longer ignoring {pattern}");
+            } else {
+                ignore.push(pattern.clone());
+                state.status_line = format!("Ignoring {pattern}");
+            }
+            vec![Effect::SaveConfig(state.app_config())]
+        }
+        KeyCode::Char(c) => {
+            if !key.modifiers.contains(KeyModifiers::CONTROL) {
+                state.scan_ignore_input.push(c);
+            }
+            vec![]
+        }
+        _ => vec![],
+    }
+}
+
+fn on_add_folder_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    match key.code {
+        KeyCode::Esc => {
+            state.add_folder_mode = false;
+            state.watch_folder_mode = false;
+            state.add_folder_input.clear();
+            vec![]
+        }
+        KeyCode::Backspace => {
+            state.add_folder_input.pop();
+            vec![]
+        }
+        KeyCode::Enter => {
+            let root = state.add_folder_input.trim().to_string();
+            if root.is_empty() {
+                state.status_line = "Folder path is empty".to_string();
+                return vec![];
+            }
+            state.add_folder_mode = false;
+            state.add_folder_input.clear();
+            let root = crate::services::fs::normalize_path(Path::new(&root));
+            if std::mem::take(&mut state.watch_folder_mode) {
+                return add_watch_folder(state, root);
+            }
+            start_project_scan(state, root)
+        }
+        KeyCode::Char(c) => {
+            if !key.modifiers.contains(KeyModifiers::CONTROL) {
+                state.add_folder_input.push(c);
+            }
+            vec![]
+        }
+        _ => vec![],
+    }
+}
+
+fn on_add_repo_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    match key.code {
+        KeyCode::Esc => {
+            state.add_repo_mode = false;
+            state.add_repo_input.clear();
+            vec![]
+        }
+        KeyCode::Backspace => {
+            state.add_repo_input.pop();
+            vec![]
+        }
+        KeyCode::Enter => {
+            let repo = state.add_repo_input.trim().to_string();
+            if repo.is_empty() {
+                state.status_line = "Repo URL is empty".to_string();
+                return vec![];
+            }
+            state.add_repo_mode = false;
+            state.add_repo_input.clear();
+            add_repository(state, repo)
+        }
+        KeyCode::Char(c) => {
+            if !key.modifiers.contains(KeyModifiers::CONTROL) {
+                state.add_repo_input.push(c);
+            }
+            vec![]
+        }
+        _ => vec![],
+    }
+}
+
+fn on_repo_header_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    match key.code {
+        KeyCode::Esc => {
+            state.repo_header_mode = false;
+            state.repo_header_input.clear();
+            vec![]
+        }
+        KeyCode::Backspace => {
+            state.repo_header_input.pop();
+            vec![]
+        }
+        KeyCode::Enter => {
+            let input = state.repo_header_input.trim().to_string();
+            let Some((name, value)) = input.split_once(':') else {
+                state.status_line = "Header must be written as Name: value".to_string();
+                return vec![];
+            };
+            let (name, value) = (name.trim().to_string(), value.trim().to_string());
+            if name.is_empty() {
+                state.status_line = "Header name is empty".to_string();
+                return vec![];
+            }
+            let Some(repo) = state.repositories.get_mut(state.selected_repository) else {
+                state.status_line = "No repository selected".to_string();
+                return vec![];
+            };
+            if value.is_empty() {
+                repo.headers.remove(&name);
+                state.status_line = format!("Removed header {name}");
+            } else {
+                repo.headers.insert(name.clone(), value);
+                state.status_line = format!("Set header {name}");
+            }
+            state.repo_header_mode = false;
+            state.repo_header_input.clear();
+            save_repositories(state)
+        }
+        KeyCode::Char(c) => {
+            if !key.modifiers.contains(KeyModifiers::CONTROL) {
+                state.repo_header_input.push(c);
+            }
+            vec![]
+        }
+        _ => vec![],
+    }
+}
+
+fn on_repo_removal_confirm(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    let Some(url) = state.pending_repo_removal.take() else {
+        return vec![];
+    };
+    if key.code != KeyCode::Char('y') {
+        state.status_line = "Repository removal cancelled".to_string();
+        return vec![];
+    }
+    if let Some(repo) = state.repositories.iter().find(|r| r.url == url) {
+        let repo_key = repo.key().to_string();
+        state.disabled_repos.retain(|id| id != &repo_key);
+        state.repo_priority.retain(|id| id != &repo_key);
+    }
+    state.repositories.retain(|r| r.url != url);
+    if state.selected_repository >= state.repositories.len() {
+        state.selected_repository = state.repositories.len().saturating_sub(1);
+    }
+    state.status_line = format!("Removed repository {url}");
+    state.removed_repo_urls.push(url);
+    let mut effects = save_repositories(state);
+    effects.push(Effect::SaveConfig(state.app_config()));
+    effects
+}
+
+fn on_project_removal_confirm(
+    state: &mut AppState,
+    key: crossterm::event::KeyEvent,
+) -> Vec<Effect> {
+    let Some(path) = state.pending_project_removal.take() else {
+        return vec![];
+    };
+    if key.code != KeyCode::Char('y') {
+        state.status_line = "Project removal cancelled".to_string();
+        return vec![];
+    }
+    state.projects.retain(|p| p.path != path);
+    state.offline_projects.remove(&path);
+    state.selected_project_clamped();
+    state.status_line = format!("Removed {} from the list", path.display());
+    vec![Effect::SaveConfig(state.app_config())]
+}
+
+fn on_add_package_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    match key.code {
+        KeyCode::Esc => {
+            state.add_package_mode = false;
+            state.add_package_input.clear();
+            vec![]
+        }
+        KeyCode::Backspace => {
+            state.add_package_input.pop();
+            vec![]
+        }
+        KeyCode::Enter => {
+            let package = state.add_package_input.trim().to_string();
+            if package.is_empty() {
+                state.status_line = "Package name is empty".to_string();
+                return vec![];
+            }
+
+            if let Some(project) = state.selected_project() {
+                let project_path = project.path.clone();
+                state.add_package_mode = false;
+                state.add_package_input.clear();
+                return enqueue_project_task(
+                    state,
+                    format!("vpm add package {package}"),
+                    vec![
+                        "add".to_string(),
                         "package".to_string(),
                         package,
                         "-p".to_string(),
@@ -469,7 +1243,7 @@ fn on_add_package_input(state: &mut AppState, key: crossterm::event::KeyEvent) -
                     ],
                     Some(project_path),
                     None,
-                )];
+                );
             }
 
             state.status_line = "No project selected".to_string();
@@ -496,17 +1270,46 @@ fn on_available_package_search_input(
             state.available_package_search.pop();
             state.selected_available_package_clamped();
         }
-        KeyCode::Char(c) => {
-            if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                state.available_package_search.push(c);
-                state.selected_available_package_clamped();
-            }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.available_package_search.push(c);
+            state.selected_available_package_clamped();
         }
         _ => {}
     }
     vec![]
 }
 
+fn on_version_picker_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    let Some(picker) = state.version_picker.as_mut() else {
+        return vec![];
+    };
+    match key.code {
+        KeyCode::Esc => {
+            state.version_picker = None;
+            vec![]
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            if !picker.versions.is_empty() {
+                picker.selected = (picker.selected + 1).min(picker.versions.len() - 1);
+            }
+            vec![]
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            picker.selected = picker.selected.saturating_sub(1);
+            vec![]
+        }
+        KeyCode::Enter => {
+            let Some(version) = picker.selected_version().map(|v| v.version.clone()) else {
+                return vec![];
+            };
+            let package_id = picker.package_id.clone();
+            state.version_picker = None;
+            install_package_version(state, &package_id, &version)
+        }
+        _ => vec![],
+    }
+}
+
 fn on_new_project_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
     match key.code {
         KeyCode::Esc => {
@@ -540,6 +1343,7 @@ fn on_new_project_input(state: &mut AppState, key: crossterm::event::KeyEvent) -
                 display_name: name.clone(),
                 tags: vec![],
                 last_opened: None,
+                pinned: false,
             };
 
             state.new_project_mode = false;
@@ -547,13 +1351,13 @@ fn on_new_project_input(state: &mut AppState, key: crossterm::event::KeyEvent) -
             state.new_project_path_input.clear();
             state.screen = Screen::Projects;
 
-            vec![enqueue_project_task(
+            enqueue_project_task(
                 state,
                 format!("vpm new {name} {template} -p {path}"),
                 vec!["new".to_string(), name, template, "-p".to_string(), path],
                 Some(project_root),
                 Some(pending_project),
-            )]
+            )
         }
         KeyCode::Char(c) => {
             if !key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -588,23 +1392,377 @@ fn on_new_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effe
     }
 }
 
-fn on_add_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
-    match key.code {
-        KeyCode::Char('a') => {
-            state.add_project_mode = true;
-            state.add_project_input.clear();
-            vec![]
-        }
-        KeyCode::Char('f') => {
-            state.add_folder_mode = true;
+fn start_project_scan(state: &mut AppState, root: PathBuf) -> Vec<Effect> {
+    state.status_line = format!("Scanning {}...", root.display());
+    state.push_log(
+        LogSource::Projects,
+        LogLevel::Info,
+        format!(
+            "scanning {} (max depth {})",
+            root.display(),
+            state.scan_settings.max_depth
+        ),
+    );
+    let scan = ProjectScan::new(next_scan_id(state), root.clone());
+    let scan_id = scan.id;
+    state.project_scan = Some(scan);
+    vec![Effect::ScanProjectsFolder {
+        scan_id,
+        root,
+        settings: state.scan_settings.clone(),
+    }]
+}
+
+fn start_watch_scan(state: &mut AppState) -> Vec<Effect> {
+    if state.watch_folders.is_empty() {
+        return vec![];
+    }
+    state.status_line = format!(
+        "Rescanning {} watch folder(s)...",
+        state.watch_folders.len()
+    );
+    let mut roots = Vec::new();
+    for root in &state.watch_folders {
+        let root = crate::services::fs::normalize_path(root);
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+    scan_watch_roots(state, roots)
+}
+
+fn add_watch_folder(state: &mut AppState, root: PathBuf) -> Vec<Effect> {
+    if state
+        .watch_folders
+        .iter()
+        .any(|folder| crate::services::fs::normalize_path(folder) == root)
+    {
+        state.status_line = "Folder is already watched".to_string();
+        return vec![];
+    }
+    state.watch_folders.push(root.clone());
+    state.selected_watch_folder = state.watch_folders.len() - 1;
+    state.status_line = format!("Watching {}", root.display());
+    let mut effects = vec![Effect::SaveConfig(state.app_config())];
+    effects.extend(scan_watch_roots(state, vec![root]));
+    effects
+}
+
+// Watch roots join a watch scan that is already running or awaiting review;
+// a manual folder scan is left alone and the roots wait until it is cleared.
+fn scan_watch_roots(state: &mut AppState, roots: Vec<PathBuf>) -> Vec<Effect> {
+    let scan_id = match state.project_scan.as_mut() {
+        Some(scan) if scan.watch => scan.id,
+        Some(_) => {
+            for root in roots {
+                if !state.queued_watch_roots.contains(&root) {
+                    state.queued_watch_roots.push(root);
+                }
+            }
+            state.status_line =
+                "Watch folder scan queued until the current scan is cleared".to_string();
+            return vec![];
+        }
+        None => {
+            let scan_id = next_scan_id(state);
+            state.project_scan = Some(ProjectScan::watch(scan_id, Vec::new()));
+            scan_id
+        }
+    };
+    let settings = state.scan_settings.clone();
+    let Some(scan) = state.project_scan.as_mut() else {
+        return vec![];
+    };
+    let mut effects = Vec::new();
+    for root in roots {
+        if scan.pending.contains(&root) {
+            continue;
+        }
+        if !scan.roots.contains(&root) {
+            scan.roots.push(root.clone());
+        }
+        scan.pending.push(root.clone());
+        effects.push(Effect::ScanProjectsFolder {
+            scan_id,
+            root,
+            settings: settings.clone(),
+        });
+    }
+    effects
+}
+
+fn start_queued_watch_scan(state: &mut AppState) -> Vec<Effect> {
+    if state.queued_watch_roots.is_empty() {
+        return vec![];
+    }
+    let roots = std::mem::take(&mut state.queued_watch_roots);
+    scan_watch_roots(state, roots)
+}
+
+fn next_scan_id(state: &mut AppState) -> u64 {
+    let id = state.next_scan_id;
+    state.next_scan_id += 1;
+    id
+}
+
+// Events from a scan that was replaced or discarded carry a stale id and are
+// dropped, even when the new scan covers the same root.
+fn active_scan<'a>(
+    state: &'a mut AppState,
+    scan_id: u64,
+    root: &Path,
+) -> Option<&'a mut ProjectScan> {
+    state
+        .project_scan
+        .as_mut()
+        .filter(|scan| scan.id == scan_id && scan.pending.iter().any(|r| r == root))
+}
+
+fn check_project_paths(state: &mut AppState) -> Vec<Effect> {
+    state.project_check_pending = true;
+    vec![Effect::CheckProjectPaths(
+        state.projects.iter().map(|p| p.path.clone()).collect(),
+    )]
+}
+
+fn on_initial_paths_checked(state: &mut AppState, offline: Vec<PathBuf>) -> Vec<Effect> {
+    state.project_paths_known = true;
+    state.offline_projects = offline
+        .into_iter()
+        .filter(|path| state.projects.iter().any(|p| &p.path == path))
+        .collect();
+    if !state.offline_projects.is_empty() {
+        state.push_log(
+            LogSource::Config,
+            LogLevel::Warn,
+            format!(
+                "{} project(s) offline; kept in the list",
+                state.offline_projects.len()
+            ),
+        );
+    }
+    let mut effects = Vec::new();
+    if let Some(project) = state.selected_project() {
+        if !state.offline_projects.contains(&project.path) {
+            effects.push(Effect::ReadManifest {
+                project_path: project.path.clone(),
+            });
+        }
+    }
+    let online = state
+        .projects
+        .iter()
+        .map(|p| p.path.clone())
+        .filter(|path| !state.offline_projects.contains(path))
+        .collect::<Vec<_>>();
+    if !online.is_empty() {
+        effects.push(Effect::ReadProjectDetails(online));
+    }
+    effects
+}
+
+fn flag_offline_projects(state: &mut AppState, root: &Path) {
+    let found = state
+        .project_scan
+        .as_ref()
+        .map(|scan| scan.seen.clone())
+        .unwrap_or_default();
+    for path in &found {
+        state.offline_projects.remove(path);
+    }
+    let missing = state
+        .projects
+        .iter()
+        .filter(|p| p.path.starts_with(root) && !found.contains(&p.path) && !p.path.exists())
+        .map(|p| p.path.clone())
+        .collect::<Vec<_>>();
+    for path in missing {
+        if state.offline_projects.insert(path.clone()) {
+            state.push_log(
+                LogSource::Projects,
+                LogLevel::Warn,
+                format!("project folder disappeared: {}", path.display()),
+            );
+        }
+    }
+}
+
+fn on_scan_progressed(state: &mut AppState) -> Vec<Effect> {
+    let registered = state
+        .projects
+        .iter()
+        .map(|p| p.path.clone())
+        .collect::<Vec<_>>();
+    let Some(scan) = state.project_scan.as_mut() else {
+        return vec![];
+    };
+    if scan.is_running() {
+        return vec![];
+    }
+    scan.current = None;
+    let new = scan
+        .found
+        .iter()
+        .filter(|p| !registered.contains(&p.path))
+        .count();
+    if !scan.watch {
+        state.status_line = format!(
+            "Scan complete: {} project(s) found, {new} not yet added",
+            scan.found.len()
+        );
+    } else if new == 0 {
+        state.project_scan = None;
+        state.status_line = "Watch folders: no new projects".to_string();
+        return start_queued_watch_scan(state);
+    } else {
+        state.status_line =
+            format!("Watch folders: {new} new project(s) found; review them on the Add screen");
+    }
+    vec![]
+}
+
+fn on_project_scan_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    let registered = state
+        .projects
+        .iter()
+        .map(|p| p.path.clone())
+        .collect::<Vec<_>>();
+    let Some(scan) = state.project_scan.as_mut() else {
+        return vec![];
+    };
+    match key.code {
+        KeyCode::Esc => {
+            state.project_scan = None;
+            state.status_line = "Scan results discarded".to_string();
+            start_queued_watch_scan(state)
+        }
+        KeyCode::Char('j') => {
+            if !scan.found.is_empty() {
+                scan.selected = (scan.selected + 1).min(scan.found.len() - 1);
+            }
+            vec![]
+        }
+        KeyCode::Char('k') => {
+            scan.selected = scan.selected.saturating_sub(1);
+            vec![]
+        }
+        KeyCode::Char(' ') => {
+            if let Some(project) = scan.found.get(scan.selected) {
+                if !registered.contains(&project.path) {
+                    scan.chosen[scan.selected] = !scan.chosen[scan.selected];
+                }
+            }
+            vec![]
+        }
+        KeyCode::Char('A') => {
+            let select = !scan.chosen.iter().any(|c| *c);
+            for (project, chosen) in scan.found.iter().zip(scan.chosen.iter_mut()) {
+                *chosen = select && !registered.contains(&project.path);
+            }
+            vec![]
+        }
+        KeyCode::Enter => {
+            let paths = scan.chosen_paths();
+            state.project_scan = None;
+            let mut added = 0;
+            for path in paths {
+                if registered.contains(&path) {
+                    continue;
+                }
+                state.projects.push(ProjectMeta::from_path(path));
+                added += 1;
+            }
+            state.selected_project_clamped();
+            state.status_line = format!("Added {added} project(s) from scan");
+            let mut effects = Vec::new();
+            if added > 0 {
+                effects.push(Effect::SaveConfig(state.app_config()));
+            }
+            effects.extend(start_queued_watch_scan(state));
+            effects
+        }
+        _ => vec![],
+    }
+}
+
+fn on_add_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    if state.project_scan.is_some() {
+        return on_project_scan_key(state, key);
+    }
+    match key.code {
+        KeyCode::Char('+') => {
+            state.scan_settings.max_depth = (state.scan_settings.max_depth + 1).min(16);
+            state.status_line = format!("Scan depth: {}", state.scan_settings.max_depth);
+            vec![Effect::SaveConfig(state.app_config())]
+        }
+        KeyCode::Char('-') => {
+            state.scan_settings.max_depth = state.scan_settings.max_depth.saturating_sub(1).max(1);
+            state.status_line = format!("Scan depth: {}", state.scan_settings.max_depth);
+            vec![Effect::SaveConfig(state.app_config())]
+        }
+        KeyCode::Char('i') => {
+            state.scan_ignore_mode = true;
+            state.scan_ignore_input.clear();
+            vec![]
+        }
+        KeyCode::Char('a') => {
+            state.add_project_mode = true;
+            state.add_project_input.clear();
+            vec![]
+        }
+        KeyCode::Char('f') => {
+            state.add_folder_mode = true;
+            state.add_folder_input.clear();
+            vec![]
+        }
+        KeyCode::Char('w') => {
+            state.add_folder_mode = true;
+            state.watch_folder_mode = true;
             state.add_folder_input.clear();
             vec![]
         }
+        KeyCode::Char('W') => {
+            if state.watch_folders.is_empty() {
+                state.status_line = "No watch folders configured".to_string();
+                return vec![];
+            }
+            start_watch_scan(state)
+        }
+        KeyCode::Char('j') => {
+            if !state.watch_folders.is_empty() {
+                state.selected_watch_folder =
+                    (state.selected_watch_folder + 1).min(state.watch_folders.len() - 1);
+            }
+            vec![]
+        }
+        KeyCode::Char('k') => {
+            state.selected_watch_folder = state.selected_watch_folder.saturating_sub(1);
+            vec![]
+        }
+        KeyCode::Char('x') => {
+            if state.selected_watch_folder >= state.watch_folders.len() {
+                return vec![];
+            }
+            let root = state.watch_folders.remove(state.selected_watch_folder);
+            state.selected_watch_folder = state
+                .selected_watch_folder
+                .min(state.watch_folders.len().saturating_sub(1));
+            state.status_line = format!("Stopped watching {}", root.display());
+            vec![Effect::SaveConfig(state.app_config())]
+        }
         _ => vec![],
     }
 }
 
 fn on_projects_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    let acts_on_selection = matches!(
+        key.code,
+        KeyCode::Enter | KeyCode::Char('t' | 'T' | 'r' | 'p' | 'd' | 'o' | 'J' | 'K')
+    );
+    if acts_on_selection && !selection_visible(state) {
+        state.status_line = "No project selected".to_string();
+        return vec![];
+    }
     match key.code {
         KeyCode::Char('/') => {
             state.search_mode = true;
@@ -615,17 +1773,139 @@ fn on_projects_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec
             vec![]
         }
         KeyCode::Char('j') => {
-            let len = state.filtered_projects().len();
-            if len > 0 {
-                state.selected_project = (state.selected_project + 1).min(len - 1);
-            }
+            move_project_selection(state, true);
             vec![]
         }
         KeyCode::Char('k') => {
-            state.selected_project = state.selected_project.saturating_sub(1);
+            move_project_selection(state, false);
+            vec![]
+        }
+        KeyCode::Char('t') | KeyCode::Char('T') => {
+            let Some(project) = state.selected_project() else {
+                state.status_line = "No project selected".to_string();
+                return vec![];
+            };
+            if key.code == KeyCode::Char('T') && project.tags.is_empty() {
+                state.status_line = format!("{} has no tags", project.display_name);
+                return vec![];
+            }
+            state.tag_edit = Some(if key.code == KeyCode::Char('t') {
+                TagEdit::Add
+            } else {
+                TagEdit::Remove
+            });
+            state.tag_input.clear();
+            vec![]
+        }
+        KeyCode::Char(']') => {
+            cycle_tag_filter(state, true);
+            vec![]
+        }
+        KeyCode::Char('[') => {
+            cycle_tag_filter(state, false);
+            vec![]
+        }
+        KeyCode::Char('r') => {
+            let Some(project) = state.selected_project() else {
+                state.status_line = "No project selected".to_string();
+                return vec![];
+            };
+            state.rename_project_input = project.display_name.clone();
+            state.rename_project_mode = true;
+            vec![]
+        }
+        KeyCode::Char('p') => {
+            let Some(project) = state.projects.get_mut(state.selected_project) else {
+                state.status_line = "No project selected".to_string();
+                return vec![];
+            };
+            project.pinned = !project.pinned;
+            state.status_line = format!(
+                "{} {}",
+                if project.pinned { "Pinned" } else { "Unpinned" },
+                project.display_name
+            );
+            state.pin_projects_to_top();
+            vec![Effect::SaveConfig(state.app_config())]
+        }
+        KeyCode::Char('s') => {
+            let table = &mut state.project_table;
+            table.sort = match table.sort {
+                None => ProjectColumn::ALL.first().copied(),
+                Some(column) => ProjectColumn::ALL
+                    .iter()
+                    .position(|c| *c == column)
+                    .and_then(|idx| ProjectColumn::ALL.get(idx + 1).copied()),
+            };
+            state.status_line = match table.sort {
+                Some(column) => format!("Sorted by {}", column.title()),
+                None => "Manual order".to_string(),
+            };
+            vec![Effect::SaveConfig(state.app_config())]
+        }
+        KeyCode::Char('S') => {
+            let table = &mut state.project_table;
+            if table.sort.is_none() {
+                state.status_line = "Pick a sort column with [s] first".to_string();
+                return vec![];
+            }
+            table.descending = !table.descending;
+            state.status_line = if table.descending {
+                "Sorted descending".to_string()
+            } else {
+                "Sorted ascending".to_string()
+            };
+            vec![Effect::SaveConfig(state.app_config())]
+        }
+        KeyCode::Char(c @ '1'..='7') => {
+            let column = ProjectColumn::ALL[c as usize - '1' as usize];
+            let table = &mut state.project_table;
+            if table.hidden.contains(&column) {
+                table.hidden.retain(|h| *h != column);
+                state.status_line = format!("Showing {} column", column.title());
+            } else if table.visible_columns().len() > 1 {
+                table.hidden.push(column);
+                state.status_line = format!("Hiding {} column", column.title());
+            } else {
+                state.status_line = "At least one column must stay visible".to_string();
+                return vec![];
+            }
+            vec![Effect::SaveConfig(state.app_config())]
+        }
+        KeyCode::Char('K') => move_selected_project(state, true),
+        KeyCode::Char('J') => move_selected_project(state, false),
+        KeyCode::Char('g') => {
+            state.group_by_tag = !state.group_by_tag;
+            state.status_line = if state.group_by_tag {
+                "Grouped by tag".to_string()
+            } else {
+                "Flat project list".to_string()
+            };
+            vec![]
+        }
+        KeyCode::Char('d') => {
+            let Some(project) = state.selected_project() else {
+                state.status_line = "No project selected".to_string();
+                return vec![];
+            };
+            let (name, path) = (project.display_name.clone(), project.path.clone());
+            state.status_line = format!(
+                "Remove {name} from the list? Files on disk are kept. [y] confirm, any other key cancels"
+            );
+            state.pending_project_removal = Some(path);
             vec![]
         }
+        KeyCode::Char('o') => open_in_unity(state),
         KeyCode::Enter => {
+            if let Some(project) = state.selected_project() {
+                if state.offline_projects.contains(&project.path) {
+                    state.status_line = format!(
+                        "{} is offline; its folder is not reachable",
+                        project.display_name
+                    );
+                    return vec![];
+                }
+            }
             state.screen = Screen::Manage;
             if let Some(project) = state.selected_project() {
                 return vec![Effect::ReadManifest {
@@ -648,7 +1928,20 @@ fn on_manage_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<E
             }
             vec![]
         }
-        KeyCode::Char('R') => vec![Effect::LoadAvailablePackages],
+        KeyCode::Char('R') => vec![load_available_packages(state)],
+        KeyCode::Char('p') => {
+            state.show_prereleases = !state.show_prereleases;
+            state.selected_available_package_clamped();
+            state.status_line = format!(
+                "Prereleases {}",
+                if state.show_prereleases {
+                    "shown"
+                } else {
+                    "hidden"
+                }
+            );
+            vec![]
+        }
         KeyCode::Char('/') => {
             state.available_package_search_mode = true;
             vec![]
@@ -673,10 +1966,11 @@ fn on_manage_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<E
                     state.selected_available_package =
                         (state.selected_available_package + 1).min(len - 1);
                 }
-            } else if let Some(summary) = &state.selected_project_manifest {
-                if !summary.packages.is_empty() {
+            } else {
+                let len = state.installed_packages().len();
+                if len > 0 {
                     state.selected_manifest_package =
-                        (state.selected_manifest_package + 1).min(summary.packages.len() - 1);
+                        (state.selected_manifest_package + 1).min(len - 1);
                 }
             }
             vec![]
@@ -698,342 +1992,3160 @@ fn on_manage_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<E
         KeyCode::Char('-') | KeyCode::Char('_') | KeyCode::Char('－') | KeyCode::Char('x') => {
             remove_selected_available_package(state, false)
         }
+        KeyCode::Enter | KeyCode::Char('@') => open_version_picker(state),
+        KeyCode::Char('I') => {
+            state.show_package_detail = !state.show_package_detail;
+            vec![]
+        }
         KeyCode::Char('u') => update_selected_installed_package(state),
+        KeyCode::Char('A') => update_all_outdated_packages(state),
         KeyCode::Char('U') => update_vrchat_sdk_package(state),
         KeyCode::Char('d') => remove_selected_package(state, false),
         KeyCode::Char('D') => remove_selected_package(state, true),
         KeyCode::Char('v') => resolve_selected_project(state),
+        KeyCode::Char('M') => start_migration(state),
+        KeyCode::Char('o') => open_in_unity(state),
+        KeyCode::Char('b') => open_backups(state),
         _ => vec![],
     }
 }
 
-fn on_settings_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
-    match key.code {
-        KeyCode::Char('t') => vec![enqueue_system_task(
-            state,
-            "vpm install templates",
-            ["install", "templates"],
-        )],
-        KeyCode::Char('h') => vec![enqueue_system_task(
-            state,
-            "vpm check hub",
-            ["check", "hub"],
-        )],
-        KeyCode::Char('u') => vec![enqueue_system_task(
-            state,
-            "vpm check unity",
-            ["check", "unity"],
-        )],
-        KeyCode::Char('l') => vec![enqueue_system_task(
-            state,
-            "vpm list unity",
-            ["list", "unity"],
-        )],
-        KeyCode::Char('s') => vec![enqueue_system_task(
-            state,
-            "vpm open settingsFolder",
-            ["open", "settingsFolder"],
-        )],
-        KeyCode::Char('1') => vec![enqueue_project_task(
-            state,
-            format!("vpm add repo {NADENA_REPO_URL}"),
-            vec![
-                "add".to_string(),
-                "repo".to_string(),
-                NADENA_REPO_URL.to_string(),
-            ],
-            None,
-            None,
-        )],
-        KeyCode::Char('2') => vec![enqueue_project_task(
-            state,
-            format!("vpm add repo {LILTOON_REPO_URL}"),
-            vec![
-                "add".to_string(),
-                "repo".to_string(),
-                LILTOON_REPO_URL.to_string(),
-            ],
-            None,
-            None,
-        )],
-        KeyCode::Char('a') => {
-            state.add_repo_mode = true;
-            state.add_repo_input.clear();
-            vec![]
-        }
-        KeyCode::Char('r') => vec![enqueue_system_task(
-            state,
-            "vpm list repos",
-            ["list", "repos"],
-        )],
-        KeyCode::Char('c') => {
-            if let Some(task) = state
-                .tasks
-                .iter()
-                .rev()
-                .find(|t| t.state == TaskState::Running)
-            {
-                return vec![Effect::CancelTask { task_id: task.id }];
-            }
-            vec![]
-        }
-        _ => vec![],
-    }
-}
-
-fn install_selected_available_package(state: &mut AppState) -> Vec<Effect> {
+fn open_in_unity(state: &mut AppState) -> Vec<Effect> {
     let Some(project) = state.selected_project() else {
         state.status_line = "No project selected".to_string();
         return vec![];
     };
-    let Some(pkg) = state.selected_available_package() else {
-        state.status_line = "No available package selected".to_string();
+    let (name, project_path) = (project.display_name.clone(), project.path.clone());
+    if state.offline_projects.contains(&project_path) {
+        state.status_line = format!("{name} is offline; its folder is not reachable");
         return vec![];
-    };
-
-    let project_path = project.path.clone();
-    let package_name = pkg.id.clone();
-    let args = vec![
-        "add".to_string(),
-        "package".to_string(),
-        package_name.clone(),
-        "-p".to_string(),
-        project_path.to_string_lossy().to_string(),
-    ];
+    }
+    state.status_line = format!("Opening {name} in Unity...");
+    vec![Effect::OpenInUnity {
+        project_path,
+        settings: state.unity_settings.clone(),
+        editors: state
+            .system_checks
+            .installed_editors
+            .clone()
+            .unwrap_or_default(),
+    }]
+}
 
-    vec![enqueue_project_task(
-        state,
-        format!("vpm add package {package_name}"),
-        args,
-        Some(project_path),
-        None,
-    )]
+fn on_unity_opened(
+    state: &mut AppState,
+    project_path: PathBuf,
+    result: Result<(String, PathBuf), String>,
+) -> Vec<Effect> {
+    let now = state.now.with_timezone(&Utc);
+    let Some(project) = state.projects.iter_mut().find(|p| p.path == project_path) else {
+        return vec![];
+    };
+    match result {
+        Ok((version, editor)) => {
+            project.last_opened = Some(now.to_rfc3339_opts(SecondsFormat::Secs, true));
+            let name = project.display_name.clone();
+            state.status_line = format!("Opened {name} in Unity {version}");
+            state.push_log(
+                LogSource::Projects,
+                LogLevel::Success,
+                format!("opened {name} with {}", editor.display()),
+            );
+            vec![Effect::SaveConfig(state.app_config())]
+        }
+        Err(err) => {
+            let name = project.display_name.clone();
+            state.status_line = format!("Could not open {name} in Unity");
+            state.push_log(
+                LogSource::Projects,
+                LogLevel::Error,
+                format!("could not open {name}: {err}"),
+            );
+            vec![]
+        }
+    }
 }
 
-fn remove_selected_available_package(state: &mut AppState, force: bool) -> Vec<Effect> {
+fn start_migration(state: &mut AppState) -> Vec<Effect> {
     let Some(project) = state.selected_project() else {
         state.status_line = "No project selected".to_string();
         return vec![];
     };
-    let Some(pkg) = state.selected_available_package() else {
-        state.status_line = "No available package selected".to_string();
+    let (name, path) = (project.display_name.clone(), project.path.clone());
+    if let Some(migration) = &state.migration {
+        state.status_line = format!(
+            "Migration of {} is already in progress",
+            migration.project_path.display()
+        );
         return vec![];
-    };
-
-    let project_path = project.path.clone();
-    let package_name = pkg.id.clone();
-    let mut args = vec![
-        "remove".to_string(),
-        "package".to_string(),
-        package_name.clone(),
-        "-p".to_string(),
-        project_path.to_string_lossy().to_string(),
-    ];
-    if force {
-        args.push("--force".to_string());
     }
-    vec![enqueue_project_task(
-        state,
-        if force {
-            format!("vpm remove package {package_name} --force")
-        } else {
-            format!("vpm remove package {package_name}")
-        },
-        args,
-        Some(project_path),
-        None,
-    )]
+    let kind = state
+        .selected_project_manifest
+        .as_ref()
+        .and_then(|m| m.kind);
+    if kind != Some(ProjectKind::LegacySdk3) {
+        state.status_line = format!("{name} is not a legacy SDK3 project");
+        return vec![];
+    }
+    state.status_line = format!(
+        "Migrate {name} to VPM? A zip backup is made first. [y] confirm, any other key cancels"
+    );
+    state.migration = Some(Migration {
+        project_path: path,
+        backup_path: None,
+        stage: MigrationStage::Confirm,
+    });
+    vec![]
 }
 
-fn update_selected_installed_package(state: &mut AppState) -> Vec<Effect> {
-    let Some(project) = state.selected_project() else {
-        state.status_line = "No project selected".to_string();
+fn on_migration_confirm(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    if key.code != KeyCode::Char('y') {
+        state.migration = None;
+        state.status_line = "Migration cancelled".to_string();
+        return vec![];
+    }
+    let Some(migration) = state.migration.as_mut() else {
         return vec![];
     };
-    let Some(pkg) = state.selected_manifest_package() else {
-        state.status_line = "No installed package selected".to_string();
+    migration.stage = MigrationStage::BackingUp;
+    let project_path = migration.project_path.clone();
+    state.status_line = format!("Backing up {}...", project_path.display());
+    state.push_log(
+        LogSource::Projects,
+        LogLevel::Info,
+        format!("backing up {} before migration", project_path.display()),
+    );
+    vec![Effect::BackupProject {
+        project_path,
+        settings: state.backup_settings.clone(),
+    }]
+}
+
+fn on_project_backed_up(
+    state: &mut AppState,
+    project_path: PathBuf,
+    result: Result<BackupEntry, String>,
+    skipped: Vec<PathBuf>,
+) -> Vec<Effect> {
+    for link in skipped {
+        state.push_log(
+            LogSource::Projects,
+            LogLevel::Warn,
+            format!(
+                "backup left out {}: the link is broken or loops",
+                link.display()
+            ),
+        );
+    }
+    if state
+        .migration
+        .as_ref()
+        .is_none_or(|m| m.project_path != project_path || m.stage != MigrationStage::BackingUp)
+    {
+        on_manual_backup_done(state, &project_path, result);
         return vec![];
+    }
+    let backup = match result {
+        Ok(backup) => backup.path,
+        Err(err) => {
+            state.migration = None;
+            state.status_line = "Backup failed; migration aborted".to_string();
+            state.push_log(
+                LogSource::Projects,
+                LogLevel::Error,
+                format!("backup failed, migration aborted: {err}"),
+            );
+            return vec![];
+        }
     };
-    let project_path = project.path.clone();
-    let package_name = pkg.name.clone();
-    vec![enqueue_project_task(
+    state.push_log(
+        LogSource::Projects,
+        LogLevel::Success,
+        format!("backup created at {}", backup.display()),
+    );
+    let task_id = state.next_task_id;
+    let effects = enqueue_project_task(
         state,
-        format!("vpm add package {package_name} (update)"),
+        format!("vpm migrate project {}", project_path.display()),
         vec![
-            "add".to_string(),
-            "package".to_string(),
-            package_name,
-            "-p".to_string(),
-            project_path.to_string_lossy().to_string(),
+            "migrate".to_string(),
+            "project".to_string(),
+            project_path.display().to_string(),
+            "--inplace".to_string(),
         ],
         Some(project_path),
         None,
-    )]
+    );
+    if let Some(migration) = state.migration.as_mut() {
+        migration.backup_path = Some(backup);
+        migration.stage = MigrationStage::Migrating(task_id);
+    }
+    effects
 }
 
-fn update_vrchat_sdk_package(state: &mut AppState) -> Vec<Effect> {
-    let Some(project) = state.selected_project() else {
-        state.status_line = "No project selected".to_string();
-        return vec![];
-    };
-    let manifest = match &state.selected_project_manifest {
-        Some(v) => v,
-        None => {
-            state.status_line = "Manifest is not loaded".to_string();
-            return vec![];
-        }
+fn on_migration_task_done(state: &mut AppState, task_id: u64, success: bool) {
+    let Some(migration) = state.migration.as_mut() else {
+        return;
     };
+    if migration.stage != MigrationStage::Migrating(task_id) {
+        return;
+    }
+    if success {
+        migration.stage = MigrationStage::Verifying;
+        return;
+    }
+    let backup = migration_backup_text(migration);
+    state.migration = None;
+    state.status_line = "Migration failed".to_string();
+    state.push_log(
+        LogSource::Projects,
+        LogLevel::Error,
+        format!("migration failed; backup kept at {backup}"),
+    );
+}
 
-    let sdk_pkg = if manifest
-        .packages
-        .iter()
-        .any(|p| p.name == "com.vrchat.avatars")
-    {
-        "com.vrchat.avatars"
-    } else if manifest
-        .packages
-        .iter()
-        .any(|p| p.name == "com.vrchat.worlds")
-    {
-        "com.vrchat.worlds"
-    } else {
-        state.status_line = "No VRChat SDK package found in project".to_string();
-        return vec![];
+fn verify_migration(state: &mut AppState, project_path: &Path, summary: Option<&ManifestSummary>) {
+    let Some(migration) = &state.migration else {
+        return;
     };
+    if migration.stage != MigrationStage::Verifying || migration.project_path != project_path {
+        return;
+    }
+    let backup = migration_backup_text(migration);
+    let name = migration.project_path.display().to_string();
+    state.migration = None;
+    match summary {
+        Some(summary) if summary.exists && summary.kind == Some(ProjectKind::Vpm) => {
+            let count = summary.packages.len();
+            state.status_line = format!("Migration verified: {name}");
+            state.push_log(
+                LogSource::Projects,
+                LogLevel::Success,
+                format!(
+                    "migration verified: {count} package(s) in vpm-manifest.json; backup at {backup}"
+                ),
+            );
+        }
+        _ => {
+            state.status_line = "Migration could not be verified".to_string();
+            state.push_log(
+                LogSource::Projects,
+                LogLevel::Error,
+                format!(
+                    "migration could not be verified: Packages/vpm-manifest.json is missing; backup kept at {backup}"
+                ),
+            );
+        }
+    }
+}
 
-    let project_path = project.path.clone();
-    vec![enqueue_project_task(
-        state,
-        format!("vpm add package {sdk_pkg} (sdk update)"),
-        vec![
-            "add".to_string(),
-            "package".to_string(),
-            sdk_pkg.to_string(),
-            "-p".to_string(),
-            project_path.to_string_lossy().to_string(),
-        ],
-        Some(project_path),
-        None,
-    )]
+fn migration_backup_text(migration: &Migration) -> String {
+    migration
+        .backup_path
+        .as_ref()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "(none)".to_string())
 }
 
-fn resolve_selected_project(state: &mut AppState) -> Vec<Effect> {
+fn open_backups(state: &mut AppState) -> Vec<Effect> {
     let Some(project) = state.selected_project() else {
         state.status_line = "No project selected".to_string();
         return vec![];
     };
-    let project_path = project.path.clone();
-    vec![enqueue_project_task(
-        state,
-        format!("vpm resolve project {}", project_path.display()),
-        vec![
-            "resolve".to_string(),
-            "project".to_string(),
-            project_path.to_string_lossy().to_string(),
-        ],
-        Some(project_path),
-        None,
-    )]
+    let (name, project_path) = (project.display_name.clone(), project.path.clone());
+    if state.offline_projects.contains(&project_path) {
+        state.status_line = format!("{name} is offline; its folder is not reachable");
+        return vec![];
+    }
+    state.backup_browser = Some(BackupBrowser {
+        project_path: project_path.clone(),
+        entries: Vec::new(),
+        selected: 0,
+        loading: true,
+        busy: false,
+        pending_restore: None,
+    });
+    state.status_line = format!("Listing backups of {name}...");
+    vec![Effect::ListBackups {
+        project_path,
+        settings: state.backup_settings.clone(),
+    }]
 }
 
-fn remove_selected_package(state: &mut AppState, force: bool) -> Vec<Effect> {
-    let Some(project) = state.selected_project() else {
-        state.status_line = "No project selected".to_string();
+fn on_backups_listed(
+    state: &mut AppState,
+    project_path: PathBuf,
+    result: Result<Vec<BackupEntry>, String>,
+) -> Vec<Effect> {
+    let Some(browser) = state
+        .backup_browser
+        .as_mut()
+        .filter(|b| b.project_path == project_path)
+    else {
         return vec![];
     };
-    let Some(pkg) = state.selected_manifest_package() else {
-        state.status_line = "No installed package selected".to_string();
+    browser.loading = false;
+    match result {
+        Ok(entries) => {
+            state.status_line = format!("{} backup(s) found", entries.len());
+            browser.entries = entries;
+            browser.selected = 0;
+        }
+        Err(err) => {
+            state.status_line = "Could not list backups".to_string();
+            state.push_log(
+                LogSource::Projects,
+                LogLevel::Error,
+                format!("could not list backups: {err}"),
+            );
+        }
+    }
+    vec![]
+}
+
+fn on_backup_browser_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    let Some(browser) = state.backup_browser.as_mut() else {
         return vec![];
     };
-
-    let pkg_name = pkg.name.clone();
-    let project_path = project.path.clone();
-    let mut args = vec![
-        "remove".to_string(),
-        "package".to_string(),
-        pkg_name.clone(),
-        "-p".to_string(),
-        project_path.to_string_lossy().to_string(),
-    ];
-    if force {
-        args.push("--force".to_string());
+    if let Some(target) = browser.pending_restore.take() {
+        if key.code != KeyCode::Char('y') {
+            state.status_line = "Restore cancelled".to_string();
+            return vec![];
+        }
+        let Some(backup) = browser.selected_entry().map(|e| e.path.clone()) else {
+            return vec![];
+        };
+        browser.busy = true;
+        state.status_line = format!("Restoring into {}...", target.display());
+        return vec![Effect::RestoreBackup { backup, target }];
+    }
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('b') => {
+            state.backup_browser = None;
+            vec![]
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            if !browser.entries.is_empty() {
+                browser.selected = (browser.selected + 1).min(browser.entries.len() - 1);
+            }
+            vec![]
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            browser.selected = browser.selected.saturating_sub(1);
+            vec![]
+        }
+        KeyCode::Char('n') => {
+            if browser.busy {
+                state.status_line = "A backup or restore is already running".to_string();
+                return vec![];
+            }
+            browser.busy = true;
+            let project_path = browser.project_path.clone();
+            state.status_line = format!("Backing up {}...", project_path.display());
+            vec![Effect::BackupProject {
+                project_path,
+                settings: state.backup_settings.clone(),
+            }]
+        }
+        KeyCode::Enter => {
+            if browser.busy {
+                state.status_line = "A backup or restore is already running".to_string();
+                return vec![];
+            }
+            let Some(entry) = browser.selected_entry() else {
+                state.status_line = "No backup selected".to_string();
+                return vec![];
+            };
+            let Some(target) = restore_target(&browser.project_path, entry) else {
+                return vec![];
+            };
+            state.status_line = format!(
+                "Restore {} into {}? [y] confirm, any other key cancels",
+                entry.file_name(),
+                target.display()
+            );
+            browser.pending_restore = Some(target);
+            vec![]
+        }
+        _ => vec![],
     }
+}
 
-    vec![enqueue_project_task(
-        state,
-        if force {
-            format!("vpm remove package {pkg_name} --force")
-        } else {
-            format!("vpm remove package {pkg_name}")
-        },
-        args,
-        Some(project_path),
-        None,
-    )]
+fn restore_target(project_path: &Path, entry: &BackupEntry) -> Option<PathBuf> {
+    let name = project_path.file_name()?.to_string_lossy();
+    Some(project_path.with_file_name(format!(
+        "{name}-restored-{}",
+        entry.created.format("%Y%m%d-%H%M%S")
+    )))
 }
 
-fn enqueue_project_task(
+fn on_manual_backup_done(
     state: &mut AppState,
-    label: String,
-    args: Vec<String>,
-    refresh_manifest_path: Option<PathBuf>,
-    pending_add_project: Option<ProjectMeta>,
-) -> Effect {
-    let task_id = state.next_task_id;
-    state.next_task_id = state.next_task_id.saturating_add(1);
-    state.tasks.push(TaskRecord {
-        id: task_id,
-        label: label.clone(),
-        state: TaskState::Running,
-        exit_code: None,
-        error: None,
-        refresh_manifest_path,
-        pending_add_project,
-    });
-    state.status_line = format!("Running {label}");
-    state.push_log(Some(task_id), format!("[task:{task_id}] start {label}"));
-
-    Effect::RunVpmCommand {
-        task_id,
-        label,
-        args,
+    project_path: &Path,
+    result: Result<BackupEntry, String>,
+) {
+    let mut browser = state
+        .backup_browser
+        .as_mut()
+        .filter(|b| b.project_path == project_path);
+    if let Some(browser) = browser.as_deref_mut() {
+        browser.busy = false;
+    }
+    match result {
+        Ok(entry) => {
+            let message = format!(
+                "backup created at {} ({})",
+                entry.path.display(),
+                entry.size_label()
+            );
+            state.status_line = format!("Backup created: {}", entry.file_name());
+            if let Some(browser) = browser {
+                browser.entries.insert(0, entry);
+                browser.selected = 0;
+            }
+            state.push_log(LogSource::Projects, LogLevel::Success, message);
+        }
+        Err(err) => {
+            state.status_line = "Backup failed".to_string();
+            state.push_log(
+                LogSource::Projects,
+                LogLevel::Error,
+                format!("backup failed: {err}"),
+            );
+        }
     }
 }
 
-fn enqueue_system_task<const N: usize>(
+fn on_backup_restored(
     state: &mut AppState,
-    label: &str,
-    args: [&str; N],
-) -> Effect {
-    enqueue_project_task(
-        state,
-        label.to_string(),
-        args.into_iter().map(|v| v.to_string()).collect(),
-        None,
-        None,
-    )
+    backup: PathBuf,
+    result: Result<PathBuf, String>,
+) -> Vec<Effect> {
+    if let Some(browser) = state.backup_browser.as_mut() {
+        browser.busy = false;
+    }
+    match result {
+        Ok(target) => {
+            state.status_line = format!("Restored into {}", target.display());
+            state.push_log(
+                LogSource::Projects,
+                LogLevel::Success,
+                format!("restored {} into {}", backup.display(), target.display()),
+            );
+            if state.projects.iter().any(|p| p.path == target) {
+                return vec![];
+            }
+            state.projects.push(ProjectMeta::from_path(target.clone()));
+            vec![
+                Effect::SaveConfig(state.app_config()),
+                Effect::ReadProjectDetails(vec![target]),
+            ]
+        }
+        Err(err) => {
+            state.status_line = "Restore failed".to_string();
+            state.push_log(
+                LogSource::Projects,
+                LogLevel::Error,
+                format!("restore failed: {err}"),
+            );
+            vec![]
+        }
+    }
 }
 
-fn task_is_label(state: &AppState, task_id: u64, label: &str) -> bool {
-    state
-        .tasks
-        .iter()
-        .find(|t| t.id == task_id)
-        .map(|t| t.label == label)
-        .unwrap_or(false)
+fn on_settings_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    match key.code {
+        KeyCode::Char('t') => {
+            enqueue_system_task(state, "vpm install templates", ["install", "templates"])
+        }
+        KeyCode::Char('h') => enqueue_system_task(state, "vpm check hub", ["check", "hub"]),
+        KeyCode::Char('u') => enqueue_system_task(state, "vpm check unity", ["check", "unity"]),
+        KeyCode::Char('l') => enqueue_system_task(state, "vpm list unity", ["list", "unity"]),
+        KeyCode::Char('s') => {
+            enqueue_system_task(state, "vpm open settingsFolder", ["open", "settingsFolder"])
+        }
+        KeyCode::Char('1') => add_repository(state, NADENA_REPO_URL.to_string()),
+        KeyCode::Char('2') => add_repository(state, LILTOON_REPO_URL.to_string()),
+        KeyCode::Char('a') => {
+            state.add_repo_mode = true;
+            state.add_repo_input.clear();
+            vec![]
+        }
+        KeyCode::Char('r') => enqueue_system_task(state, "vpm list repos", ["list", "repos"]),
+        KeyCode::Char('c') => {
+            if let Some(task_id) = state
+                .tasks
+                .iter()
+                .rev()
+                .find(|t| matches!(t.state, TaskState::Running | TaskState::Queued))
+                .map(|t| t.id)
+            {
+                return cancel_task(state, task_id);
+            }
+            vec![]
+        }
+        _ => vec![],
+    }
 }
 
-fn task_state_text(task: &TaskRecord) -> String {
-    match task.state {
-        TaskState::Running => "running".to_string(),
-        TaskState::Success => "ok".to_string(),
-        TaskState::Cancelled => "cancelled".to_string(),
-        TaskState::Failed => {
-            if let Some(err) = &task.error {
-                format!("failed: {err}")
+fn on_tasks_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    match key.code {
+        KeyCode::Char('K') => {
+            state.task_output_scroll = (state.task_output_scroll + 1).min(task_output_lines(state));
+            return vec![];
+        }
+        KeyCode::Char('J') => {
+            state.task_output_scroll = state.task_output_scroll.saturating_sub(1);
+            return vec![];
+        }
+        KeyCode::Char('G') => {
+            state.task_output_scroll = 0;
+            return vec![];
+        }
+        _ => state.task_output_scroll = 0,
+    }
+    if key.code == KeyCode::Char('H') {
+        state.show_task_history = !state.show_task_history;
+        if state.show_task_history {
+            state.selected_history_session = 0;
+            state.selected_history_task = 0;
+            return read_selected_history_log(state);
+        }
+        return vec![];
+    }
+    if state.show_task_history {
+        return on_task_history_key(state, key);
+    }
+
+    match key.code {
+        KeyCode::Char('j') => {
+            if !state.tasks.is_empty() {
+                state.selected_task = (state.selected_task + 1).min(state.tasks.len() - 1);
+            }
+            vec![]
+        }
+        KeyCode::Char('k') => {
+            state.selected_task = state.selected_task.saturating_sub(1);
+            vec![]
+        }
+        KeyCode::Char('c') => match state.selected_task().map(|t| t.id) {
+            Some(task_id) => cancel_task(state, task_id),
+            None => vec![],
+        },
+        KeyCode::Char('r') => {
+            let Some(task) = state.selected_task().cloned() else {
+                return vec![];
+            };
+            if task.is_active() {
+                state.status_line = format!("Task {} is still {}", task.id, task_state_text(&task));
+                return vec![];
+            }
+            let effects = enqueue_project_task(
+                state,
+                task.label,
+                task.args,
+                task.refresh_manifest_path,
+                task.pending_add_project,
+            );
+            state.selected_task = 0;
+            effects
+        }
+        _ => vec![],
+    }
+}
+
+fn task_output_lines(state: &AppState) -> usize {
+    if state.show_task_history {
+        return state.history_log.len();
+    }
+    state
+        .selected_task()
+        .map(|task| state.task_logs(task.id).len())
+        .unwrap_or(0)
+}
+
+fn on_task_history_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    match key.code {
+        KeyCode::Char('j') => {
+            let len = state.history_session_entries().len();
+            if len > 0 {
+                state.selected_history_task = (state.selected_history_task + 1).min(len - 1);
+            }
+            read_selected_history_log(state)
+        }
+        KeyCode::Char('k') => {
+            state.selected_history_task = state.selected_history_task.saturating_sub(1);
+            read_selected_history_log(state)
+        }
+        KeyCode::Char(']') => {
+            let len = state.history_sessions().len();
+            if len > 0 {
+                state.selected_history_session = (state.selected_history_session + 1).min(len - 1);
+            }
+            state.selected_history_task = 0;
+            read_selected_history_log(state)
+        }
+        KeyCode::Char('[') => {
+            state.selected_history_session = state.selected_history_session.saturating_sub(1);
+            state.selected_history_task = 0;
+            read_selected_history_log(state)
+        }
+        KeyCode::Char('r') => {
+            let Some(entry) = state.selected_history_entry().cloned() else {
+                return vec![];
+            };
+            state.show_task_history = false;
+            state.selected_task = 0;
+            enqueue_project_task(state, entry.label, entry.args, entry.project_path, None)
+        }
+        _ => vec![],
+    }
+}
+
+fn read_selected_history_log(state: &mut AppState) -> Vec<Effect> {
+    state.history_log.clear();
+    match state.selected_history_entry() {
+        Some(entry) => vec![Effect::ReadHistoryLog {
+            session: entry.session.clone(),
+            task_id: entry.task_id,
+        }],
+        None => vec![],
+    }
+}
+
+fn on_repos_key(state: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<Effect> {
+    match key.code {
+        KeyCode::Char('j') => {
+            if !state.repositories.is_empty() {
+                state.selected_repository =
+                    (state.selected_repository + 1).min(state.repositories.len() - 1);
+            }
+            vec![]
+        }
+        KeyCode::Char('k') => {
+            state.selected_repository = state.selected_repository.saturating_sub(1);
+            vec![]
+        }
+        KeyCode::Char('J') => move_selected_repository(state, 1),
+        KeyCode::Char('K') => move_selected_repository(state, -1),
+        KeyCode::Char(' ') => {
+            let Some(repo) = state.selected_repository() else {
+                state.status_line = "No repository selected".to_string();
+                return vec![];
+            };
+            let repo_key = repo.key().to_string();
+            let name = repo.name.clone();
+            if state.disabled_repos.contains(&repo_key) {
+                state.disabled_repos.retain(|id| id != &repo_key);
+                state.status_line = format!("Enabled {name}");
             } else {
-                format!("failed (exit={})", task.exit_code.unwrap_or(-1))
+                state.disabled_repos.push(repo_key);
+                state.status_line = format!("Disabled {name}");
+            }
+            vec![
+                Effect::SaveConfig(state.app_config()),
+                load_available_packages(state),
+            ]
+        }
+        KeyCode::Char('d') => {
+            let Some(repo) = state.selected_repository() else {
+                state.status_line = "No repository selected".to_string();
+                return vec![];
+            };
+            let (name, url) = (repo.name.clone(), repo.url.clone());
+            state.status_line = format!("Remove {name}? [y] confirm, any other key cancels");
+            state.pending_repo_removal = Some(url);
+            vec![]
+        }
+        KeyCode::Char('e') => {
+            if state.selected_repository().is_none() {
+                state.status_line = "No repository selected".to_string();
+                return vec![];
             }
+            state.repo_header_mode = true;
+            state.repo_header_input.clear();
+            vec![]
+        }
+        KeyCode::Char('E') => {
+            let Some(repo) = state.repositories.get_mut(state.selected_repository) else {
+                state.status_line = "No repository selected".to_string();
+                return vec![];
+            };
+            repo.headers.clear();
+            state.status_line = format!("Cleared headers for {}", repo.name);
+            save_repositories(state)
+        }
+        KeyCode::Char('a') => {
+            state.add_repo_mode = true;
+            state.add_repo_input.clear();
+            vec![]
         }
+        KeyCode::Char('r') => vec![Effect::LoadRepositories],
+        _ => vec![],
+    }
+}
+
+fn move_selected_repository(state: &mut AppState, delta: isize) -> Vec<Effect> {
+    let from = state.selected_repository;
+    let Some(to) = from.checked_add_signed(delta) else {
+        return vec![];
+    };
+    if to >= state.repositories.len() {
+        return vec![];
+    }
+    state.repositories.swap(from, to);
+    state.selected_repository = to;
+
+    let user_keys = state
+        .repositories
+        .iter()
+        .map(|r| r.key().to_string())
+        .collect::<Vec<_>>();
+    state.repo_priority.retain(|id| !user_keys.contains(id));
+    state.repo_priority.extend(user_keys);
+
+    let mut effects = save_repositories(state);
+    effects.push(Effect::SaveConfig(state.app_config()));
+    effects
+}
+
+// vpm downloads the index and records its cache in settings.json; the list is
+// reloaded when the task finishes.
+fn add_repository(state: &mut AppState, url: String) -> Vec<Effect> {
+    if state.repositories.iter().any(|r| r.url == url) {
+        state.status_line = format!("Repository {url} is already added");
+        return vec![];
+    }
+    enqueue_project_task(
+        state,
+        format!("vpm add repo {url}"),
+        vec!["add".to_string(), "repo".to_string(), url],
+        None,
+        None,
+    )
+}
+
+// settings.json is rewritten through a temp file, so a save never overlaps
+// another save or a vpm task that writes settings; edits made meanwhile are
+// saved once they finish.
+fn save_repositories(state: &mut AppState) -> Vec<Effect> {
+    if state.repo_save_in_flight || vpm_settings_task_running(state) {
+        state.repo_save_queued = true;
+        return vec![];
+    }
+    state.repo_save_in_flight = true;
+    vec![Effect::SaveRepositories {
+        repos: state.repositories.clone(),
+        removed: std::mem::take(&mut state.removed_repo_urls),
+    }]
+}
+
+fn vpm_settings_task_running(state: &AppState) -> bool {
+    state
+        .tasks
+        .iter()
+        .any(|t| t.state == TaskState::Running && t.scope == Some(TaskScope::VpmSettings))
+}
+
+fn install_selected_available_package(state: &mut AppState) -> Vec<Effect> {
+    let Some(project) = state.selected_project() else {
+        state.status_line = "No project selected".to_string();
+        return vec![];
+    };
+    let Some(pkg) = state.selected_available_package() else {
+        state.status_line = "No available package selected".to_string();
+        return vec![];
+    };
+
+    let project_path = project.path.clone();
+    let package_name = pkg.id.clone();
+    let args = vec![
+        "add".to_string(),
+        "package".to_string(),
+        package_name.clone(),
+        "-p".to_string(),
+        project_path.to_string_lossy().to_string(),
+    ];
+
+    enqueue_project_task(
+        state,
+        format!("vpm add package {package_name}"),
+        args,
+        Some(project_path),
+        None,
+    )
+}
+
+fn open_version_picker(state: &mut AppState) -> Vec<Effect> {
+    let package_id = if state.manage_focus_available {
+        state.selected_available_package().map(|p| p.id.clone())
+    } else {
+        state.selected_manifest_package().map(|p| p.name)
+    };
+    let Some(package_id) = package_id else {
+        state.status_line = "No package selected".to_string();
+        return vec![];
+    };
+    let Some(package) = state.available_packages.iter().find(|p| p.id == package_id) else {
+        state.status_line = format!("{package_id} is not in the available package catalog");
+        return vec![];
+    };
+
+    let installed_version = state
+        .installed_packages()
+        .into_iter()
+        .find(|p| p.name == package_id)
+        .map(|p| p.version().to_string());
+    let versions = package
+        .versions
+        .iter()
+        .filter(|v| {
+            state.show_prereleases
+                || !crate::semver::is_prerelease(&v.version)
+                || installed_version.as_deref() == Some(v.version.as_str())
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    if versions.is_empty() {
+        state.status_line = format!("No versions of {package_id} to pick from");
+        return vec![];
+    }
+    let selected = installed_version
+        .as_deref()
+        .and_then(|installed| versions.iter().position(|v| v.version == installed))
+        .unwrap_or(0);
+
+    state.version_picker = Some(VersionPicker {
+        package_id,
+        installed_version,
+        versions,
+        selected,
+    });
+    vec![]
+}
+
+fn install_package_version(state: &mut AppState, package_id: &str, version: &str) -> Vec<Effect> {
+    let Some(project) = state.selected_project() else {
+        state.status_line = "No project selected".to_string();
+        return vec![];
+    };
+    let project_path = project.path.clone();
+    let spec = format!("{package_id}@{version}");
+    enqueue_project_task(
+        state,
+        format!("vpm add package {spec}"),
+        vec![
+            "add".to_string(),
+            "package".to_string(),
+            spec,
+            "-p".to_string(),
+            project_path.to_string_lossy().to_string(),
+        ],
+        Some(project_path),
+        None,
+    )
+}
+
+fn remove_selected_available_package(state: &mut AppState, force: bool) -> Vec<Effect> {
+    let Some(project) = state.selected_project() else {
+        state.status_line = "No project selected".to_string();
+        return vec![];
+    };
+    let Some(pkg) = state.selected_available_package() else {
+        state.status_line = "No available package selected".to_string();
+        return vec![];
+    };
+
+    let project_path = project.path.clone();
+    let package_name = pkg.id.clone();
+    let mut args = vec![
+        "remove".to_string(),
+        "package".to_string(),
+        package_name.clone(),
+        "-p".to_string(),
+        project_path.to_string_lossy().to_string(),
+    ];
+    if force {
+        args.push("--force".to_string());
+    }
+    enqueue_project_task(
+        state,
+        if force {
+            format!("vpm remove package {package_name} --force")
+        } else {
+            format!("vpm remove package {package_name}")
+        },
+        args,
+        Some(project_path),
+        None,
+    )
+}
+
+fn update_selected_installed_package(state: &mut AppState) -> Vec<Effect> {
+    let Some(project) = state.selected_project() else {
+        state.status_line = "No project selected".to_string();
+        return vec![];
+    };
+    let Some(pkg) = state.selected_manifest_package() else {
+        state.status_line = "No installed package selected".to_string();
+        return vec![];
+    };
+    let project_path = project.path.clone();
+    let package_name = pkg.name.clone();
+    enqueue_project_task(
+        state,
+        format!("vpm add package {package_name} (update)"),
+        vec![
+            "add".to_string(),
+            "package".to_string(),
+            package_name,
+            "-p".to_string(),
+            project_path.to_string_lossy().to_string(),
+        ],
+        Some(project_path),
+        None,
+    )
+}
+
+fn update_all_outdated_packages(state: &mut AppState) -> Vec<Effect> {
+    let Some(project) = state.selected_project() else {
+        state.status_line = "No project selected".to_string();
+        return vec![];
+    };
+    let project_path = project.path.clone();
+    let outdated = state.outdated_packages();
+    if outdated.is_empty() {
+        state.status_line = "All packages are up to date".to_string();
+        return vec![];
+    }
+
+    let mut effects = Vec::new();
+    for update in &outdated {
+        let spec = format!("{}@{}", update.name, update.latest_version);
+        effects.extend(enqueue_project_task(
+            state,
+            format!(
+                "vpm add package {spec} (update from {})",
+                update.current_version
+            ),
+            vec![
+                "add".to_string(),
+                "package".to_string(),
+                spec,
+                "-p".to_string(),
+                project_path.to_string_lossy().to_string(),
+            ],
+            Some(project_path.clone()),
+            None,
+        ));
+    }
+    state.status_line = format!("Queued {} package update(s)", outdated.len());
+    effects
+}
+
+fn update_vrchat_sdk_package(state: &mut AppState) -> Vec<Effect> {
+    let Some(project) = state.selected_project() else {
+        state.status_line = "No project selected".to_string();
+        return vec![];
+    };
+    let manifest = match &state.selected_project_manifest {
+        Some(v) => v,
+        None => {
+            state.status_line = "Manifest is not loaded".to_string();
+            return vec![];
+        }
+    };
+
+    let sdk_pkg = if manifest
+        .packages
+        .iter()
+        .any(|p| p.name == "com.vrchat.avatars")
+    {
+        "com.vrchat.avatars"
+    } else if manifest
+        .packages
+        .iter()
+        .any(|p| p.name == "com.vrchat.worlds")
+    {
+        "com.vrchat.worlds"
+    } else {
+        state.status_line = "No VRChat SDK package found in project".to_string();
+        return vec![];
+    };
+
+    let project_path = project.path.clone();
+    enqueue_project_task(
+        state,
+        format!("vpm add package {sdk_pkg} (sdk update)"),
+        vec![
+            "add".to_string(),
+            "package".to_string(),
+            sdk_pkg.to_string(),
+            "-p".to_string(),
+            project_path.to_string_lossy().to_string(),
+        ],
+        Some(project_path),
+        None,
+    )
+}
+
+fn resolve_selected_project(state: &mut AppState) -> Vec<Effect> {
+    let Some(project) = state.selected_project() else {
+        state.status_line = "No project selected".to_string();
+        return vec![];
+    };
+    let project_path = project.path.clone();
+    enqueue_project_task(
+        state,
+        format!("vpm resolve project {}", project_path.display()),
+        vec![
+            "resolve".to_string(),
+            "project".to_string(),
+            project_path.to_string_lossy().to_string(),
+        ],
+        Some(project_path),
+        None,
+    )
+}
+
+fn remove_selected_package(state: &mut AppState, force: bool) -> Vec<Effect> {
+    let Some(project) = state.selected_project() else {
+        state.status_line = "No project selected".to_string();
+        return vec![];
+    };
+    let Some(pkg) = state.selected_manifest_package() else {
+        state.status_line = "No installed package selected".to_string();
+        return vec![];
+    };
+
+    let pkg_name = pkg.name.clone();
+    let project_path = project.path.clone();
+    let mut args = vec![
+        "remove".to_string(),
+        "package".to_string(),
+        pkg_name.clone(),
+        "-p".to_string(),
+        project_path.to_string_lossy().to_string(),
+    ];
+    if force {
+        args.push("--force".to_string());
+    }
+
+    enqueue_project_task(
+        state,
+        if force {
+            format!("vpm remove package {pkg_name} --force")
+        } else {
+            format!("vpm remove package {pkg_name}")
+        },
+        args,
+        Some(project_path),
+        None,
+    )
+}
+
+fn on_config_ready(state: &mut AppState) -> Vec<Effect> {
+    let mut effects = vec![
+        Effect::ConfigureCommandBackend(state.vpm_settings.clone()),
+        load_available_packages(state),
+    ];
+    effects.extend(enqueue_system_task(state, "vpm --version", ["--version"]));
+    effects.extend(enqueue_system_task(
+        state,
+        "vpm list unity",
+        ["list", "unity"],
+    ));
+    effects
+}
+
+fn warn_missing_editors(state: &mut AppState, before: &BTreeSet<PathBuf>) {
+    for path in state.projects_missing_editor().difference(before) {
+        let Some(project) = state.projects.iter().find(|p| &p.path == path) else {
+            continue;
+        };
+        let message = format!(
+            "{} uses Unity {}, which is not installed",
+            project.display_name,
+            state
+                .project_versions
+                .get(path)
+                .map(String::as_str)
+                .unwrap_or("?")
+        );
+        state.push_log(LogSource::Projects, LogLevel::Warn, message);
+    }
+}
+
+fn load_available_packages(state: &AppState) -> Effect {
+    Effect::LoadAvailablePackages {
+        repo_priority: state.repo_priority.clone(),
+        disabled_repos: state.disabled_repos.clone(),
+    }
+}
+
+fn enqueue_project_task(
+    state: &mut AppState,
+    label: String,
+    args: Vec<String>,
+    refresh_manifest_path: Option<PathBuf>,
+    pending_add_project: Option<ProjectMeta>,
+) -> Vec<Effect> {
+    let task_id = state.next_task_id;
+    state.next_task_id = state.next_task_id.saturating_add(1);
+    let project_path = refresh_manifest_path
+        .clone()
+        .or_else(|| pending_add_project.as_ref().map(|p| p.path.clone()));
+    let scope = task_scope(&args, project_path.as_deref());
+    state.tasks.push(TaskRecord {
+        id: task_id,
+        label: label.clone(),
+        args,
+        project_path,
+        scope,
+        state: TaskState::Queued,
+        exit_code: None,
+        error: None,
+        refresh_manifest_path,
+        pending_add_project,
+        queued_at: state.now,
+        started_at: None,
+        finished_at: None,
+    });
+    state.status_line = format!("Queued {label}");
+    let mut effects = vec![log_task(
+        state,
+        task_id,
+        LogLevel::Info,
+        format!("queued {label}"),
+    )];
+    effects.extend(schedule_tasks(state));
+    effects
+}
+
+fn task_scope(args: &[String], project_path: Option<&Path>) -> Option<TaskScope> {
+    if let Some(path) = project_path {
+        return Some(TaskScope::Project(crate::services::fs::normalize_path(
+            path,
+        )));
+    }
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["add" | "remove", "repo", ..] | ["install", "templates", ..] => {
+            Some(TaskScope::VpmSettings)
+        }
+        _ => None,
+    }
+}
+
+fn schedule_tasks(state: &mut AppState) -> Vec<Effect> {
+    let now = state.now;
+    let limit = state.max_concurrent_tasks.max(1);
+    let mut running = state
+        .tasks
+        .iter()
+        .filter(|t| t.state == TaskState::Running)
+        .count();
+    let repo_save_in_flight = state.repo_save_in_flight;
+    let mut busy_scopes = state
+        .tasks
+        .iter()
+        .filter(|t| t.state == TaskState::Running)
+        .filter_map(|t| t.scope.clone())
+        .collect::<Vec<_>>();
+
+    let mut started = Vec::new();
+    for task in state
+        .tasks
+        .iter_mut()
+        .filter(|t| t.state == TaskState::Queued)
+    {
+        let blocked = task.scope.as_ref().is_some_and(|scope| {
+            busy_scopes.contains(scope) || (repo_save_in_flight && *scope == TaskScope::VpmSettings)
+        });
+        if let Some(scope) = &task.scope {
+            busy_scopes.push(scope.clone());
+        }
+        if blocked || running >= limit {
+            continue;
+        }
+        task.state = TaskState::Running;
+        task.started_at = Some(now);
+        running += 1;
+        started.push((task.id, task.label.clone(), task.args.clone()));
+    }
+
+    let mut effects = Vec::new();
+    for (task_id, label, args) in started {
+        state.status_line = format!("Running {label}");
+        let command_line = command_line(state, &args);
+        effects.push(log_task(
+            state,
+            task_id,
+            LogLevel::Info,
+            format!("start {command_line}"),
+        ));
+        effects.extend(record_task_history(state, task_id));
+        effects.push(Effect::RunVpmCommand {
+            task_id,
+            label,
+            args,
+        });
+    }
+    effects
+}
+
+fn command_line(state: &AppState, args: &[String]) -> String {
+    let mut parts = vec![state.vpm_settings.program().display().to_string()];
+    parts.extend(args.iter().cloned());
+    parts.join(" ")
+}
+
+fn log_task<T: Into<String>>(
+    state: &mut AppState,
+    task_id: u64,
+    level: LogLevel,
+    text: T,
+) -> Effect {
+    state.push_log(LogSource::Task(task_id), level, text);
+    append_last_log(state, task_id)
+}
+
+fn append_last_log(state: &AppState, task_id: u64) -> Effect {
+    Effect::AppendTaskLog {
+        session: state.session_id.clone(),
+        task_id,
+        line: state
+            .logs
+            .last()
+            .map(|l| l.export_line())
+            .unwrap_or_default(),
+    }
+}
+
+fn record_task_history(state: &mut AppState, task_id: u64) -> Option<Effect> {
+    let task = state.tasks.iter().find(|t| t.id == task_id)?;
+    let entry = TaskHistoryEntry {
+        session: state.session_id.clone(),
+        task_id,
+        label: task.label.clone(),
+        command_line: command_line(state, &task.args),
+        args: task.args.clone(),
+        project_path: task.project_path.clone(),
+        state: task.state,
+        exit_code: task.exit_code,
+        error: task.error.clone(),
+        queued_at: task.queued_at,
+        started_at: task.started_at,
+        finished_at: task.finished_at,
+    };
+    match state
+        .task_history
+        .iter_mut()
+        .find(|e| e.session == entry.session && e.task_id == task_id)
+    {
+        Some(existing) => *existing = entry.clone(),
+        None => state.task_history.push(entry.clone()),
+    }
+    Some(Effect::RecordTaskHistory(entry))
+}
+
+fn cancel_task(state: &mut AppState, task_id: u64) -> Vec<Effect> {
+    let now = state.now;
+    let Some(task) = state.tasks.iter_mut().find(|t| t.id == task_id) else {
+        return vec![];
+    };
+    match task.state {
+        TaskState::Running => vec![Effect::CancelTask { task_id }],
+        TaskState::Queued => {
+            task.state = TaskState::Cancelled;
+            task.finished_at = Some(now);
+            state.status_line = format!("Task {task_id} cancelled before start");
+            let mut effects = vec![log_task(
+                state,
+                task_id,
+                LogLevel::Warn,
+                "cancelled before start",
+            )];
+            effects.extend(record_task_history(state, task_id));
+            effects.extend(schedule_tasks(state));
+            effects
+        }
+        _ => vec![],
+    }
+}
+
+fn enqueue_system_task<const N: usize>(
+    state: &mut AppState,
+    label: &str,
+    args: [&str; N],
+) -> Vec<Effect> {
+    enqueue_project_task(
+        state,
+        label.to_string(),
+        args.into_iter().map(|v| v.to_string()).collect(),
+        None,
+        None,
+    )
+}
+
+fn task_is_label(state: &AppState, task_id: u64, label: &str) -> bool {
+    state
+        .tasks
+        .iter()
+        .find(|t| t.id == task_id)
+        .map(|t| t.label == label)
+        .unwrap_or(false)
+}
+
+fn task_state_text(task: &TaskRecord) -> String {
+    match task.state {
+        TaskState::Queued => "queued".to_string(),
+        TaskState::Running => "running".to_string(),
+        TaskState::Success => "ok".to_string(),
+        TaskState::Cancelled => "cancelled".to_string(),
+        TaskState::Failed => {
+            if let Some(err) = &task.error {
+                format!("failed: {err}")
+            } else {
+                format!("failed (exit={})", task.exit_code.unwrap_or(-1))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::harness::{
+        backup_entry, project_details, sample_state, sample_state_on, version, without_history,
+        Harness, TempDir,
+    };
+    use crate::app::state::{
+        AppConfig, BackupSettings, DiscoveredProject, LogFilter, ProjectKind, RepoEntry,
+        ScanSettings, StreamFilter,
+    };
+    use chrono::{Local, TimeZone};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn enter_on_projects_opens_manage_and_reads_manifest() {
+        let mut h = Harness::new(sample_state());
+        h.press(KeyCode::Char('j'));
+
+        let effects = without_history(h.press(KeyCode::Enter));
+
+        assert_eq!(h.state.screen, Screen::Manage);
+        assert_eq!(
+            effects,
+            vec![Effect::ReadManifest {
+                project_path: PathBuf::from("/projects/WorldProject"),
+            }]
+        );
+    }
+
+    #[test]
+    fn adding_a_project_path_saves_config_and_reads_manifest() {
+        let mut h = Harness::new(sample_state_on(Screen::Add));
+        h.press(KeyCode::Char('a'));
+        h.type_text("/projects/NewWorld");
+
+        let effects = without_history(h.press(KeyCode::Enter));
+
+        assert_eq!(h.state.projects.len(), 3);
+        assert_eq!(h.state.selected_project, 2);
+        assert_eq!(
+            effects,
+            vec![
+                Effect::SaveConfig(h.state.app_config()),
+                Effect::ReadManifest {
+                    project_path: PathBuf::from("/projects/NewWorld"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn adding_the_same_project_spelled_differently_is_rejected() {
+        let temp = TempDir::new("add-project");
+        let project = std::fs::canonicalize(temp.path())
+            .expect("canonical path")
+            .join("World");
+        std::fs::create_dir(&project).expect("create project");
+        let mut h = Harness::new(sample_state_on(Screen::Add));
+        h.press(KeyCode::Char('a'));
+        h.type_text(&project.display().to_string());
+        h.press(KeyCode::Enter);
+
+        h.press(KeyCode::Char('a'));
+        h.type_text(&format!("{}/../World", project.display()));
+        let effects = h.press(KeyCode::Enter);
+
+        assert!(effects.is_empty());
+        assert_eq!(h.state.projects.len(), 3);
+        assert_eq!(h.state.projects[2].path, project);
+        assert_eq!(h.state.status_line, "Project already exists");
+    }
+
+    #[test]
+    fn batch_update_runs_vpm_and_refreshes_manifest_when_done() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+
+        let effects = without_history(h.press(KeyCode::Char('A')));
+
+        assert_eq!(
+            effects,
+            vec![Effect::RunVpmCommand {
+                task_id: 1,
+                label: "vpm add package com.vrchat.avatars@3.6.0 (update from 3.5.0)".to_string(),
+                args: [
+                    "add",
+                    "package",
+                    "com.vrchat.avatars@3.6.0",
+                    "-p",
+                    "/projects/AvatarProject"
+                ]
+                .map(String::from)
+                .to_vec(),
+            }]
+        );
+        assert_eq!(h.state.tasks[0].state, TaskState::Running);
+
+        h.output(1, OutputStream::Stdout, "Added com.vrchat.avatars@3.6.0");
+        let effects = without_history(h.finish(1, 0));
+
+        assert_eq!(h.state.tasks[0].state, TaskState::Success);
+        assert_eq!(
+            effects,
+            vec![Effect::ReadManifest {
+                project_path: PathBuf::from("/projects/AvatarProject"),
+            }]
+        );
+        assert!(h
+            .state
+            .logs
+            .iter()
+            .any(|l| l.display() == "[1:out] Added com.vrchat.avatars@3.6.0"
+                && l.level == LogLevel::Info));
+    }
+
+    #[test]
+    fn failed_task_records_exit_code_and_keeps_manifest_refresh() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        h.press(KeyCode::Char('d'));
+
+        h.output(
+            1,
+            OutputStream::Stderr,
+            "package is required by another package",
+        );
+        let effects = without_history(h.finish(1, 1));
+
+        let task = &h.state.tasks[0];
+        assert_eq!(task.state, TaskState::Failed);
+        assert_eq!(task.exit_code, Some(1));
+        assert_eq!(h.state.status_line, "Task 1 failed");
+        assert!(matches!(effects.as_slice(), [Effect::ReadManifest { .. }]));
+    }
+
+    #[test]
+    fn tasks_on_the_same_project_run_one_at_a_time() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        h.press(KeyCode::Char('A'));
+
+        let effects = without_history(h.press(KeyCode::Char('d')));
+
+        assert!(effects.is_empty());
+        assert_eq!(h.state.tasks[1].state, TaskState::Queued);
+
+        let effects = without_history(h.finish(1, 0));
+
+        assert_eq!(h.state.tasks[1].state, TaskState::Running);
+        assert!(matches!(
+            effects.as_slice(),
+            [
+                Effect::ReadManifest { .. },
+                Effect::RunVpmCommand { task_id: 2, .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn tasks_on_one_project_spelled_two_ways_run_one_at_a_time() {
+        let dir = TempDir::new("task-scope");
+        let project = dir.path().join("AvatarProject");
+        std::fs::create_dir_all(&project).unwrap();
+        let mut h = Harness::new(AppState {
+            projects: vec![
+                ProjectMeta::from_path(project.clone()),
+                ProjectMeta::from_path(project.join("..").join("AvatarProject")),
+            ],
+            ..sample_state_on(Screen::Manage)
+        });
+        h.press(KeyCode::Char('A'));
+        h.state.selected_project = 1;
+
+        let effects = without_history(h.press(KeyCode::Char('A')));
+
+        assert!(effects.is_empty());
+        assert_eq!(h.state.tasks[0].state, TaskState::Running);
+        assert_eq!(h.state.tasks[1].state, TaskState::Queued);
+        assert_eq!(h.state.tasks[0].scope, h.state.tasks[1].scope);
+    }
+
+    #[test]
+    fn vpm_settings_changes_run_one_at_a_time_beside_read_only_tasks() {
+        let mut h = Harness::new(sample_state_on(Screen::Settings));
+        h.press(KeyCode::Char('1'));
+        h.press(KeyCode::Char('t'));
+        h.press(KeyCode::Char('h'));
+
+        assert_eq!(h.state.tasks[0].state, TaskState::Running);
+        assert_eq!(h.state.tasks[1].state, TaskState::Queued);
+        assert_eq!(h.state.tasks[2].state, TaskState::Running);
+
+        h.finish(3, 0);
+        assert_eq!(h.state.tasks[1].state, TaskState::Queued);
+
+        let effects = without_history(h.finish(1, 0));
+
+        assert_eq!(h.state.tasks[1].state, TaskState::Running);
+        assert!(effects
+            .iter()
+            .any(|e| matches!(e, Effect::RunVpmCommand { task_id: 2, .. })));
+    }
+
+    #[test]
+    fn concurrency_cap_queues_tasks_and_queued_tasks_can_be_cancelled() {
+        let mut h = Harness::new(AppState {
+            max_concurrent_tasks: 1,
+            ..sample_state_on(Screen::Settings)
+        });
+        h.press(KeyCode::Char('h'));
+        let effects = without_history(h.press(KeyCode::Char('u')));
+
+        assert!(effects.is_empty());
+        assert_eq!(h.state.tasks[1].state, TaskState::Queued);
+
+        let effects = without_history(h.press(KeyCode::Char('c')));
+
+        assert!(effects.is_empty());
+        assert_eq!(h.state.tasks[1].state, TaskState::Cancelled);
+        assert!(without_history(h.finish(1, 0)).is_empty());
+        assert_eq!(h.state.tasks[1].state, TaskState::Cancelled);
+    }
+
+    #[test]
+    fn tasks_screen_reruns_finished_task_with_same_args() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        h.press(KeyCode::Char('A'));
+        h.finish(1, 1);
+        h.state.screen = Screen::Tasks;
+
+        let effects = without_history(h.press(KeyCode::Char('r')));
+
+        assert_eq!(h.state.tasks.len(), 2);
+        assert_eq!(h.state.tasks[1].args, h.state.tasks[0].args);
+        assert_eq!(
+            h.state.tasks[1].refresh_manifest_path,
+            Some(PathBuf::from("/projects/AvatarProject"))
+        );
+        assert!(matches!(
+            effects.as_slice(),
+            [Effect::RunVpmCommand { task_id: 2, .. }]
+        ));
+    }
+
+    #[test]
+    fn tasks_screen_cancels_the_selected_task() {
+        let mut h = Harness::new(sample_state_on(Screen::Settings));
+        h.press(KeyCode::Char('h'));
+        h.press(KeyCode::Char('u'));
+        h.state.screen = Screen::Tasks;
+        h.press(KeyCode::Char('j'));
+
+        let effects = without_history(h.press(KeyCode::Char('c')));
+
+        assert_eq!(effects, vec![Effect::CancelTask { task_id: 1 }]);
+    }
+
+    #[test]
+    fn task_times_come_from_the_actions_that_change_them() {
+        let queued = Local.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        let done = queued + chrono::Duration::seconds(42);
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        h.state.now = queued;
+        h.press(KeyCode::Char('A'));
+        h.output(1, OutputStream::Stdout, "Resolving");
+        h.state.now = done;
+        h.finish(1, 0);
+
+        let task = &h.state.tasks[0];
+        assert_eq!(task.queued_at, queued);
+        assert_eq!(task.started_at, Some(queued));
+        assert_eq!(task.finished_at, Some(done));
+        assert_eq!(h.state.task_history[0].finished_at, Some(done));
+        let stamps = h
+            .state
+            .logs
+            .iter()
+            .filter(|l| l.task_id() == Some(1))
+            .map(|l| (l.text.as_str(), l.timestamp))
+            .collect::<Vec<_>>();
+        assert!(stamps.contains(&("Resolving", queued)));
+        assert!(stamps.contains(&("done", done)));
+    }
+
+    #[test]
+    fn task_output_scrolls_back_and_returns_to_the_newest_line() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        h.press(KeyCode::Char('A'));
+        for n in 0..40 {
+            h.output(
+                1,
+                OutputStream::Stdout,
+                &format!("Resolving dependency {n}"),
+            );
+        }
+        h.state.screen = Screen::Tasks;
+        assert!(!h.render().contains("Resolving dependency 18"));
+
+        for _ in 0..3 {
+            h.press(KeyCode::Char('K'));
+        }
+        h.press(KeyCode::Char('J'));
+        assert_eq!(h.state.task_output_scroll, 2);
+        let screen = h.render();
+        assert!(screen.contains("(scrolled 2 up, [G] newest)"));
+        assert!(screen.contains("Resolving dependency 18"));
+
+        for _ in 0..100 {
+            h.press(KeyCode::Char('K'));
+        }
+        assert_eq!(h.state.task_output_scroll, h.state.task_logs(1).len());
+        h.press(KeyCode::Char('G'));
+        assert_eq!(h.state.task_output_scroll, 0);
+        h.press(KeyCode::Char('K'));
+        h.press(KeyCode::Char('j'));
+        assert_eq!(h.state.task_output_scroll, 0);
+    }
+
+    #[test]
+    fn version_picker_starts_on_the_installed_version_and_hides_prereleases() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        h.state.available_packages[0]
+            .versions
+            .insert(0, version("3.7.0-beta.1"));
+
+        let effects = h.press(KeyCode::Enter);
+        let picker = h.state.version_picker.as_ref().expect("picker opened");
+        assert!(effects.is_empty());
+        assert_eq!(picker.package_id, "com.vrchat.avatars");
+        assert_eq!(picker.installed_version.as_deref(), Some("3.5.0"));
+        assert_eq!(
+            picker
+                .versions
+                .iter()
+                .map(|v| v.version.as_str())
+                .collect::<Vec<_>>(),
+            vec!["3.6.0", "3.5.0"]
+        );
+        assert_eq!(picker.selected, 1);
+
+        h.press(KeyCode::Char('j'));
+        assert_eq!(h.state.version_picker.as_ref().map(|p| p.selected), Some(1));
+        h.press(KeyCode::Char('k'));
+        h.press(KeyCode::Char('k'));
+        assert_eq!(h.state.version_picker.as_ref().map(|p| p.selected), Some(0));
+        assert!(h.press(KeyCode::Esc).is_empty());
+        assert!(h.state.version_picker.is_none());
+
+        h.press(KeyCode::Char('p'));
+        h.press(KeyCode::Char('@'));
+        let picker = h.state.version_picker.as_ref().expect("picker reopened");
+        assert_eq!(picker.versions[0].version, "3.7.0-beta.1");
+        assert_eq!(picker.selected, 2);
+    }
+
+    #[test]
+    fn version_picker_installs_the_chosen_version_into_the_selected_project() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        h.press(KeyCode::Enter);
+        h.press(KeyCode::Char('k'));
+
+        let effects = without_history(h.press(KeyCode::Enter));
+
+        assert!(h.state.version_picker.is_none());
+        assert_eq!(
+            effects,
+            vec![Effect::RunVpmCommand {
+                task_id: 1,
+                label: "vpm add package com.vrchat.avatars@3.6.0".to_string(),
+                args: vec![
+                    "add".to_string(),
+                    "package".to_string(),
+                    "com.vrchat.avatars@3.6.0".to_string(),
+                    "-p".to_string(),
+                    "/projects/AvatarProject".to_string(),
+                ],
+            }]
+        );
+        assert_eq!(
+            h.state.tasks[0].refresh_manifest_path,
+            Some(PathBuf::from("/projects/AvatarProject"))
+        );
+    }
+
+    #[test]
+    fn version_picker_needs_a_catalog_entry_and_a_project() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        h.state.available_packages.remove(0);
+        h.press(KeyCode::Char('l'));
+        h.press(KeyCode::Enter);
+        assert!(h.state.version_picker.is_none());
+        assert_eq!(
+            h.state.status_line,
+            "com.vrchat.avatars is not in the available package catalog"
+        );
+
+        h.press(KeyCode::Char('h'));
+        h.press(KeyCode::Enter);
+        let picker = h.state.version_picker.as_ref().expect("picker opened");
+        assert_eq!(picker.package_id, "com.vrchat.base");
+        assert_eq!(picker.installed_version.as_deref(), Some("3.5.0"));
+
+        h.state.projects.clear();
+        let effects = h.press(KeyCode::Enter);
+        assert!(effects.is_empty());
+        assert!(h.state.tasks.is_empty());
+        assert_eq!(h.state.status_line, "No project selected");
+    }
+
+    fn history_entry(session: &str, task_id: u64, args: &[&str]) -> TaskHistoryEntry {
+        TaskHistoryEntry {
+            session: session.to_string(),
+            task_id,
+            label: format!("vpm {}", args.join(" ")),
+            command_line: format!("vpm {}", args.join(" ")),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            project_path: Some(PathBuf::from("/projects/AvatarProject")),
+            state: TaskState::Failed,
+            exit_code: Some(1),
+            error: None,
+            queued_at: Local::now(),
+            started_at: Some(Local::now()),
+            finished_at: Some(Local::now()),
+        }
+    }
+
+    #[test]
+    fn task_history_browses_past_sessions_and_reruns() {
+        let mut h = Harness::new(sample_state_on(Screen::Tasks));
+        h.send(Action::Init);
+        h.send(Action::TaskHistoryLoaded(Ok(vec![
+            history_entry("20240501-090000", 1, &["resolve", "project"]),
+            history_entry("20240502-090000", 1, &["check", "hub"]),
+            history_entry("20240502-090000", 2, &["list", "repos"]),
+        ])));
+
+        let effects = h.press(KeyCode::Char('H'));
+        assert_eq!(
+            effects,
+            vec![Effect::ReadHistoryLog {
+                session: "20240502-090000".to_string(),
+                task_id: 2,
+            }]
+        );
+
+        let effects = h.press(KeyCode::Char(']'));
+        assert_eq!(
+            effects,
+            vec![Effect::ReadHistoryLog {
+                session: "20240501-090000".to_string(),
+                task_id: 1,
+            }]
+        );
+        h.send(Action::HistoryLogLoaded(Ok(vec![
+            "[1:err] could not resolve".to_string(),
+        ])));
+        assert_eq!(h.state.history_log, vec!["[1:err] could not resolve"]);
+
+        let effects = without_history(h.press(KeyCode::Char('r')));
+        assert!(!h.state.show_task_history);
+        assert!(matches!(
+            effects.as_slice(),
+            [Effect::RunVpmCommand { args, .. }] if args == &["resolve", "project"]
+        ));
+    }
+
+    #[test]
+    fn finished_task_is_recorded_in_history() {
+        let mut h = Harness::new(sample_state_on(Screen::Settings));
+        h.state.session_id = "20240501-090000-4242".to_string();
+        let started = h.press(KeyCode::Char('h'));
+        assert!(started.iter().any(|e| matches!(
+            e,
+            Effect::RecordTaskHistory(entry)
+                if entry.state == TaskState::Running && entry.finished_at.is_none()
+        )));
+        assert_eq!(h.state.task_history.len(), 1);
+        h.output(1, OutputStream::Stderr, "hub missing");
+
+        let effects = h.finish(1, 1);
+
+        let session = h.state.session_id.clone();
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::AppendTaskLog { session: s, task_id: 1, line }
+                if s == &session && line.ends_with(" error [task:1] failed")
+        )));
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::RecordTaskHistory(entry)
+                if entry.session == session
+                    && entry.command_line == "vpm check hub"
+                    && entry.exit_code == Some(1)
+        )));
+        assert_eq!(h.state.task_history.len(), 1);
+        assert_eq!(h.state.task_history[0].state, TaskState::Failed);
+
+        h.send(Action::TaskHistorySaved(Err("disk full".to_string())));
+        assert_eq!(h.state.status_line, "Task history save failed");
+    }
+
+    #[test]
+    fn log_level_comes_from_stream_and_stderr_tag() {
+        let mut h = Harness::new(sample_state_on(Screen::Settings));
+        h.press(KeyCode::Char('h'));
+        h.output(1, OutputStream::Stdout, "0 checks failed");
+        h.output(1, OutputStream::Stderr, "warning: hub path unset");
+        h.output(1, OutputStream::Stderr, "Resolving packages");
+        h.output(1, OutputStream::Stderr, "[09:00:00 ERR] hub not found");
+        h.press(KeyCode::Char('r'));
+        h.output(2, OutputStream::Stdout, "Official");
+
+        let levels = h
+            .state
+            .task_logs(1)
+            .into_iter()
+            .filter(|l| l.stream.is_some())
+            .map(|l| l.level)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            levels,
+            vec![
+                LogLevel::Info,
+                LogLevel::Warn,
+                LogLevel::Warn,
+                LogLevel::Error
+            ]
+        );
+
+        h.state.log_filter = LogFilter {
+            min_level: Some(LogLevel::Error),
+            ..LogFilter::default()
+        };
+        let errors = h
+            .state
+            .filtered_logs(h.state.log_filter)
+            .into_iter()
+            .map(|l| l.display())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec!["[1:err] [09:00:00 ERR] hub not found".to_string()]
+        );
+        let exported = h
+            .state
+            .task_logs(1)
+            .into_iter()
+            .map(|l| LogLevel::from_export_line(&l.export_line()))
+            .collect::<Vec<_>>();
+        assert!(exported.ends_with(&[
+            Some(LogLevel::Info),
+            Some(LogLevel::Warn),
+            Some(LogLevel::Warn),
+            Some(LogLevel::Error)
+        ]));
+    }
+
+    #[test]
+    fn log_pane_follows_tail_until_scrolled_and_pages() {
+        let mut h = Harness::new(sample_state_on(Screen::Settings));
+        h.press(KeyCode::Char('h'));
+        for n in 0..30 {
+            h.output(1, OutputStream::Stdout, &format!("line {n}"));
+        }
+        let max = h.state.log_max_scroll();
+        assert!(h.state.log_follow);
+        assert_eq!(h.state.log_scroll_offset(), max);
+
+        h.press(KeyCode::PageUp);
+        assert!(!h.state.log_follow);
+        assert_eq!(h.state.log_scroll_offset(), max - h.state.log_pane_rows);
+
+        h.output(1, OutputStream::Stdout, "line 30");
+        assert_eq!(h.state.log_scroll_offset(), max - h.state.log_pane_rows);
+
+        h.press(KeyCode::Home);
+        assert_eq!(h.state.log_scroll_offset(), 0);
+        h.press(KeyCode::End);
+        assert!(h.state.log_follow);
+        assert_eq!(h.state.log_scroll_offset(), max + 1);
+
+        h.send(Action::Resize { height: 14 });
+        assert_eq!(h.state.log_pane_rows, 3);
+        let bottom = h.state.log_scroll_offset();
+        h.press(KeyCode::PageUp);
+        assert_eq!(h.state.log_scroll_offset(), bottom - 3);
+    }
+
+    #[test]
+    fn log_pane_searches_filters_and_exports() {
+        let mut h = Harness::new(sample_state_on(Screen::Settings));
+        h.press(KeyCode::Char('h'));
+        for n in 0..20 {
+            h.output(1, OutputStream::Stdout, &format!("resolving {n}"));
+        }
+        h.output(1, OutputStream::Stderr, "error: Resolve failed");
+        h.press(KeyCode::Char('r'));
+        h.output(2, OutputStream::Stdout, "Official");
+
+        h.press(KeyCode::Char('L'));
+        h.press(KeyCode::Char('/'));
+        h.type_text("resolv");
+        h.press(KeyCode::Enter);
+        assert!(h.state.log_focus);
+        assert_eq!(h.state.log_matches().len(), 21);
+
+        let matches = h.state.log_matches();
+        let first_visible = h.state.log_match;
+        assert!(matches[first_visible] >= h.state.log_scroll_offset());
+
+        h.press(KeyCode::Home);
+        h.press(KeyCode::Char('n'));
+        assert_eq!(h.state.log_match, first_visible + 1);
+        let offset = h.state.log_scroll_offset();
+        assert!((offset..offset + h.state.log_pane_rows).contains(&matches[first_visible + 1]));
+        for _ in first_visible + 1..matches.len() {
+            h.press(KeyCode::Char('n'));
+        }
+        assert_eq!(h.state.log_match, 0);
+        h.press(KeyCode::Char('N'));
+        assert_eq!(h.state.log_match, matches.len() - 1);
+
+        h.press(KeyCode::Char('t'));
+        assert_eq!(h.state.log_filter.task_id, Some(1));
+        h.press(KeyCode::Char('s'));
+        h.press(KeyCode::Char('s'));
+        assert_eq!(h.state.log_filter.stream, StreamFilter::Stderr);
+        let effects = h.press(KeyCode::Char('x'));
+        assert!(matches!(
+            effects.as_slice(),
+            [Effect::ExportLogs { lines }]
+                if lines.len() == 1 && lines[0].ends_with("error [1:err] error: Resolve failed")
+        ));
+
+        h.press(KeyCode::Char('t'));
+        h.press(KeyCode::Char('t'));
+        assert_eq!(h.state.log_filter.task_id, None);
+        h.press(KeyCode::Esc);
+        assert!(!h.state.log_focus);
+    }
+
+    #[test]
+    fn folder_scan_streams_results_and_adds_chosen_projects() {
+        let mut h = Harness::new(sample_state_on(Screen::Add));
+        h.press(KeyCode::Char('f'));
+        h.type_text("/projects");
+        let effects = h.press(KeyCode::Enter);
+        assert_eq!(
+            effects,
+            vec![Effect::ScanProjectsFolder {
+                scan_id: 1,
+                root: PathBuf::from("/projects"),
+                settings: ScanSettings::default(),
+            }]
+        );
+
+        let root = PathBuf::from("/projects");
+        h.send(Action::ScanProgress {
+            scan_id: 1,
+            root: root.clone(),
+            visited: 26,
+            dir: PathBuf::from("/projects/Clients"),
+        });
+        for (path, kind) in [
+            ("/projects/AvatarProject", ProjectKind::Vpm),
+            ("/projects/Clients/Acme/World", ProjectKind::Vpm),
+            ("/projects/Clients/Acme/Old", ProjectKind::Unity),
+        ] {
+            h.send(Action::ProjectDiscovered {
+                scan_id: 1,
+                root: root.clone(),
+                project: DiscoveredProject {
+                    path: PathBuf::from(path),
+                    kind,
+                },
+            });
+        }
+        h.send(Action::ProjectDiscovered {
+            scan_id: 1,
+            root: PathBuf::from("/elsewhere"),
+            project: DiscoveredProject {
+                path: PathBuf::from("/elsewhere/Stray"),
+                kind: ProjectKind::Vpm,
+            },
+        });
+        h.send(Action::ScanFinished {
+            scan_id: 1,
+            root,
+            result: Ok(3),
+        });
+
+        let scan = h.state.project_scan.as_ref().expect("scan results");
+        assert!(!scan.is_running());
+        assert_eq!(scan.visited, 26);
+        assert_eq!(scan.chosen, vec![false, true, true]);
+        assert_eq!(
+            h.state.status_line,
+            "Scan complete: 3 project(s) found, 2 not yet added"
+        );
+
+        h.press(KeyCode::Char(' '));
+        h.press(KeyCode::Char('j'));
+        h.press(KeyCode::Char('j'));
+        h.press(KeyCode::Char(' '));
+        let effects = h.press(KeyCode::Enter);
+
+        let paths = h
+            .state
+            .projects
+            .iter()
+            .map(|p| p.path.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "/projects/AvatarProject",
+                "/projects/WorldProject",
+                "/projects/Clients/Acme/World",
+            ]
+        );
+        assert_eq!(effects, vec![Effect::SaveConfig(h.state.app_config())]);
+        assert!(h.state.project_scan.is_none());
+    }
+
+    #[test]
+    fn rescanning_a_root_ignores_events_from_the_discarded_scan() {
+        let mut h = Harness::new(sample_state_on(Screen::Add));
+        h.press(KeyCode::Char('f'));
+        h.type_text("/projects");
+        h.press(KeyCode::Enter);
+        h.press(KeyCode::Esc);
+        h.press(KeyCode::Char('f'));
+        h.type_text("/projects");
+        let effects = h.press(KeyCode::Enter);
+        assert!(matches!(
+            effects.as_slice(),
+            [Effect::ScanProjectsFolder { scan_id: 2, .. }]
+        ));
+
+        let root = PathBuf::from("/projects");
+        for (scan_id, path) in [(1, "/projects/Stale"), (2, "/projects/Fresh")] {
+            h.send(Action::ProjectDiscovered {
+                scan_id,
+                root: root.clone(),
+                project: DiscoveredProject {
+                    path: PathBuf::from(path),
+                    kind: ProjectKind::Vpm,
+                },
+            });
+        }
+        h.send(Action::ScanFinished {
+            scan_id: 1,
+            root: root.clone(),
+            result: Ok(1),
+        });
+        assert!(h
+            .state
+            .project_scan
+            .as_ref()
+            .is_some_and(|s| s.is_running()));
+        h.send(Action::ScanFinished {
+            scan_id: 2,
+            root,
+            result: Ok(1),
+        });
+
+        let scan = h.state.project_scan.as_ref().expect("scan results");
+        assert_eq!(
+            scan.found
+                .iter()
+                .map(|p| p.path.clone())
+                .collect::<Vec<_>>(),
+            vec![PathBuf::from("/projects/Fresh")]
+        );
+    }
+
+    #[test]
+    fn scan_ignore_patterns_are_added_and_removed_from_the_add_screen() {
+        let mut h = Harness::new(sample_state_on(Screen::Add));
+        h.press(KeyCode::Char('i'));
+        h.type_text("Backup*");
+        let effects = h.press(KeyCode::Enter);
+        assert!(h
+            .state
+            .scan_settings
+            .ignore
+            .contains(&"Backup*".to_string()));
+        assert_eq!(effects, vec![Effect::SaveConfig(h.state.app_config())]);
+
+        h.press(KeyCode::Char('i'));
+        h.type_text(".git");
+        h.press(KeyCode::Enter);
+        assert_eq!(
+            h.state.scan_settings.ignore,
+            vec!["Library", "Temp", "node_modules", "Backup*"]
+        );
+        assert_eq!(h.state.status_line, "No longer ignoring .git");
+    }
+
+    #[test]
+    fn watch_scans_wait_for_a_manual_scan_and_merge_into_a_running_one() {
+        let temp = TempDir::new("watch-queue");
+        let watched = temp.path().join("Watched");
+        std::fs::create_dir(&watched).expect("create watch folder");
+        let canonical = std::fs::canonicalize(&watched).expect("canonical path");
+        let mut h = Harness::new(sample_state_on(Screen::Add));
+        h.press(KeyCode::Char('f'));
+        h.type_text("/projects");
+        h.press(KeyCode::Enter);
+
+        let config = AppConfig {
+            projects: h.state.projects.clone(),
+            watch_folders: vec![watched.clone(), watched.join("..").join("Watched")],
+            ..AppConfig::default()
+        };
+        let effects = h.send(Action::ConfigLoaded(Ok(config.clone())));
+        assert!(!effects
+            .iter()
+            .any(|e| matches!(e, Effect::ScanProjectsFolder { .. })));
+        assert_eq!(h.state.queued_watch_roots, vec![canonical.clone()]);
+        assert!(h
+            .state
+            .project_scan
+            .as_ref()
+            .is_some_and(|scan| scan.id == 1 && !scan.watch));
+
+        let effects = h.press(KeyCode::Esc);
+        assert_eq!(
+            effects,
+            vec![Effect::ScanProjectsFolder {
+                scan_id: 2,
+                root: canonical.clone(),
+                settings: h.state.scan_settings.clone(),
+            }]
+        );
+        assert!(h.state.queued_watch_roots.is_empty());
+
+        let effects = h.send(Action::ConfigLoaded(Ok(AppConfig {
+            watch_folders: vec![watched.clone(), PathBuf::from("/clients")],
+            ..config
+        })));
+        let scanned = effects
+            .iter()
+            .filter_map(|e| match e {
+                Effect::ScanProjectsFolder { scan_id, root, .. } => Some((*scan_id, root.clone())),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(scanned, vec![(2, PathBuf::from("/clients"))]);
+        assert_eq!(
+            h.state.project_scan.as_ref().map(|scan| scan.roots.clone()),
+            Some(vec![canonical, PathBuf::from("/clients")])
+        );
+    }
+
+    #[test]
+    fn watch_folders_rescan_at_startup_offer_new_projects_and_flag_missing_ones() {
+        let mut h = Harness::new(AppState::default());
+        let config = AppConfig {
+            projects: vec![
+                ProjectMeta::from_path(PathBuf::from("/watched/Gone")),
+                ProjectMeta::from_path(PathBuf::from("/watched/Kept")),
+                ProjectMeta::from_path(PathBuf::from("/elsewhere/Old")),
+            ],
+            watch_folders: vec![PathBuf::from("/watched"), PathBuf::from("/clients")],
+            ..AppConfig::default()
+        };
+
+        let effects = h.send(Action::ConfigLoaded(Ok(config)));
+
+        let scanned = effects
+            .iter()
+            .filter_map(|e| match e {
+                Effect::ScanProjectsFolder { root, .. } => Some(root.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            scanned,
+            vec![PathBuf::from("/watched"), PathBuf::from("/clients")]
+        );
+        assert_eq!(h.state.projects.len(), 3);
+        assert!(h.state.offline_projects.is_empty());
+        h.send(Action::ProjectPathsChecked(vec![PathBuf::from(
+            "/watched/Gone",
+        )]));
+        assert!(h
+            .state
+            .offline_projects
+            .contains(Path::new("/watched/Gone")));
+
+        for (root, path) in [("/watched", "/watched/Kept"), ("/watched", "/watched/New")] {
+            h.send(Action::ProjectDiscovered {
+                scan_id: 1,
+                root: PathBuf::from(root),
+                project: DiscoveredProject {
+                    path: PathBuf::from(path),
+                    kind: ProjectKind::Vpm,
+                },
+            });
+        }
+        h.send(Action::ScanFinished {
+            scan_id: 1,
+            root: PathBuf::from("/watched"),
+            result: Ok(2),
+        });
+        assert!(h
+            .state
+            .offline_projects
+            .contains(Path::new("/watched/Gone")));
+        assert!(!h
+            .state
+            .offline_projects
+            .contains(Path::new("/watched/Kept")));
+        h.send(Action::ScanFinished {
+            scan_id: 1,
+            root: PathBuf::from("/clients"),
+            result: Err("failed to read /clients".to_string()),
+        });
+
+        let scan = h.state.project_scan.as_ref().expect("new projects offered");
+        assert_eq!(
+            scan.found
+                .iter()
+                .map(|p| p.path.clone())
+                .collect::<Vec<_>>(),
+            vec![PathBuf::from("/watched/New")]
+        );
+        assert_eq!(
+            h.state.status_line,
+            "Watch folders: 1 new project(s) found; review them on the Add screen"
+        );
+
+        h.state.screen = Screen::Add;
+        let effects = h.press(KeyCode::Enter);
+        assert_eq!(effects, vec![Effect::SaveConfig(h.state.app_config())]);
+        assert!(h
+            .state
+            .projects
+            .iter()
+            .any(|p| p.path == Path::new("/watched/New")));
+    }
+
+    #[test]
+    fn watch_folders_are_added_removed_and_rescanned_from_the_add_screen() {
+        let mut h = Harness::new(sample_state_on(Screen::Add));
+        h.press(KeyCode::Char('w'));
+        h.type_text("/projects");
+        let effects = h.press(KeyCode::Enter);
+        assert_eq!(h.state.watch_folders, vec![PathBuf::from("/projects")]);
+        assert!(matches!(
+            effects.as_slice(),
+            [Effect::SaveConfig(config), Effect::ScanProjectsFolder { root, .. }]
+                if config.watch_folders == vec![PathBuf::from("/projects")]
+                    && root == Path::new("/projects")
+        ));
+
+        h.send(Action::ScanFinished {
+            scan_id: 1,
+            root: PathBuf::from("/projects"),
+            result: Ok(0),
+        });
+        assert!(h.state.project_scan.is_none());
+        assert_eq!(h.state.status_line, "Watch folders: no new projects");
+
+        let effects = h.press(KeyCode::Char('W'));
+        assert_eq!(effects.len(), 1);
+
+        h.state.project_scan = None;
+        let effects = h.press(KeyCode::Char('x'));
+        assert!(h.state.watch_folders.is_empty());
+        assert_eq!(effects, vec![Effect::SaveConfig(h.state.app_config())]);
+    }
+
+    #[test]
+    fn missing_projects_stay_listed_as_offline_and_recover() {
+        let mut h = Harness::new(AppState::default());
+        let config = AppConfig {
+            projects: vec![
+                ProjectMeta::from_path(PathBuf::from("/Volumes/External/Avatar")),
+                ProjectMeta::from_path(PathBuf::from("/Volumes/External/World")),
+            ],
+            ..AppConfig::default()
+        };
+
+        let paths = vec![
+            PathBuf::from("/Volumes/External/Avatar"),
+            PathBuf::from("/Volumes/External/World"),
+        ];
+        let effects = h.send(Action::ConfigLoaded(Ok(config)));
+
+        assert_eq!(h.state.projects.len(), 2);
+        assert!(h.state.offline_projects.is_empty());
+        assert!(effects.contains(&Effect::CheckProjectPaths(paths.clone())));
+        assert!(!effects.iter().any(|e| matches!(
+            e,
+            Effect::SaveConfig(_) | Effect::ReadManifest { .. } | Effect::ReadProjectDetails(_)
+        )));
+
+        let effects = h.send(Action::ProjectPathsChecked(paths.clone()));
+        assert!(effects.is_empty());
+        assert_eq!(h.state.offline_projects.len(), 2);
+
+        h.state.screen = Screen::Projects;
+        assert!(h.press(KeyCode::Enter).is_empty());
+        assert_eq!(h.state.screen, Screen::Projects);
+
+        let checks = (0..PROJECT_CHECK_TICKS * 2)
+            .flat_map(|_| h.send(Action::Tick))
+            .collect::<Vec<_>>();
+        assert_eq!(checks, vec![Effect::CheckProjectPaths(paths)]);
+        assert!(h.state.project_check_pending);
+
+        let effects = h.send(Action::ProjectPathsChecked(vec![PathBuf::from(
+            "/Volumes/External/World",
+        )]));
+        assert_eq!(
+            effects,
+            vec![
+                Effect::ReadManifest {
+                    project_path: PathBuf::from("/Volumes/External/Avatar"),
+                },
+                Effect::ReadProjectDetails(vec![PathBuf::from("/Volumes/External/Avatar")]),
+            ]
+        );
+        assert_eq!(
+            h.state.offline_projects.iter().collect::<Vec<_>>(),
+            vec![Path::new("/Volumes/External/World")]
+        );
+
+        h.send(Action::ProjectDetailsLoaded(project_details(&[(
+            "/Volumes/External/Avatar",
+            "2022.3.22f1",
+        )])));
+        let effects = h.send(Action::ProjectPathsChecked(vec![
+            PathBuf::from("/Volumes/External/Avatar"),
+            PathBuf::from("/Volumes/External/World"),
+        ]));
+        assert!(effects.is_empty());
+        assert_eq!(
+            h.state
+                .project_versions
+                .get(Path::new("/Volumes/External/Avatar"))
+                .map(|v| v.as_str()),
+            Some("2022.3.22f1")
+        );
+    }
+
+    #[test]
+    fn removing_a_project_needs_confirmation_and_keeps_files() {
+        let mut h = Harness::new(sample_state_on(Screen::Projects));
+        h.press(KeyCode::Char('d'));
+        assert!(h.press(KeyCode::Char('n')).is_empty());
+        assert_eq!(h.state.projects.len(), 2);
+        assert_eq!(h.state.status_line, "Project removal cancelled");
+
+        h.press(KeyCode::Char('d'));
+        let effects = h.press(KeyCode::Char('y'));
+
+        assert_eq!(
+            h.state
+                .projects
+                .iter()
+                .map(|p| p.display_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["WorldProject"]
+        );
+        assert_eq!(effects, vec![Effect::SaveConfig(h.state.app_config())]);
+    }
+
+    #[test]
+    fn manifest_loaded_error_clears_previous_manifest() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+
+        let effects = h.send(Action::ManifestLoaded {
+            project_path: PathBuf::from("/projects/AvatarProject"),
+            result: Err("broken json".to_string()),
+        });
+
+        assert!(effects.is_empty());
+        assert!(h.state.selected_project_manifest.is_none());
+        assert!(
+            h.state
+                .logs
+                .iter()
+                .any(|l| l.display() == "[manifest] failed: broken json"
+                    && l.level == LogLevel::Error)
+        );
+    }
+
+    #[test]
+    fn legacy_project_migration_backs_up_runs_vpm_and_verifies() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        h.press(KeyCode::Char('M'));
+        assert!(h.state.migration.is_none());
+        assert_eq!(
+            h.state.status_line,
+            "AvatarProject is not a legacy SDK3 project"
+        );
+
+        let project = PathBuf::from("/projects/AvatarProject");
+        let backup = backup_entry(
+            "/backups/AvatarProject-20260101-000000.zip",
+            "2026-01-01 00:00:00",
+            2048,
+        );
+        if let Some(summary) = h.state.selected_project_manifest.as_mut() {
+            summary.exists = false;
+            summary.kind = Some(ProjectKind::LegacySdk3);
+        }
+        h.press(KeyCode::Char('M'));
+        assert!(h.press(KeyCode::Char('n')).is_empty());
+        assert!(h.state.migration.is_none());
+
+        h.press(KeyCode::Char('M'));
+        assert_eq!(
+            h.press(KeyCode::Char('y')),
+            vec![Effect::BackupProject {
+                project_path: project.clone(),
+                settings: BackupSettings::default(),
+            }]
+        );
+        let effects = without_history(h.send(Action::ProjectBackedUp {
+            project_path: project.clone(),
+            result: Ok(backup.clone()),
+            skipped: Vec::new(),
+        }));
+        assert!(matches!(
+            effects.as_slice(),
+            [Effect::RunVpmCommand { args, .. }]
+                if args == &["migrate", "project", "/projects/AvatarProject", "--inplace"]
+        ));
+
+        h.state.selected_project = 1;
+        let effects = without_history(h.finish(1, 0));
+        assert!(effects.contains(&Effect::ReadManifest {
+            project_path: project.clone(),
+        }));
+        assert_eq!(
+            h.state.migration.as_ref().map(|m| &m.stage),
+            Some(&MigrationStage::Verifying)
+        );
+
+        let mut migrated = sample_state().selected_project_manifest.expect("manifest");
+        migrated.kind = Some(ProjectKind::Vpm);
+        h.send(Action::ManifestLoaded {
+            project_path: PathBuf::from("/projects/WorldProject"),
+            result: Ok(migrated.clone()),
+        });
+        assert!(h.state.migration.is_some());
+        h.send(Action::ManifestLoaded {
+            project_path: project.clone(),
+            result: Ok(migrated),
+        });
+        assert!(h.state.migration.is_none());
+        assert_eq!(
+            h.state.project_manifests.get(&project).and_then(|m| m.kind),
+            Some(ProjectKind::Vpm)
+        );
+        assert_eq!(
+            h.state.logs.last().map(|l| (l.level, l.display())),
+            Some((
+                LogLevel::Success,
+                format!(
+                    "[projects] migration verified: 2 package(s) in vpm-manifest.json; backup at {}",
+                    backup.path.display()
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn failed_migration_task_reports_the_backup_location() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        let project = PathBuf::from("/projects/AvatarProject");
+        h.state.migration = Some(Migration {
+            project_path: project.clone(),
+            backup_path: None,
+            stage: MigrationStage::BackingUp,
+        });
+        h.send(Action::ProjectBackedUp {
+            project_path: project,
+            result: Ok(backup_entry(
+                "/projects/backup.zip",
+                "2026-01-01 00:00:00",
+                1,
+            )),
+            skipped: Vec::new(),
+        });
+
+        h.finish(1, 1);
+
+        assert!(h.state.migration.is_none());
+        assert_eq!(h.state.status_line, "Migration failed");
+        assert!(h.state.logs.iter().any(|l| l.level == LogLevel::Error
+            && l.display() == "[projects] migration failed; backup kept at /projects/backup.zip"));
+    }
+
+    #[test]
+    fn backups_are_listed_created_and_restored_into_a_new_folder() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        let project = PathBuf::from("/projects/AvatarProject");
+        h.state.backup_settings.folder = Some(PathBuf::from("/backups"));
+        let settings = h.state.backup_settings.clone();
+
+        assert_eq!(
+            h.press(KeyCode::Char('b')),
+            vec![Effect::ListBackups {
+                project_path: project.clone(),
+                settings: settings.clone(),
+            }]
+        );
+        h.send(Action::BackupsListed {
+            project_path: project.clone(),
+            result: Ok(vec![backup_entry(
+                "/backups/AvatarProject-20260101-120000.zip",
+                "2026-01-01 12:00:00",
+                4096,
+            )]),
+        });
+        assert_eq!(
+            h.press(KeyCode::Char('n')),
+            vec![Effect::BackupProject {
+                project_path: project.clone(),
+                settings,
+            }]
+        );
+        assert!(h.press(KeyCode::Char('n')).is_empty());
+        h.send(Action::ProjectBackedUp {
+            project_path: project.clone(),
+            result: Ok(backup_entry(
+                "/backups/AvatarProject-20260102-090000.zip",
+                "2026-01-02 09:00:00",
+                5_242_880,
+            )),
+            skipped: vec![project.join("Packages/broken")],
+        });
+        let browser = h.state.backup_browser.as_ref().expect("browser");
+        assert_eq!(browser.entries.len(), 2);
+        assert!(!browser.busy);
+        assert_eq!(
+            h.state
+                .logs
+                .iter()
+                .rev()
+                .take(2)
+                .map(|l| l.display())
+                .collect::<Vec<_>>(),
+            vec![
+                "[projects] backup created at /backups/AvatarProject-20260102-090000.zip (5.0 MB)",
+                "[projects] backup left out /projects/AvatarProject/Packages/broken: the link is broken or loops",
+            ]
+        );
+
+        h.press(KeyCode::Char('j'));
+        h.press(KeyCode::Enter);
+        assert!(h.press(KeyCode::Char('x')).is_empty());
+        assert_eq!(h.state.status_line, "Restore cancelled");
+        h.press(KeyCode::Enter);
+        assert_eq!(
+            h.state.status_line,
+            "Restore AvatarProject-20260101-120000.zip into /projects/AvatarProject-restored-20260101-120000? [y] confirm, any other key cancels"
+        );
+        let target = PathBuf::from("/projects/AvatarProject-restored-20260101-120000");
+        assert_eq!(
+            h.press(KeyCode::Char('y')),
+            vec![Effect::RestoreBackup {
+                backup: PathBuf::from("/backups/AvatarProject-20260101-120000.zip"),
+                target: target.clone(),
+            }]
+        );
+
+        let effects = h.send(Action::BackupRestored {
+            backup: PathBuf::from("/backups/AvatarProject-20260101-120000.zip"),
+            result: Ok(target.clone()),
+        });
+        assert!(h.state.projects.iter().any(|p| p.path == target));
+        assert!(matches!(
+            effects.as_slice(),
+            [Effect::SaveConfig(config), Effect::ReadProjectDetails(paths)]
+                if config.projects.len() == 3 && paths == &vec![target.clone()]
+        ));
+
+        h.press(KeyCode::Esc);
+        assert!(h.state.backup_browser.is_none());
+    }
+
+    #[test]
+    fn projects_using_an_uninstalled_editor_are_warned_once() {
+        let mut h = Harness::new(sample_state_on(Screen::Projects));
+        h.send(Action::ProjectDetailsLoaded(project_details(&[
+            ("/projects/AvatarProject", "2019.4.31f1"),
+            ("/projects/WorldProject", "2022.3.22f1"),
+        ])));
+        assert_eq!(
+            h.state
+                .editor_installed(Path::new("/projects/AvatarProject")),
+            None
+        );
+
+        let effects = h.send(Action::ConfigLoaded(Ok(AppConfig {
+            projects: h.state.projects.clone(),
+            ..AppConfig::default()
+        })));
+        let task_id = effects
+            .iter()
+            .find_map(|e| match e {
+                Effect::RunVpmCommand { task_id, args, .. } if args == &["list", "unity"] => {
+                    Some(*task_id)
+                }
+                _ => None,
+            })
+            .expect("vpm list unity queued");
+        h.output(
+            task_id,
+            OutputStream::Stdout,
+            "[12:00:00 INF] 2022.3.22f1 | /opt/unity/2022.3.22f1/Unity",
+        );
+        h.finish(task_id, 0);
+
+        let warnings = |h: &Harness| {
+            h.state
+                .logs
+                .iter()
+                .filter(|l| l.level == LogLevel::Warn && l.text.contains("not installed"))
+                .map(|l| l.display())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            warnings(&h),
+            vec!["[projects] AvatarProject uses Unity 2019.4.31f1, which is not installed"]
+        );
+        assert_eq!(
+            h.state
+                .editor_installed(Path::new("/projects/WorldProject")),
+            Some(true)
+        );
+
+        h.send(Action::ProjectDetailsLoaded(project_details(&[(
+            "/projects/AvatarProject",
+            "2019.4.31f1",
+        )])));
+        assert_eq!(warnings(&h).len(), 1);
+    }
+
+    #[test]
+    fn open_in_unity_launches_editor_and_stamps_last_opened() {
+        let mut h = Harness::new(sample_state_on(Screen::Projects));
+        h.state.unity_settings.editor_root = Some(PathBuf::from("/opt/unity"));
+        let project = PathBuf::from("/projects/AvatarProject");
+
+        assert_eq!(
+            h.press(KeyCode::Char('o')),
+            vec![Effect::OpenInUnity {
+                project_path: project.clone(),
+                settings: h.state.unity_settings.clone(),
+                editors: Vec::new(),
+            }]
+        );
+
+        let effects = h.send(Action::UnityOpened {
+            project_path: project.clone(),
+            result: Err("Unity 2019.4.31f1 is not installed".to_string()),
+        });
+        assert!(effects.is_empty());
+        assert!(h.state.projects[0].last_opened.is_none());
+        assert_eq!(
+            h.state.logs.last().map(|l| l.display()),
+            Some(
+                "[projects] could not open AvatarProject: Unity 2019.4.31f1 is not installed"
+                    .to_string()
+            )
+        );
+
+        let effects = h.send(Action::UnityOpened {
+            project_path: project,
+            result: Ok((
+                "2022.3.22f1".to_string(),
+                PathBuf::from("/opt/unity/2022.3.22f1/Editor/Unity"),
+            )),
+        });
+        assert!(h.state.projects[0].last_opened.is_some());
+        assert_eq!(
+            h.state.status_line,
+            "Opened AvatarProject in Unity 2022.3.22f1"
+        );
+        assert_eq!(effects, vec![Effect::SaveConfig(h.state.app_config())]);
+        assert_eq!(
+            h.state.app_config().unity.editor_root,
+            Some(PathBuf::from("/opt/unity"))
+        );
+
+        h.state.screen = Screen::Manage;
+        assert!(matches!(
+            h.press(KeyCode::Char('o')).as_slice(),
+            [Effect::OpenInUnity { .. }]
+        ));
+    }
+
+    #[test]
+    fn tags_are_added_with_autocomplete_removed_and_filter_the_list() {
+        let mut h = Harness::new(sample_state_on(Screen::Projects));
+        h.state.projects.push(ProjectMeta::from_path(PathBuf::from(
+            "/projects/ClientShop",
+        )));
+        h.state.projects[2].tags = vec!["client-acme".to_string()];
+
+        h.press(KeyCode::Char('t'));
+        h.type_text("cli");
+        assert_eq!(h.state.tag_suggestions(), vec!["client-acme"]);
+        h.press(KeyCode::Tab);
+        let effects = h.press(KeyCode::Enter);
+        assert_eq!(h.state.projects[0].tags, vec!["client-acme"]);
+        assert_eq!(effects, vec![Effect::SaveConfig(h.state.app_config())]);
+
+        h.press(KeyCode::Char('t'));
+        h.type_text("personal");
+        h.press(KeyCode::Enter);
+        h.press(KeyCode::Char('t'));
+        h.type_text("personal");
+        assert!(h.press(KeyCode::Enter).is_empty());
+        assert_eq!(
+            h.state.status_line,
+            "AvatarProject is already tagged 'personal'"
+        );
+
+        h.press(KeyCode::Char(']'));
+        assert_eq!(h.state.tag_filter.as_deref(), Some("client-acme"));
+        assert_eq!(
+            h.state
+                .filtered_projects()
+                .iter()
+                .map(|p| p.display_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["AvatarProject", "ClientShop"]
+        );
+        h.press(KeyCode::Char('j'));
+        assert_eq!(h.state.selected_project, 2);
+        h.press(KeyCode::Char('j'));
+        assert_eq!(h.state.selected_project, 2);
+
+        h.press(KeyCode::Char('T'));
+        h.press(KeyCode::Tab);
+        h.press(KeyCode::Enter);
+        assert!(h.state.projects[2].tags.is_empty());
+        assert_eq!(h.state.selected_project, 0);
+        h.press(KeyCode::Char('['));
+        assert_eq!(h.state.tag_filter, None);
+
+        h.press(KeyCode::Char('j'));
+        h.press(KeyCode::Char('T'));
+        assert_eq!(h.state.tag_edit, None);
+        assert_eq!(h.state.status_line, "WorldProject has no tags");
+    }
+
+    #[test]
+    fn batch_update_targets_prereleases_only_when_shown() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        h.state.available_packages[2].latest_prerelease = Some("1.10.0-beta.1".to_string());
+        h.press(KeyCode::Char('A'));
+        assert_eq!(h.state.status_line, "Queued 1 package update(s)");
+
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        h.state.available_packages[2].latest_prerelease = Some("1.10.0-beta.1".to_string());
+        h.state.show_prereleases = true;
+        h.press(KeyCode::Char('A'));
+        assert_eq!(
+            h.state
+                .tasks
+                .iter()
+                .map(|t| t.args[2].as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "com.vrchat.avatars@3.6.0",
+                "nadena.dev.modular-avatar@1.10.0-beta.1"
+            ]
+        );
+    }
+
+    #[test]
+    fn selection_keys_do_nothing_when_the_filter_hides_every_project() {
+        let mut h = Harness::new(sample_state_on(Screen::Projects));
+        h.press(KeyCode::Char('/'));
+        h.type_text("no such project");
+        h.press(KeyCode::Enter);
+        assert!(h.state.visible_projects().is_empty());
+
+        for key in ['d', 'o', 'r', 'p', 't', 'K'] {
+            assert!(h.press(KeyCode::Char(key)).is_empty());
+        }
+        assert!(h.press(KeyCode::Enter).is_empty());
+        assert_eq!(h.state.status_line, "No project selected");
+        assert_eq!(h.state.pending_project_removal, None);
+        assert!(!h.state.rename_project_mode);
+        assert_eq!(h.state.tag_edit, None);
+        assert_eq!(h.state.screen, Screen::Projects);
+        assert!(h.state.projects.iter().all(|p| !p.pinned));
+    }
+
+    #[test]
+    fn grouped_view_navigates_projects_in_group_order() {
+        let mut h = Harness::new(sample_state_on(Screen::Projects));
+        h.state.projects[1].tags = vec!["client".to_string()];
+        h.press(KeyCode::Char('g'));
+
+        assert_eq!(
+            h.state
+                .project_groups()
+                .iter()
+                .map(|(tag, members)| (tag.clone(), members.len()))
+                .collect::<Vec<_>>(),
+            vec![(Some("client".to_string()), 1), (None, 1)]
+        );
+        h.press(KeyCode::Char('k'));
+        assert_eq!(h.state.selected_project, 1);
+        h.press(KeyCode::Char('j'));
+        assert_eq!(h.state.selected_project, 0);
+    }
+
+    #[test]
+    fn projects_are_renamed_pinned_and_reordered() {
+        let mut h = Harness::new(sample_state_on(Screen::Projects));
+        h.state.projects.push(ProjectMeta::from_path(PathBuf::from(
+            "/projects/ClientShop",
+        )));
+        let names = |h: &Harness| {
+            h.state
+                .projects
+                .iter()
+                .map(|p| p.display_name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        h.press(KeyCode::Char('r'));
+        assert_eq!(h.state.rename_project_input, "AvatarProject");
+        for _ in 0.."Project".len() {
+            h.press(KeyCode::Backspace);
+        }
+        h.type_text(" (Main)");
+        let effects = h.press(KeyCode::Enter);
+        assert_eq!(h.state.projects[0].display_name, "Avatar (Main)");
+        assert_eq!(effects, vec![Effect::SaveConfig(h.state.app_config())]);
+
+        h.press(KeyCode::Char('J'));
+        assert_eq!(
+            names(&h),
+            vec!["WorldProject", "Avatar (Main)", "ClientShop"]
+        );
+        assert_eq!(h.state.selected_project, 1);
+
+        h.press(KeyCode::Char('j'));
+        let effects = h.press(KeyCode::Char('p'));
+        assert_eq!(
+            names(&h),
+            vec!["ClientShop", "WorldProject", "Avatar (Main)"]
+        );
+        assert_eq!(h.state.selected_project, 0);
+        assert_eq!(effects, vec![Effect::SaveConfig(h.state.app_config())]);
+
+        assert!(h.press(KeyCode::Char('J')).is_empty());
+        assert_eq!(
+            names(&h),
+            vec!["ClientShop", "WorldProject", "Avatar (Main)"]
+        );
+
+        let mut restarted = Harness::new(AppState::default());
+        let mut config = h.state.app_config();
+        config.projects.reverse();
+        restarted.send(Action::ConfigLoaded(Ok(config)));
+        assert_eq!(
+            names(&restarted),
+            vec!["ClientShop", "Avatar (Main)", "WorldProject"]
+        );
+    }
+
+    #[test]
+    fn project_table_sort_and_columns_persist_in_config() {
+        let mut h = Harness::new(sample_state_on(Screen::Projects));
+        let world = PathBuf::from("/projects/WorldProject");
+        let mut manifest = sample_state().selected_project_manifest.expect("manifest");
+        h.state
+            .project_manifests
+            .insert(world.clone(), manifest.clone());
+        manifest.packages.truncate(1);
+        h.state
+            .project_manifests
+            .insert(PathBuf::from("/projects/AvatarProject"), manifest);
+        let order = |h: &Harness| {
+            h.state
+                .filtered_projects()
+                .iter()
+                .map(|p| p.display_name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        for _ in 0..4 {
+            h.press(KeyCode::Char('s'));
+        }
+        assert_eq!(h.state.project_table.sort, Some(ProjectColumn::Packages));
+        assert_eq!(order(&h), vec!["AvatarProject", "WorldProject"]);
+        let effects = h.press(KeyCode::Char('S'));
+        assert_eq!(order(&h), vec!["WorldProject", "AvatarProject"]);
+        assert_eq!(effects, vec![Effect::SaveConfig(h.state.app_config())]);
+        h.send(Action::ProjectDetailsLoaded(project_details(&[(
+            "/projects/AvatarProject",
+            "2022.3.22f1",
+        )])));
+        assert_eq!(order(&h), vec!["WorldProject", "AvatarProject"]);
+        assert!(h.send(Action::Tick).is_empty());
+        h.press(KeyCode::Char('j'));
+        assert_eq!(h.state.selected_project, 0);
+        assert!(h.press(KeyCode::Char('J')).is_empty());
+
+        h.press(KeyCode::Char('7'));
+        h.press(KeyCode::Char('3'));
+        h.press(KeyCode::Char('3'));
+        assert_eq!(h.state.project_table.hidden, vec![ProjectColumn::Path]);
+        for c in ['1', '2', '3', '4', '5', '6'] {
+            h.press(KeyCode::Char(c));
+        }
+        assert_eq!(
+            h.state.project_table.visible_columns(),
+            vec![ProjectColumn::LastOpened]
+        );
+        assert_eq!(h.state.status_line, "At least one column must stay visible");
+
+        let mut restarted = Harness::new(AppState::default());
+        restarted.send(Action::ConfigLoaded(Ok(h.state.app_config())));
+        assert_eq!(restarted.state.project_table, h.state.project_table);
+        assert!(restarted.state.project_table.descending);
+    }
+
+    fn repo(id: &str, url: &str) -> RepoEntry {
+        RepoEntry {
+            id: Some(id.to_string()),
+            name: id.to_string(),
+            url: url.to_string(),
+            local_path: None,
+            headers: BTreeMap::new(),
+            package_count: None,
+            cache_age_secs: None,
+        }
+    }
+
+    #[test]
+    fn repositories_are_added_through_vpm_and_saves_wait_for_settings_tasks() {
+        let mut h = Harness::new(sample_state_on(Screen::Repos));
+        h.state.repositories = vec![repo("com.example.a", "https://a.example/vpm.json")];
+
+        h.press(KeyCode::Char('a'));
+        h.type_text(" https://b.example/vpm.json ");
+        h.press(KeyCode::Enter);
+        assert_eq!(h.state.tasks.len(), 1);
+        assert_eq!(
+            h.state.tasks[0].args,
+            vec!["add", "repo", "https://b.example/vpm.json"]
+        );
+        assert_eq!(h.state.tasks[0].scope, Some(TaskScope::VpmSettings));
+
+        h.press(KeyCode::Char('a'));
+        h.type_text("https://a.example/vpm.json");
+        assert!(h.press(KeyCode::Enter).is_empty());
+        assert_eq!(h.state.tasks.len(), 1);
+
+        h.press(KeyCode::Char('e'));
+        h.type_text("Authorization: Bearer secret");
+        assert!(h.press(KeyCode::Enter).is_empty());
+        assert!(h.state.repo_save_queued);
+
+        h.state.screen = Screen::Settings;
+        h.press(KeyCode::Char('1'));
+        assert_eq!(h.state.tasks[1].args, vec!["add", "repo", NADENA_REPO_URL]);
+        assert_eq!(h.state.tasks[1].state, TaskState::Queued);
+
+        let effects = h.finish(1, 0);
+        assert!(effects.contains(&Effect::SaveRepositories {
+            repos: h.state.repositories.clone(),
+            removed: Vec::new(),
+        }));
+        assert_eq!(h.state.tasks[1].state, TaskState::Queued);
+
+        let effects = h.send(Action::RepositoriesSaved(Ok(())));
+        assert!(effects.contains(&Effect::LoadRepositories));
+        assert!(effects
+            .iter()
+            .any(|e| matches!(e, Effect::RunVpmCommand { task_id: 2, .. })));
+        assert!(!h.state.repo_save_in_flight);
+
+        h.state.screen = Screen::Repos;
+        assert!(h.press(KeyCode::Char('E')).is_empty());
+        assert!(h.state.repo_save_queued);
+    }
+
+    #[test]
+    fn disabling_a_repo_added_in_process_hides_its_packages() {
+        let temp = TempDir::new("repo-key");
+        let settings_path = temp.path().join("settings.json");
+        let repos_dir = temp.path().join("Repos");
+        std::fs::create_dir_all(&repos_dir).unwrap();
+        let url = "https://tools.example/vpm.json";
+        let mut h = Harness::new(sample_state_on(Screen::Repos));
+
+        h.press(KeyCode::Char('a'));
+        h.type_text(url);
+        h.press(KeyCode::Enter);
+        // What `vpm add repo` leaves behind: a cache without `repo.id`.
+        let cache = repos_dir.join("user-1.json");
+        std::fs::write(
+            &cache,
+            r#"{"repo": {"name": "Tools", "packages": {
+                "com.example.tool": {"versions": {"1.0.0": {"displayName": "Tool"}}}
+            }}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            &settings_path,
+            format!(
+                r#"{{"userRepos": [{{"url": "{url}", "localPath": "{}"}}]}}"#,
+                cache.display()
+            ),
+        )
+        .unwrap();
+        assert!(h.finish(1, 0).contains(&Effect::LoadRepositories));
+        h.send(Action::RepositoriesLoaded(
+            crate::services::repos::load_user_repos_from(&settings_path).map_err(|e| e.to_string()),
+        ));
+        let catalog = |effect: &Effect| match effect {
+            Effect::LoadAvailablePackages {
+                repo_priority,
+                disabled_repos,
+            } => crate::services::fs::load_available_packages_from_dir(
+                &repos_dir,
+                repo_priority,
+                disabled_repos,
+            )
+            .unwrap(),
+            other => panic!("expected a catalog load, got {other:?}"),
+        };
+        assert_eq!(catalog(&load_available_packages(&h.state)).len(), 1);
+
+        let effects = h.press(KeyCode::Char(' '));
+
+        assert_eq!(h.state.disabled_repos, vec![url]);
+        assert!(catalog(&effects[1]).is_empty());
+    }
+
+    #[test]
+    fn repositories_are_removed_and_reordered() {
+        let mut h = Harness::new(sample_state_on(Screen::Repos));
+        h.state.repositories = vec![
+            repo("com.example.a", "https://a.example/vpm.json"),
+            repo("com.example.b", "https://b.example/vpm.json"),
+            repo("com.example.c", "https://c.example/vpm.json"),
+        ];
+
+        let effects = h.press(KeyCode::Char('J'));
+        assert_eq!(
+            effects,
+            vec![
+                Effect::SaveRepositories {
+                    repos: h.state.repositories.clone(),
+                    removed: Vec::new(),
+                },
+                Effect::SaveConfig(h.state.app_config()),
+            ]
+        );
+        assert_eq!(h.state.selected_repository, 1);
+        assert_eq!(
+            h.state.repo_priority,
+            vec!["com.example.b", "com.example.a", "com.example.c"]
+        );
+        h.send(Action::RepositoriesSaved(Ok(())));
+
+        h.press(KeyCode::Char('d'));
+        assert!(h.press(KeyCode::Char('n')).is_empty());
+        assert_eq!(h.state.repositories.len(), 3);
+
+        h.state.disabled_repos = vec!["com.example.a".to_string()];
+        h.press(KeyCode::Char('d'));
+        let effects = h.press(KeyCode::Char('y'));
+        assert_eq!(
+            effects,
+            vec![
+                Effect::SaveRepositories {
+                    repos: h.state.repositories.clone(),
+                    removed: vec!["https://a.example/vpm.json".to_string()],
+                },
+                Effect::SaveConfig(h.state.app_config()),
+            ]
+        );
+        assert!(h.state.disabled_repos.is_empty());
+        assert_eq!(
+            h.state.repo_priority,
+            vec!["com.example.b", "com.example.c"]
+        );
+        assert_eq!(
+            h.state
+                .repositories
+                .iter()
+                .map(|r| r.key())
+                .collect::<Vec<_>>(),
+            vec!["com.example.b", "com.example.c"]
+        );
+        assert_eq!(
+            h.state.status_line,
+            "Removed repository https://a.example/vpm.json"
+        );
+    }
+
+    #[test]
+    fn dependency_tree_stops_at_a_cycle_back_to_the_root_package() {
+        let mut state = sample_state();
+        let mut avatars = version("3.6.0");
+        avatars.dependencies = [("com.vrchat.base".to_string(), "3.6.0".to_string())].into();
+        let mut base = version("3.6.0");
+        base.dependencies = [("com.vrchat.avatars".to_string(), "3.6.0".to_string())].into();
+        state.available_packages[0].versions[0] = avatars.clone();
+        state.available_packages[1].versions[0] = base;
+
+        let tree = state
+            .dependency_tree("com.vrchat.avatars", &avatars)
+            .into_iter()
+            .map(|n| (n.depth, n.id))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            tree,
+            vec![
+                (0, "com.vrchat.base".to_string()),
+                (1, "com.vrchat.avatars".to_string())
+            ]
+        );
     }
 }
@@ -1,5 +1,9 @@
+use crate::app::action::OutputStream;
+use crate::semver::VersionBump;
+use chrono::{DateTime, Local, NaiveDateTime};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Screen {
@@ -7,6 +11,8 @@ pub enum Screen {
     Add,
     Projects,
     Manage,
+    Repos,
+    Tasks,
     Settings,
 }
 
@@ -16,7 +22,9 @@ impl Screen {
             Self::New => Self::Add,
             Self::Add => Self::Projects,
             Self::Projects => Self::Manage,
-            Self::Manage => Self::Settings,
+            Self::Manage => Self::Repos,
+            Self::Repos => Self::Tasks,
+            Self::Tasks => Self::Settings,
             Self::Settings => Self::New,
         }
     }
@@ -27,24 +35,287 @@ impl Screen {
             Self::Add => Self::New,
             Self::Projects => Self::Add,
             Self::Manage => Self::Projects,
-            Self::Settings => Self::Manage,
+            Self::Repos => Self::Manage,
+            Self::Tasks => Self::Repos,
+            Self::Settings => Self::Tasks,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProjectMeta {
     pub path: PathBuf,
     pub display_name: String,
     #[serde(default)]
     pub tags: Vec<String>,
     pub last_opened: Option<String>,
+    #[serde(default)]
+    pub pinned: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+impl ProjectMeta {
+    pub fn from_path(path: PathBuf) -> Self {
+        let display_name = path
+            .file_name()
+            .map(|v| v.to_string_lossy().to_string())
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+        Self {
+            path,
+            display_name,
+            tags: vec![],
+            last_opened: None,
+            pinned: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AppConfig {
     #[serde(default)]
     pub projects: Vec<ProjectMeta>,
+    #[serde(default)]
+    pub repo_priority: Vec<String>,
+    #[serde(default)]
+    pub disabled_repos: Vec<String>,
+    #[serde(default)]
+    pub vpm: VpmSettings,
+    #[serde(default = "default_max_concurrent_tasks")]
+    pub max_concurrent_tasks: usize,
+    #[serde(default)]
+    pub scan: ScanSettings,
+    #[serde(default)]
+    pub watch_folders: Vec<PathBuf>,
+    #[serde(default)]
+    pub unity: UnitySettings,
+    #[serde(default)]
+    pub projects_table: ProjectTableSettings,
+    #[serde(default)]
+    pub backup: BackupSettings,
+}
+
+fn default_max_concurrent_tasks() -> usize {
+    2
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            projects: Vec::new(),
+            repo_priority: Vec::new(),
+            disabled_repos: Vec::new(),
+            vpm: VpmSettings::default(),
+            max_concurrent_tasks: default_max_concurrent_tasks(),
+            scan: ScanSettings::default(),
+            watch_folders: Vec::new(),
+            unity: UnitySettings::default(),
+            projects_table: ProjectTableSettings::default(),
+            backup: BackupSettings::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProjectColumn {
+    Name,
+    Unity,
+    Sdk,
+    Packages,
+    Outdated,
+    LastOpened,
+    Path,
+}
+
+impl ProjectColumn {
+    pub const ALL: [Self; 7] = [
+        Self::Name,
+        Self::Unity,
+        Self::Sdk,
+        Self::Packages,
+        Self::Outdated,
+        Self::LastOpened,
+        Self::Path,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Name => "Name",
+            Self::Unity => "Unity",
+            Self::Sdk => "SDK",
+            Self::Packages => "Pkgs",
+            Self::Outdated => "Outdated",
+            Self::LastOpened => "Last opened",
+            Self::Path => "Path",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectTableSettings {
+    #[serde(default)]
+    pub sort: Option<ProjectColumn>,
+    #[serde(default)]
+    pub descending: bool,
+    #[serde(default)]
+    pub hidden: Vec<ProjectColumn>,
+}
+
+impl ProjectTableSettings {
+    pub fn visible_columns(&self) -> Vec<ProjectColumn> {
+        ProjectColumn::ALL
+            .into_iter()
+            .filter(|c| !self.hidden.contains(c))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnitySettings {
+    #[serde(default)]
+    pub editor_root: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupSettings {
+    #[serde(default)]
+    pub folder: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanSettings {
+    #[serde(default = "default_scan_max_depth")]
+    pub max_depth: usize,
+    #[serde(default = "default_scan_ignore")]
+    pub ignore: Vec<String>,
+}
+
+fn default_scan_max_depth() -> usize {
+    4
+}
+
+fn default_scan_ignore() -> Vec<String> {
+    ["Library", "Temp", ".git", "node_modules"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+impl Default for ScanSettings {
+    fn default() -> Self {
+        Self {
+            max_depth: default_scan_max_depth(),
+            ignore: default_scan_ignore(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProjectKind {
+    Vpm,
+    LegacySdk3,
+    Unity,
+}
+
+impl ProjectKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Vpm => "vpm",
+            Self::LegacySdk3 => "legacy-sdk3",
+            Self::Unity => "unity",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscoveredProject {
+    pub path: PathBuf,
+    pub kind: ProjectKind,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProjectScan {
+    pub id: u64,
+    pub roots: Vec<PathBuf>,
+    pub pending: Vec<PathBuf>,
+    pub watch: bool,
+    pub visited: usize,
+    pub current: Option<PathBuf>,
+    pub found: Vec<DiscoveredProject>,
+    pub seen: Vec<PathBuf>,
+    pub chosen: Vec<bool>,
+    pub selected: usize,
+}
+
+impl ProjectScan {
+    pub fn new(id: u64, root: PathBuf) -> Self {
+        Self {
+            id,
+            roots: vec![root.clone()],
+            pending: vec![root],
+            watch: false,
+            visited: 0,
+            current: None,
+            found: Vec::new(),
+            seen: Vec::new(),
+            chosen: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    pub fn watch(id: u64, roots: Vec<PathBuf>) -> Self {
+        Self {
+            id,
+            roots: roots.clone(),
+            pending: roots,
+            watch: true,
+            visited: 0,
+            current: None,
+            found: Vec::new(),
+            seen: Vec::new(),
+            chosen: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    pub fn chosen_paths(&self) -> Vec<PathBuf> {
+        self.found
+            .iter()
+            .zip(&self.chosen)
+            .filter(|(_, chosen)| **chosen)
+            .map(|(project, _)| project.path.clone())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VpmSettings {
+    #[serde(default)]
+    pub executable: Option<PathBuf>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub working_dir: Option<PathBuf>,
+}
+
+impl VpmSettings {
+    pub fn program(&self) -> PathBuf {
+        self.executable
+            .as_deref()
+            .map(crate::services::fs::expand_home)
+            .unwrap_or_else(|| PathBuf::from("vpm"))
+    }
+
+    pub fn current_dir(&self) -> Option<PathBuf> {
+        self.working_dir
+            .as_deref()
+            .map(crate::services::fs::expand_home)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -53,52 +324,554 @@ pub struct PackageInfo {
     pub version: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    pub dependencies: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct InstalledPackage {
+    pub name: String,
+    pub declared_version: Option<String>,
+    pub locked_version: Option<String>,
+    pub required_by: Vec<String>,
+}
+
+impl InstalledPackage {
+    pub fn is_direct(&self) -> bool {
+        self.declared_version.is_some()
+    }
+
+    pub fn version(&self) -> &str {
+        self.locked_version
+            .as_deref()
+            .or(self.declared_version.as_deref())
+            .unwrap_or("?")
+    }
+
+    pub fn has_version_mismatch(&self) -> bool {
+        match (&self.declared_version, &self.locked_version) {
+            (Some(declared), Some(locked)) => {
+                !crate::semver::satisfies_dependency(locked, declared)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PackageVersion {
+    pub version: String,
+    /// Repository this version was read from.
+    pub repo_id: String,
+    pub unity: Option<String>,
+    pub vrchat_version: Option<String>,
+    pub dependencies: BTreeMap<String, String>,
+    pub description: Option<String>,
+    pub author: Option<String>,
+    pub changelog_url: Option<String>,
+    pub documentation_url: Option<String>,
+    pub legacy_folders: Vec<String>,
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DependencyNode {
+    pub depth: usize,
+    pub id: String,
+    pub range: String,
+    pub resolved_version: Option<String>,
+    pub installed_version: Option<String>,
+    pub in_catalog: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoEntry {
+    pub id: Option<String>,
+    pub name: String,
+    pub url: String,
+    pub local_path: Option<PathBuf>,
+    pub headers: BTreeMap<String, String>,
+    pub package_count: Option<usize>,
+    pub cache_age_secs: Option<u64>,
+}
+
+impl RepoEntry {
+    /// The name `disabled_repos` and `repo_priority` use for this repo; the
+    /// catalog loader keys the repo's cache the same way.
+    pub fn key(&self) -> &str {
+        self.id.as_deref().unwrap_or(&self.url)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PackageSource {
+    pub repo_id: String,
+    pub latest_version: String,
+    pub versions: Vec<PackageVersion>,
+}
+
 #[derive(Debug, Clone)]
 pub struct AvailablePackage {
     pub id: String,
     pub display_name: String,
     pub latest_version: String,
+    pub latest_prerelease: Option<String>,
+    pub versions: Vec<PackageVersion>,
     pub repo_id: String,
+    pub sources: Vec<PackageSource>,
+}
+
+impl AvailablePackage {
+    pub fn has_stable_release(&self) -> bool {
+        self.versions
+            .iter()
+            .any(|v| !crate::semver::is_prerelease(&v.version))
+    }
+
+    pub fn latest(&self, include_prereleases: bool) -> &str {
+        match &self.latest_prerelease {
+            Some(pre) if include_prereleases => pre,
+            _ => &self.latest_version,
+        }
+    }
+
+    pub fn version_info(&self, version: &str) -> Option<&PackageVersion> {
+        self.versions.iter().find(|v| v.version == version)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VersionPicker {
+    pub package_id: String,
+    pub installed_version: Option<String>,
+    pub versions: Vec<PackageVersion>,
+    pub selected: usize,
+}
+
+impl VersionPicker {
+    pub fn selected_version(&self) -> Option<&PackageVersion> {
+        self.versions.get(self.selected)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PackageUpdate {
+    pub name: String,
+    pub current_version: String,
+    pub latest_version: String,
+    pub bump: VersionBump,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationStage {
+    Confirm,
+    BackingUp,
+    Migrating(u64),
+    Verifying,
+}
+
+#[derive(Debug, Clone)]
+pub struct Migration {
+    pub project_path: PathBuf,
+    pub backup_path: Option<PathBuf>,
+    pub stage: MigrationStage,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupEntry {
+    pub path: PathBuf,
+    pub created: NaiveDateTime,
+    pub size: u64,
+}
+
+impl BackupEntry {
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+
+    pub fn size_label(&self) -> String {
+        const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+        if self.size < 1024 {
+            return format!("{} B", self.size);
+        }
+        let mut size = self.size as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit + 1 < UNITS.len() {
+            size /= 1024.0;
+            unit += 1;
+        }
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BackupBrowser {
+    pub project_path: PathBuf,
+    pub entries: Vec<BackupEntry>,
+    pub selected: usize,
+    pub loading: bool,
+    pub busy: bool,
+    pub pending_restore: Option<PathBuf>,
+}
+
+impl BackupBrowser {
+    pub fn selected_entry(&self) -> Option<&BackupEntry> {
+        self.entries.get(self.selected)
+    }
+}
+
+enum ProjectSortKey<'a> {
+    Text(String),
+    Unity(&'a str),
+    Count(Option<usize>),
+    Opened(Option<&'a str>),
+    Path(&'a Path),
+}
+
+impl ProjectSortKey<'_> {
+    fn compare(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (Self::Text(a), Self::Text(b)) => a.cmp(b),
+            (Self::Unity(a), Self::Unity(b)) => crate::semver::compare_unity_versions(a, b),
+            (Self::Count(a), Self::Count(b)) => a.cmp(b),
+            (Self::Opened(a), Self::Opened(b)) => a.cmp(b),
+            (Self::Path(a), Self::Path(b)) => a.cmp(b),
+            _ => std::cmp::Ordering::Equal,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProjectDetails {
+    pub path: PathBuf,
+    pub unity_version: Option<String>,
+    pub manifest: Option<ManifestSummary>,
 }
 
 #[derive(Debug, Clone)]
 pub struct ManifestSummary {
     pub exists: bool,
+    pub kind: Option<ProjectKind>,
     pub packages: Vec<PackageInfo>,
+    pub locked: Vec<LockedPackage>,
     pub message: Option<String>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl ManifestSummary {
+    pub fn sdk_label(&self) -> &'static str {
+        let has = |id: &str| self.packages.iter().any(|p| p.name == id);
+        match (has("com.vrchat.avatars"), has("com.vrchat.worlds")) {
+            (true, true) => "both",
+            (true, false) => "avatar",
+            (false, true) => "world",
+            _ if self.kind == Some(ProjectKind::LegacySdk3) => "legacy",
+            _ => "-",
+        }
+    }
+
+    pub fn installed_packages(&self) -> Vec<InstalledPackage> {
+        let required_by = |name: &str| {
+            self.locked
+                .iter()
+                .filter(|l| l.dependencies.contains_key(name))
+                .map(|l| l.name.clone())
+                .collect::<Vec<_>>()
+        };
+        let locked_version = |name: &str| {
+            self.locked
+                .iter()
+                .find(|l| l.name == name)
+                .map(|l| l.version.clone())
+        };
+
+        let mut installed = self
+            .packages
+            .iter()
+            .map(|p| InstalledPackage {
+                name: p.name.clone(),
+                declared_version: Some(p.version.clone()),
+                locked_version: locked_version(&p.name),
+                required_by: required_by(&p.name),
+            })
+            .collect::<Vec<_>>();
+        installed.extend(
+            self.locked
+                .iter()
+                .filter(|l| !self.packages.iter().any(|p| p.name == l.name))
+                .map(|l| InstalledPackage {
+                    name: l.name.clone(),
+                    declared_version: None,
+                    locked_version: Some(l.version.clone()),
+                    required_by: required_by(&l.name),
+                }),
+        );
+        installed
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum TaskState {
+    Queued,
     Running,
     Success,
     Failed,
     Cancelled,
 }
 
+/// What a running task holds for itself; queued tasks with the same scope wait.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskScope {
+    /// A project folder, normalized so `~` and symlinked spellings match.
+    Project(PathBuf),
+    /// vpm's repository list and template store, shared by every project.
+    VpmSettings,
+}
+
 #[derive(Debug, Clone)]
 pub struct TaskRecord {
     pub id: u64,
     pub label: String,
+    pub args: Vec<String>,
+    pub project_path: Option<PathBuf>,
+    pub scope: Option<TaskScope>,
     pub state: TaskState,
     pub exit_code: Option<i32>,
     pub error: Option<String>,
     pub refresh_manifest_path: Option<PathBuf>,
     pub pending_add_project: Option<ProjectMeta>,
+    pub queued_at: DateTime<Local>,
+    pub started_at: Option<DateTime<Local>>,
+    pub finished_at: Option<DateTime<Local>>,
+}
+
+impl TaskRecord {
+    pub fn is_active(&self) -> bool {
+        matches!(self.state, TaskState::Queued | TaskState::Running)
+    }
+
+    pub fn duration_secs(&self, now: DateTime<Local>) -> Option<i64> {
+        let started = self.started_at?;
+        let end = self.finished_at.unwrap_or(now);
+        Some((end - started).num_seconds().max(0))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskHistoryEntry {
+    pub session: String,
+    pub task_id: u64,
+    pub label: String,
+    pub command_line: String,
+    pub args: Vec<String>,
+    pub project_path: Option<PathBuf>,
+    pub state: TaskState,
+    pub exit_code: Option<i32>,
+    pub error: Option<String>,
+    pub queued_at: DateTime<Local>,
+    pub started_at: Option<DateTime<Local>>,
+    pub finished_at: Option<DateTime<Local>>,
+}
+
+pub const LOG_PANE_LINES: usize = 8;
+/// Rows of the top-level layout in `ui::render`, which sizes itself from these.
+pub const HEADER_ROWS: u16 = 1;
+pub const MIN_BODY_ROWS: u16 = 8;
+pub const LOG_PANE_BORDER_ROWS: u16 = 2;
+
+/// Rows inside the log pane's border for a terminal of the given height; the
+/// pane gives up rows before the screen body drops below `MIN_BODY_ROWS`.
+pub fn log_pane_rows(terminal_height: u16) -> usize {
+    terminal_height
+        .saturating_sub(HEADER_ROWS + MIN_BODY_ROWS + LOG_PANE_BORDER_ROWS)
+        .clamp(1, LOG_PANE_LINES as u16) as usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Info,
+    Success,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Success => "ok",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+
+    /// Level of a line written by [`LogEntry::export_line`], read from its
+    /// level column.
+    pub fn from_export_line(line: &str) -> Option<Self> {
+        match line.split_whitespace().nth(1)? {
+            "info" => Some(Self::Info),
+            "ok" => Some(Self::Success),
+            "warn" => Some(Self::Warn),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+
+    /// vpm writes progress to stderr too, so only lines tagged as errors
+    /// (`error: …`, `[12:00:00 ERR] …`) count as errors; untagged ones warn.
+    pub fn from_stderr(line: &str) -> Self {
+        let lower = line.trim_start().to_ascii_lowercase();
+        let tag = match lower.strip_prefix('[') {
+            Some(rest) => rest
+                .split(']')
+                .next()
+                .and_then(|tag| tag.split_whitespace().last()),
+            None => lower.split([':', ' ']).next(),
+        };
+        match tag.unwrap_or_default() {
+            "error" | "err" | "fatal" | "ftl" => Self::Error,
+            "info" | "inf" | "debug" | "dbg" | "verbose" | "vrb" => Self::Info,
+            _ => Self::Warn,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogSource {
+    Task(u64),
+    Config,
+    Projects,
+    Packages,
+    Manifest,
+    Repos,
+    History,
+    Logs,
 }
 
 #[derive(Debug, Clone)]
 pub struct LogEntry {
-    pub task_id: Option<u64>,
+    pub source: LogSource,
+    pub stream: Option<OutputStream>,
+    pub level: LogLevel,
+    pub timestamp: DateTime<Local>,
     pub text: String,
 }
 
+impl LogEntry {
+    pub fn task_id(&self) -> Option<u64> {
+        match self.source {
+            LogSource::Task(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    pub fn prefix(&self) -> String {
+        match (self.source, self.stream) {
+            (LogSource::Task(id), Some(OutputStream::Stdout)) => format!("[{id}:out]"),
+            (LogSource::Task(id), Some(OutputStream::Stderr)) => format!("[{id}:err]"),
+            (LogSource::Task(id), None) => format!("[task:{id}]"),
+            (LogSource::Config, _) => "[config]".to_string(),
+            (LogSource::Projects, _) => "[projects]".to_string(),
+            (LogSource::Packages, _) => "[packages]".to_string(),
+            (LogSource::Manifest, _) => "[manifest]".to_string(),
+            (LogSource::Repos, _) => "[repos]".to_string(),
+            (LogSource::History, _) => "[history]".to_string(),
+            (LogSource::Logs, _) => "[logs]".to_string(),
+        }
+    }
+
+    pub fn display(&self) -> String {
+        format!("{} {}", self.prefix(), self.text)
+    }
+
+    pub fn export_line(&self) -> String {
+        format!(
+            "{} {:<5} {}",
+            self.timestamp.format("%Y-%m-%dT%H:%M:%S%.3f%:z"),
+            self.level.label(),
+            self.display()
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StreamFilter {
+    #[default]
+    All,
+    Stdout,
+    Stderr,
+    Messages,
+}
+
+impl StreamFilter {
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::Stdout,
+            Self::Stdout => Self::Stderr,
+            Self::Stderr => Self::Messages,
+            Self::Messages => Self::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Stdout => "stdout",
+            Self::Stderr => "stderr",
+            Self::Messages => "messages",
+        }
+    }
+
+    fn matches(self, stream: Option<OutputStream>) -> bool {
+        match self {
+            Self::All => true,
+            Self::Stdout => stream == Some(OutputStream::Stdout),
+            Self::Stderr => stream == Some(OutputStream::Stderr),
+            Self::Messages => stream.is_none(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LogFilter {
+    pub task_id: Option<u64>,
+    pub stream: StreamFilter,
+    pub min_level: Option<LogLevel>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if self.task_id.is_some() && entry.task_id() != self.task_id {
+            return false;
+        }
+        self.stream.matches(entry.stream) && self.min_level.is_none_or(|min| entry.level >= min)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnityEditor {
+    pub version: String,
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagEdit {
+    Add,
+    Remove,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SystemChecks {
     pub vpm_version: Option<String>,
     pub hub_check: Option<String>,
     pub unity_check: Option<String>,
-    pub unity_list: Vec<String>,
+    pub installed_editors: Option<Vec<UnityEditor>>,
 }
 
 #[derive(Debug, Clone)]
@@ -107,9 +880,61 @@ pub struct AppState {
     pub should_quit: bool,
     pub tick_count: u64,
     pub projects: Vec<ProjectMeta>,
+    pub repo_priority: Vec<String>,
+    pub disabled_repos: Vec<String>,
+    pub vpm_settings: VpmSettings,
+    pub unity_settings: UnitySettings,
+    pub backup_settings: BackupSettings,
+    pub default_backup_dir: Option<PathBuf>,
+    pub project_table: ProjectTableSettings,
+    pub max_concurrent_tasks: usize,
+    pub scan_settings: ScanSettings,
+    pub project_scan: Option<ProjectScan>,
+    pub next_scan_id: u64,
+    pub scan_ignore_mode: bool,
+    pub scan_ignore_input: String,
+    pub watch_folders: Vec<PathBuf>,
+    pub selected_watch_folder: usize,
+    pub watch_folder_mode: bool,
+    pub queued_watch_roots: Vec<PathBuf>,
+    pub offline_projects: BTreeSet<PathBuf>,
+    pub project_paths_known: bool,
+    pub project_check_pending: bool,
+    pub project_versions: BTreeMap<PathBuf, String>,
+    pub project_manifests: BTreeMap<PathBuf, ManifestSummary>,
+    pub repositories: Vec<RepoEntry>,
+    pub selected_repository: usize,
+    pub selected_task: usize,
+    /// Rows the task output pane is scrolled up from its newest line.
+    pub task_output_scroll: usize,
+    /// Time of the action being reduced. The event loop sets it before each
+    /// action so the reducer never reads the clock itself; task times and log
+    /// timestamps all come from here.
+    pub now: DateTime<Local>,
+    pub session_id: String,
+    pub task_history: Vec<TaskHistoryEntry>,
+    pub show_task_history: bool,
+    pub selected_history_session: usize,
+    pub selected_history_task: usize,
+    pub history_log: Vec<String>,
+    pub repo_header_mode: bool,
+    pub repo_header_input: String,
+    pub pending_repo_removal: Option<String>,
+    pub repo_save_in_flight: bool,
+    pub repo_save_queued: bool,
+    pub removed_repo_urls: Vec<String>,
+    pub pending_project_removal: Option<PathBuf>,
+    pub migration: Option<Migration>,
+    pub backup_browser: Option<BackupBrowser>,
     pub selected_project: usize,
     pub search_mode: bool,
     pub search_query: String,
+    pub tag_filter: Option<String>,
+    pub group_by_tag: bool,
+    pub tag_edit: Option<TagEdit>,
+    pub tag_input: String,
+    pub rename_project_mode: bool,
+    pub rename_project_input: String,
     pub add_project_mode: bool,
     pub add_project_input: String,
     pub add_folder_mode: bool,
@@ -130,8 +955,18 @@ pub struct AppState {
     pub available_package_search_mode: bool,
     pub selected_available_package: usize,
     pub manage_focus_available: bool,
+    pub show_prereleases: bool,
+    pub version_picker: Option<VersionPicker>,
+    pub show_package_detail: bool,
     pub logs: Vec<LogEntry>,
-    pub log_scroll: u16,
+    pub log_filter: LogFilter,
+    pub log_scroll: usize,
+    pub log_pane_rows: usize,
+    pub log_follow: bool,
+    pub log_focus: bool,
+    pub log_search_mode: bool,
+    pub log_search_query: String,
+    pub log_match: usize,
     pub tasks: Vec<TaskRecord>,
     pub next_task_id: u64,
     pub status_line: String,
@@ -145,9 +980,57 @@ impl Default for AppState {
             should_quit: false,
             tick_count: 0,
             projects: Vec::new(),
+            repo_priority: Vec::new(),
+            disabled_repos: Vec::new(),
+            vpm_settings: VpmSettings::default(),
+            unity_settings: UnitySettings::default(),
+            backup_settings: BackupSettings::default(),
+            default_backup_dir: None,
+            project_table: ProjectTableSettings::default(),
+            max_concurrent_tasks: default_max_concurrent_tasks(),
+            scan_settings: ScanSettings::default(),
+            project_scan: None,
+            next_scan_id: 1,
+            scan_ignore_mode: false,
+            scan_ignore_input: String::new(),
+            watch_folders: Vec::new(),
+            selected_watch_folder: 0,
+            watch_folder_mode: false,
+            queued_watch_roots: Vec::new(),
+            offline_projects: BTreeSet::new(),
+            project_paths_known: false,
+            project_check_pending: false,
+            project_versions: BTreeMap::new(),
+            project_manifests: BTreeMap::new(),
+            repositories: Vec::new(),
+            selected_repository: 0,
+            selected_task: 0,
+            task_output_scroll: 0,
+            now: Local::now(),
+            session_id: String::new(),
+            task_history: Vec::new(),
+            show_task_history: false,
+            selected_history_session: 0,
+            selected_history_task: 0,
+            history_log: Vec::new(),
+            repo_header_mode: false,
+            repo_header_input: String::new(),
+            pending_repo_removal: None,
+            repo_save_in_flight: false,
+            repo_save_queued: false,
+            removed_repo_urls: Vec::new(),
+            pending_project_removal: None,
+            migration: None,
+            backup_browser: None,
             selected_project: 0,
             search_mode: false,
             search_query: String::new(),
+            tag_filter: None,
+            group_by_tag: false,
+            tag_edit: None,
+            tag_input: String::new(),
+            rename_project_mode: false,
+            rename_project_input: String::new(),
             add_project_mode: false,
             add_project_input: String::new(),
             add_folder_mode: false,
@@ -168,8 +1051,18 @@ impl Default for AppState {
             available_package_search_mode: false,
             selected_available_package: 0,
             manage_focus_available: true,
+            show_prereleases: false,
+            version_picker: None,
+            show_package_detail: false,
             logs: Vec::new(),
+            log_filter: LogFilter::default(),
             log_scroll: 0,
+            log_pane_rows: LOG_PANE_LINES,
+            log_follow: true,
+            log_focus: false,
+            log_search_mode: false,
+            log_search_query: String::new(),
+            log_match: 0,
             tasks: Vec::new(),
             next_task_id: 1,
             status_line: "Ready".to_string(),
@@ -179,38 +1072,367 @@ impl Default for AppState {
 }
 
 impl AppState {
-    pub fn filtered_projects(&self) -> Vec<&ProjectMeta> {
-        if self.search_query.is_empty() {
-            return self.projects.iter().collect();
+    pub fn app_config(&self) -> AppConfig {
+        AppConfig {
+            projects: self.projects.clone(),
+            repo_priority: self.repo_priority.clone(),
+            disabled_repos: self.disabled_repos.clone(),
+            vpm: self.vpm_settings.clone(),
+            max_concurrent_tasks: self.max_concurrent_tasks,
+            scan: self.scan_settings.clone(),
+            watch_folders: self.watch_folders.clone(),
+            unity: self.unity_settings.clone(),
+            projects_table: self.project_table.clone(),
+            backup: self.backup_settings.clone(),
         }
+    }
 
-        let needle = self.search_query.to_lowercase();
+    pub fn tasks_newest_first(&self) -> Vec<&TaskRecord> {
+        self.tasks.iter().rev().collect()
+    }
+
+    pub fn selected_task(&self) -> Option<&TaskRecord> {
+        self.tasks_newest_first().get(self.selected_task).copied()
+    }
+
+    pub fn history_sessions(&self) -> Vec<&str> {
+        let mut sessions = Vec::new();
+        for entry in self.task_history.iter().rev() {
+            if !sessions.contains(&entry.session.as_str()) {
+                sessions.push(entry.session.as_str());
+            }
+        }
+        sessions
+    }
+
+    pub fn history_session_entries(&self) -> Vec<&TaskHistoryEntry> {
+        let Some(session) = self
+            .history_sessions()
+            .get(self.selected_history_session)
+            .copied()
+        else {
+            return Vec::new();
+        };
+        self.task_history
+            .iter()
+            .rev()
+            .filter(|e| e.session == session)
+            .collect()
+    }
+
+    pub fn selected_history_entry(&self) -> Option<&TaskHistoryEntry> {
+        self.history_session_entries()
+            .get(self.selected_history_task)
+            .copied()
+    }
+
+    pub fn task_logs(&self, task_id: u64) -> Vec<&LogEntry> {
+        let filter = LogFilter {
+            task_id: Some(task_id),
+            ..LogFilter::default()
+        };
+        self.filtered_logs(filter)
+    }
+
+    pub fn filtered_logs(&self, filter: LogFilter) -> Vec<&LogEntry> {
+        self.logs.iter().filter(|l| filter.matches(l)).collect()
+    }
+
+    pub fn visible_logs(&self) -> Vec<&LogEntry> {
+        self.filtered_logs(self.log_filter)
+    }
+
+    pub fn log_max_scroll(&self) -> usize {
+        self.visible_logs().len().saturating_sub(self.log_pane_rows)
+    }
+
+    pub fn log_scroll_offset(&self) -> usize {
+        if self.log_follow {
+            self.log_max_scroll()
+        } else {
+            self.log_scroll.min(self.log_max_scroll())
+        }
+    }
+
+    pub fn log_matches(&self) -> Vec<usize> {
+        let query = self.log_search_query.to_ascii_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        self.visible_logs()
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| l.display().to_ascii_lowercase().contains(&query))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    pub fn log_task_ids(&self) -> Vec<u64> {
+        let mut ids = self
+            .logs
+            .iter()
+            .filter_map(|l| l.task_id())
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    pub fn project_name_for(&self, path: &Path) -> String {
         self.projects
             .iter()
+            .find(|p| p.path == path)
+            .map(|p| p.display_name.clone())
+            .or_else(|| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+            })
+            .unwrap_or_else(|| path.display().to_string())
+    }
+
+    pub fn selected_repository(&self) -> Option<&RepoEntry> {
+        self.repositories.get(self.selected_repository)
+    }
+
+    pub fn is_repo_disabled(&self, repo: &RepoEntry) -> bool {
+        self.disabled_repos.iter().any(|id| id == repo.key())
+    }
+
+    pub fn filtered_projects(&self) -> Vec<&ProjectMeta> {
+        let needle = self.search_query.to_lowercase();
+        let mut projects = self
+            .projects
+            .iter()
+            .filter(|p| {
+                self.tag_filter
+                    .as_ref()
+                    .is_none_or(|tag| p.tags.contains(tag))
+            })
             .filter(|p| {
-                p.display_name.to_lowercase().contains(&needle)
+                needle.is_empty()
+                    || p.display_name.to_lowercase().contains(&needle)
                     || p.path.to_string_lossy().to_lowercase().contains(&needle)
                     || p.tags.iter().any(|t| t.to_lowercase().contains(&needle))
             })
+            .collect::<Vec<_>>();
+        if let Some(column) = self.project_table.sort {
+            let mut keyed = projects
+                .into_iter()
+                .map(|p| (self.project_sort_key(p, column), p))
+                .collect::<Vec<_>>();
+            keyed.sort_by(|(key_a, a), (key_b, b)| {
+                let order = key_a.compare(key_b);
+                b.pinned
+                    .cmp(&a.pinned)
+                    .then(if self.project_table.descending {
+                        order.reverse()
+                    } else {
+                        order
+                    })
+            });
+            projects = keyed.into_iter().map(|(_, p)| p).collect();
+        }
+        projects
+    }
+
+    pub fn project_cell(&self, project: &ProjectMeta, column: ProjectColumn) -> String {
+        let manifest = self.project_manifests.get(&project.path);
+        match column {
+            ProjectColumn::Name => project.display_name.clone(),
+            ProjectColumn::Unity => match (
+                self.project_versions.get(&project.path),
+                self.editor_installed(&project.path),
+            ) {
+                (Some(version), Some(false)) => format!("{version}!"),
+                (Some(version), _) => version.clone(),
+                (None, _) => "-".to_string(),
+            },
+            ProjectColumn::Sdk => manifest.map(|m| m.sdk_label()).unwrap_or("-").to_string(),
+            ProjectColumn::Packages => manifest
+                .filter(|m| m.exists)
+                .map(|m| m.packages.len().to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            ProjectColumn::Outdated => self
+                .project_outdated_count(&project.path)
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            ProjectColumn::LastOpened => project
+                .last_opened
+                .as_deref()
+                .map(|raw| {
+                    DateTime::parse_from_rfc3339(raw)
+                        .map(|t| t.with_timezone(&Local).format("%Y-%m-%d").to_string())
+                        .unwrap_or_else(|_| raw.to_string())
+                })
+                .unwrap_or_else(|| "-".to_string()),
+            ProjectColumn::Path => project.path.display().to_string(),
+        }
+    }
+
+    pub fn project_outdated_count(&self, path: &Path) -> Option<usize> {
+        self.project_manifests
+            .get(path)
+            .filter(|m| m.exists)
+            .map(|m| self.outdated_packages_in(m).len())
+    }
+
+    fn project_sort_key<'a>(
+        &'a self,
+        project: &'a ProjectMeta,
+        column: ProjectColumn,
+    ) -> ProjectSortKey<'a> {
+        match column {
+            ProjectColumn::Name => ProjectSortKey::Text(project.display_name.to_lowercase()),
+            ProjectColumn::Unity => ProjectSortKey::Unity(
+                self.project_versions
+                    .get(&project.path)
+                    .map_or("", String::as_str),
+            ),
+            ProjectColumn::Sdk => ProjectSortKey::Text(self.project_cell(project, column)),
+            ProjectColumn::Packages => ProjectSortKey::Count(
+                self.project_manifests
+                    .get(&project.path)
+                    .filter(|m| m.exists)
+                    .map(|m| m.packages.len()),
+            ),
+            ProjectColumn::Outdated => {
+                ProjectSortKey::Count(self.project_outdated_count(&project.path))
+            }
+            ProjectColumn::LastOpened => ProjectSortKey::Opened(project.last_opened.as_deref()),
+            ProjectColumn::Path => ProjectSortKey::Path(&project.path),
+        }
+    }
+
+    pub fn all_tags(&self) -> Vec<String> {
+        self.projects
+            .iter()
+            .flat_map(|p| p.tags.iter().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
             .collect()
     }
 
-    pub fn push_log<T: Into<String>>(&mut self, task_id: Option<u64>, line: T) {
-        const MAX_LOG_LINES: usize = 1500;
-        self.logs.push(LogEntry {
-            task_id,
-            text: line.into(),
+    pub fn project_groups(&self) -> Vec<(Option<String>, Vec<&ProjectMeta>)> {
+        let filtered = self.filtered_projects();
+        let mut groups = self
+            .all_tags()
+            .into_iter()
+            .map(|tag| {
+                let members = filtered
+                    .iter()
+                    .copied()
+                    .filter(|p| p.tags.contains(&tag))
+                    .collect::<Vec<_>>();
+                (Some(tag), members)
+            })
+            .filter(|(_, members)| !members.is_empty())
+            .collect::<Vec<_>>();
+        let untagged = filtered
+            .iter()
+            .copied()
+            .filter(|p| p.tags.is_empty())
+            .collect::<Vec<_>>();
+        if !untagged.is_empty() {
+            groups.push((None, untagged));
+        }
+        groups
+    }
+
+    pub fn visible_projects(&self) -> Vec<&ProjectMeta> {
+        if !self.group_by_tag {
+            return self.filtered_projects();
+        }
+        let mut seen = BTreeSet::new();
+        self.project_groups()
+            .into_iter()
+            .flat_map(|(_, members)| members)
+            .filter(|p| seen.insert(&p.path))
+            .collect()
+    }
+
+    pub fn tag_suggestions(&self) -> Vec<String> {
+        let Some(edit) = self.tag_edit else {
+            return Vec::new();
+        };
+        let current = self
+            .selected_project()
+            .map(|p| p.tags.clone())
+            .unwrap_or_default();
+        let candidates = match edit {
+            TagEdit::Add => self
+                .all_tags()
+                .into_iter()
+                .filter(|t| !current.contains(t))
+                .collect::<Vec<_>>(),
+            TagEdit::Remove => current,
+        };
+        let needle = self.tag_input.trim().to_lowercase();
+        candidates
+            .into_iter()
+            .filter(|t| t.to_lowercase().starts_with(&needle))
+            .collect()
+    }
+
+    pub fn push_log<T: Into<String>>(&mut self, source: LogSource, level: LogLevel, text: T) {
+        self.push_log_entry(LogEntry {
+            source,
+            stream: None,
+            level,
+            timestamp: self.now,
+            text: text.into(),
+        });
+    }
+
+    pub fn push_task_output(&mut self, task_id: u64, stream: OutputStream, line: String) {
+        self.push_log_entry(LogEntry {
+            source: LogSource::Task(task_id),
+            stream: Some(stream),
+            level: match stream {
+                OutputStream::Stdout => LogLevel::Info,
+                OutputStream::Stderr => LogLevel::from_stderr(&line),
+            },
+            timestamp: self.now,
+            text: line,
         });
+    }
+
+    fn push_log_entry(&mut self, entry: LogEntry) {
+        const MAX_LOG_LINES: usize = 1500;
+        self.logs.push(entry);
         if self.logs.len() > MAX_LOG_LINES {
             let overflow = self.logs.len() - MAX_LOG_LINES;
             self.logs.drain(0..overflow);
         }
     }
 
+    pub fn editor_installed(&self, project_path: &Path) -> Option<bool> {
+        let version = self.project_versions.get(project_path)?;
+        let editors = self.system_checks.installed_editors.as_ref()?;
+        Some(editors.iter().any(|e| &e.version == version))
+    }
+
+    pub fn projects_missing_editor(&self) -> BTreeSet<PathBuf> {
+        self.projects
+            .iter()
+            .filter(|p| self.editor_installed(&p.path) == Some(false))
+            .map(|p| p.path.clone())
+            .collect()
+    }
+
     pub fn selected_project(&self) -> Option<&ProjectMeta> {
         self.projects.get(self.selected_project)
     }
 
+    pub fn pin_projects_to_top(&mut self) {
+        let selected = self.selected_project().map(|p| p.path.clone());
+        self.projects.sort_by_key(|p| !p.pinned);
+        if let Some(idx) =
+            selected.and_then(|path| self.projects.iter().position(|p| p.path == path))
+        {
+            self.selected_project = idx;
+        }
+    }
+
     pub fn selected_project_clamped(&mut self) {
         if self.projects.is_empty() {
             self.selected_project = 0;
@@ -220,11 +1442,7 @@ impl AppState {
     }
 
     pub fn selected_manifest_package_clamped(&mut self) {
-        let len = self
-            .selected_project_manifest
-            .as_ref()
-            .map(|m| m.packages.len())
-            .unwrap_or(0);
+        let len = self.installed_packages().len();
         if len == 0 {
             self.selected_manifest_package = 0;
         } else if self.selected_manifest_package >= len {
@@ -232,19 +1450,127 @@ impl AppState {
         }
     }
 
-    pub fn selected_manifest_package(&self) -> Option<&PackageInfo> {
+    pub fn installed_packages(&self) -> Vec<InstalledPackage> {
+        self.selected_project_manifest
+            .as_ref()
+            .map(|m| m.installed_packages())
+            .unwrap_or_default()
+    }
+
+    pub fn selected_manifest_package(&self) -> Option<InstalledPackage> {
+        self.installed_packages()
+            .into_iter()
+            .nth(self.selected_manifest_package)
+    }
+
+    pub fn package_update(&self, package: &InstalledPackage) -> Option<PackageUpdate> {
+        let available = self
+            .available_packages
+            .iter()
+            .find(|p| p.id == package.name)?;
+        let latest = available.latest(self.show_prereleases);
+        let bump = crate::semver::bump_between(package.version(), latest)?;
+        Some(PackageUpdate {
+            name: package.name.clone(),
+            current_version: package.version().to_string(),
+            latest_version: latest.to_string(),
+            bump,
+        })
+    }
+
+    pub fn outdated_packages(&self) -> Vec<PackageUpdate> {
         self.selected_project_manifest
             .as_ref()
-            .and_then(|m| m.packages.get(self.selected_manifest_package))
+            .map(|m| self.outdated_packages_in(m))
+            .unwrap_or_default()
+    }
+
+    pub fn outdated_packages_in(&self, manifest: &ManifestSummary) -> Vec<PackageUpdate> {
+        manifest
+            .installed_packages()
+            .iter()
+            .filter(|p| p.is_direct())
+            .filter_map(|p| self.package_update(p))
+            .collect()
+    }
+
+    pub fn focused_catalog_package(&self) -> Option<&AvailablePackage> {
+        if self.manage_focus_available {
+            self.selected_available_package()
+        } else {
+            let name = self.selected_manifest_package()?.name;
+            self.available_packages.iter().find(|p| p.id == name)
+        }
+    }
+
+    pub fn dependency_tree(
+        &self,
+        package_id: &str,
+        version: &PackageVersion,
+    ) -> Vec<DependencyNode> {
+        const MAX_DEPTH: usize = 8;
+        let installed = self.installed_packages();
+        let mut nodes = Vec::new();
+        let mut stack = vec![package_id.to_string()];
+        self.collect_dependencies(version, 0, MAX_DEPTH, &installed, &mut stack, &mut nodes);
+        nodes
+    }
+
+    fn collect_dependencies(
+        &self,
+        version: &PackageVersion,
+        depth: usize,
+        max_depth: usize,
+        installed: &[InstalledPackage],
+        stack: &mut Vec<String>,
+        nodes: &mut Vec<DependencyNode>,
+    ) {
+        for (id, range) in &version.dependencies {
+            let package = self.available_packages.iter().find(|p| &p.id == id);
+            let resolved = package.and_then(|p| {
+                p.versions.iter().find(|v| {
+                    (self.show_prereleases || !crate::semver::is_prerelease(&v.version))
+                        && crate::semver::satisfies_dependency(&v.version, range)
+                })
+            });
+            nodes.push(DependencyNode {
+                depth,
+                id: id.clone(),
+                range: range.clone(),
+                resolved_version: resolved.map(|v| v.version.clone()),
+                installed_version: installed
+                    .iter()
+                    .find(|p| &p.name == id)
+                    .map(|p| p.version().to_string()),
+                in_catalog: package.is_some(),
+            });
+            if let Some(resolved) = resolved {
+                if depth + 1 < max_depth && !stack.contains(id) {
+                    stack.push(id.clone());
+                    self.collect_dependencies(
+                        resolved,
+                        depth + 1,
+                        max_depth,
+                        installed,
+                        stack,
+                        nodes,
+                    );
+                    stack.pop();
+                }
+            }
+        }
     }
 
     pub fn filtered_available_packages(&self) -> Vec<&AvailablePackage> {
+        let visible = self
+            .available_packages
+            .iter()
+            .filter(|p| self.show_prereleases || p.has_stable_release());
         if self.available_package_search.is_empty() {
-            return self.available_packages.iter().collect();
+            return visible.collect();
         }
         let needle = self.available_package_search.to_lowercase();
-        self.available_packages
-            .iter()
+        visible
             .filter(|p| {
                 p.id.to_lowercase().contains(&needle)
                     || p.display_name.to_lowercase().contains(&needle)
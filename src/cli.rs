@@ -0,0 +1,412 @@
+use crate::app::action::Action;
+use crate::app::effect::Effect;
+use crate::app::reducer::reduce;
+use crate::app::state::{AppState, ManifestSummary, ProjectMeta};
+use crate::services;
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_FAILURE: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+pub const EXIT_OUTDATED: i32 = 3;
+
+const USAGE: &str = "\
+Usage: vcc-tui [COMMAND]
+
+Without a command the interactive TUI starts.
+
+Commands:
+  projects list          List registered projects
+  projects add <path>    Register a project
+  packages <project>     List declared and locked packages of a project
+  outdated <project>     List packages with a newer version in the catalog
+                         (exit code 3 when updates are available)
+  scan <folder>          Find VPM and legacy Unity projects under a folder
+
+<project> is a registered project name or a path. All output is JSON.";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    ProjectsList,
+    ProjectsAdd(PathBuf),
+    Packages(String),
+    Outdated(String),
+    Scan(PathBuf),
+    Help,
+}
+
+pub fn parse(args: &[String]) -> Result<Option<Command>, String> {
+    let args = args.iter().map(|a| a.as_str()).collect::<Vec<_>>();
+    let command = match args.as_slice() {
+        [] => return Ok(None),
+        ["-h" | "--help" | "help"] => Command::Help,
+        ["projects", "list"] => Command::ProjectsList,
+        ["projects", "add", path] => Command::ProjectsAdd(PathBuf::from(path)),
+        ["packages", project] => Command::Packages(project.to_string()),
+        ["outdated", project] => Command::Outdated(project.to_string()),
+        ["scan", folder] => Command::Scan(PathBuf::from(folder)),
+        _ => return Err(format!("unrecognized arguments: {}", args.join(" "))),
+    };
+    Ok(Some(command))
+}
+
+pub fn run(command: Command) -> i32 {
+    if command == Command::Help {
+        println!("{USAGE}");
+        return EXIT_OK;
+    }
+    match Paths::from_home().and_then(|paths| execute(&paths, command)) {
+        Ok((output, code)) => print_json(&output, code),
+        Err(err) => print_json(&json!({ "error": format!("{err:#}") }), EXIT_FAILURE),
+    }
+}
+
+pub fn usage_error(message: &str) -> i32 {
+    print_json(&json!({ "error": message, "usage": USAGE }), EXIT_USAGE)
+}
+
+fn print_json(output: &Value, code: i32) -> i32 {
+    match serde_json::to_string_pretty(output) {
+        Ok(raw) => {
+            println!("{raw}");
+            code
+        }
+        Err(err) => {
+            eprintln!("error: failed to serialize output: {err}");
+            EXIT_FAILURE
+        }
+    }
+}
+
+/// Where the CLI reads and writes; tests point these into a temp dir.
+struct Paths {
+    config: PathBuf,
+    repos_dir: PathBuf,
+}
+
+impl Paths {
+    fn from_home() -> Result<Self> {
+        Ok(Self {
+            config: services::fs::config_file_path()?,
+            repos_dir: services::fs::vcc_repos_dir()?,
+        })
+    }
+}
+
+/// Drives the same reducer as the TUI without a terminal. Only the effects a
+/// one-shot command needs are performed; vpm tasks, path checks and watch
+/// scans are left to the interactive app.
+struct Session<'a> {
+    paths: &'a Paths,
+    state: AppState,
+}
+
+impl<'a> Session<'a> {
+    fn open(paths: &'a Paths) -> Result<Self> {
+        let config = services::fs::load_config_from(&paths.config)?;
+        let mut session = Self {
+            paths,
+            state: AppState::default(),
+        };
+        session.send(Action::ConfigLoaded(Ok(config)))?;
+        Ok(session)
+    }
+
+    fn send(&mut self, action: Action) -> Result<()> {
+        let mut pending = VecDeque::from([action]);
+        while let Some(action) = pending.pop_front() {
+            self.state.now = chrono::Local::now();
+            for effect in reduce(&mut self.state, action) {
+                match effect {
+                    Effect::SaveConfig(config) => {
+                        services::fs::save_config_to(&self.paths.config, &config)?
+                    }
+                    Effect::ReadManifest { project_path } => {
+                        let result = services::fs::read_manifest(&project_path)
+                            .map_err(|e| format!("{e:#}"));
+                        pending.push_back(Action::ManifestLoaded {
+                            project_path,
+                            result,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_project(&self, project: &str) -> Result<PathBuf> {
+        let path = services::fs::normalize_path(Path::new(project));
+        if let Some(meta) = self
+            .state
+            .projects
+            .iter()
+            .find(|p| p.display_name == project || p.path == path)
+        {
+            return Ok(meta.path.clone());
+        }
+        if path.is_dir() {
+            return Ok(path);
+        }
+        bail!("no registered project or folder named {project}")
+    }
+
+    fn manifest(&mut self, project: &str) -> Result<(PathBuf, ManifestSummary)> {
+        let path = self.resolve_project(project)?;
+        let summary = services::fs::read_manifest(&path)?;
+        if !summary.exists {
+            bail!(
+                "{}",
+                summary
+                    .message
+                    .unwrap_or_else(|| "manifest is missing".to_string())
+            );
+        }
+        self.send(Action::ManifestLoaded {
+            project_path: path.clone(),
+            result: Ok(summary),
+        })?;
+        let summary = self
+            .state
+            .project_manifests
+            .get(&path)
+            .cloned()
+            .context("manifest was not loaded")?;
+        Ok((path, summary))
+    }
+}
+
+fn execute(paths: &Paths, command: Command) -> Result<(Value, i32)> {
+    let mut session = Session::open(paths)?;
+    match command {
+        Command::Help => Ok((Value::String(USAGE.to_string()), EXIT_OK)),
+        Command::ProjectsList => projects_list(&session),
+        Command::ProjectsAdd(path) => projects_add(&mut session, path),
+        Command::Packages(project) => packages(&mut session, &project),
+        Command::Outdated(project) => outdated(&mut session, &project),
+        Command::Scan(folder) => scan(&session, folder),
+    }
+}
+
+fn project_json(project: &ProjectMeta) -> Value {
+    json!({
+        "name": project.display_name,
+        "path": project.path,
+        "tags": project.tags,
+        "last_opened": project.last_opened,
+        "exists": project.path.exists(),
+    })
+}
+
+fn projects_list(session: &Session) -> Result<(Value, i32)> {
+    let projects = session
+        .state
+        .projects
+        .iter()
+        .map(project_json)
+        .collect::<Vec<_>>();
+    Ok((Value::Array(projects), EXIT_OK))
+}
+
+fn projects_add(session: &mut Session, path: PathBuf) -> Result<(Value, i32)> {
+    let path = fs::canonicalize(services::fs::expand_home(&path))
+        .with_context(|| format!("project path does not exist: {}", path.display()))?;
+    let before = session.state.projects.len();
+    session.send(Action::AddProject(path.clone()))?;
+    let project = session
+        .state
+        .projects
+        .iter()
+        .find(|p| p.path == path)
+        .context("project was not added")?;
+    Ok((
+        json!({
+            "added": session.state.projects.len() > before,
+            "project": project_json(project),
+        }),
+        EXIT_OK,
+    ))
+}
+
+fn packages(session: &mut Session, project: &str) -> Result<(Value, i32)> {
+    let (path, summary) = session.manifest(project)?;
+    let packages = summary
+        .installed_packages()
+        .iter()
+        .map(|p| {
+            json!({
+                "name": p.name,
+                "declared_version": p.declared_version,
+                "locked_version": p.locked_version,
+                "direct": p.is_direct(),
+                "required_by": p.required_by,
+                "version_mismatch": p.has_version_mismatch(),
+            })
+        })
+        .collect::<Vec<_>>();
+    Ok((json!({ "project": path, "packages": packages }), EXIT_OK))
+}
+
+fn outdated(session: &mut Session, project: &str) -> Result<(Value, i32)> {
+    let (path, summary) = session.manifest(project)?;
+    let packages = services::fs::load_available_packages_from_dir(
+        &session.paths.repos_dir,
+        &session.state.repo_priority,
+        &session.state.disabled_repos,
+    )?;
+    session.send(Action::AvailablePackagesLoaded(Ok(packages)))?;
+
+    let updates = session
+        .state
+        .outdated_packages_in(&summary)
+        .iter()
+        .map(|u| {
+            json!({
+                "name": u.name,
+                "current_version": u.current_version,
+                "latest_version": u.latest_version,
+                "bump": u.bump.label(),
+            })
+        })
+        .collect::<Vec<_>>();
+    let code = if updates.is_empty() {
+        EXIT_OK
+    } else {
+        EXIT_OUTDATED
+    };
+    Ok((json!({ "project": path, "outdated": updates }), code))
+}
+
+fn scan(session: &Session, folder: PathBuf) -> Result<(Value, i32)> {
+    let folder = services::fs::normalize_path(&folder);
+    let found = services::scan::scan_projects(&folder, &session.state.scan_settings, |_| {})?;
+    Ok((json!({ "root": folder, "projects": found }), EXIT_OK))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::harness::TempDir;
+
+    struct Fixture {
+        _temp: TempDir,
+        root: PathBuf,
+        paths: Paths,
+    }
+
+    fn fixture(label: &str) -> Fixture {
+        let temp = TempDir::new(label);
+        let root = fs::canonicalize(temp.path()).expect("canonical temp dir");
+        let packages_dir = root.join("Avatar").join("Packages");
+        fs::create_dir_all(&packages_dir).expect("create project");
+        fs::write(
+            packages_dir.join("vpm-manifest.json"),
+            r#"{
+                "dependencies": { "com.example.tool": { "version": "1.0.0" } },
+                "locked": { "com.example.tool": { "version": "1.0.0", "dependencies": {} } }
+            }"#,
+        )
+        .expect("write manifest");
+        let repos_dir = root.join("Repos");
+        fs::create_dir_all(&repos_dir).expect("create repos dir");
+        fs::write(
+            repos_dir.join("com.example.repo-1.json"),
+            r#"{"repo": {"id": "com.example.repo", "packages": {
+                "com.example.tool": {"versions": {
+                    "1.0.0": {"displayName": "Tool"},
+                    "1.2.0": {"displayName": "Tool"}
+                }}
+            }}}"#,
+        )
+        .expect("write repo cache");
+        let paths = Paths {
+            config: root.join("config").join("config.json"),
+            repos_dir,
+        };
+        Fixture {
+            _temp: temp,
+            root,
+            paths,
+        }
+    }
+
+    fn args(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn projects_add_registers_a_canonical_path_once_and_lists_it() {
+        let f = fixture("cli-projects");
+        let project = f.root.join("Avatar");
+        let typed = f.root.join("Repos").join("..").join("Avatar");
+
+        let (first, code) = execute(&f.paths, Command::ProjectsAdd(typed)).expect("add project");
+        let (second, _) =
+            execute(&f.paths, Command::ProjectsAdd(project.clone())).expect("add again");
+        let (listed, _) = execute(&f.paths, Command::ProjectsList).expect("list projects");
+
+        assert_eq!(code, EXIT_OK);
+        assert_eq!(first["added"], json!(true));
+        assert_eq!(first["project"]["path"], json!(project));
+        assert_eq!(second["added"], json!(false));
+        assert_eq!(listed.as_array().map(Vec::len), Some(1));
+        assert_eq!(listed[0]["name"], json!("Avatar"));
+        assert!(execute(&f.paths, Command::ProjectsAdd(f.root.join("Missing"))).is_err());
+    }
+
+    #[test]
+    fn packages_and_outdated_read_a_registered_project_by_name() {
+        let f = fixture("cli-packages");
+        execute(&f.paths, Command::ProjectsAdd(f.root.join("Avatar"))).expect("add project");
+
+        let (packages, code) =
+            execute(&f.paths, Command::Packages("Avatar".to_string())).expect("packages");
+        assert_eq!(code, EXIT_OK);
+        assert_eq!(packages["packages"][0]["name"], json!("com.example.tool"));
+        assert_eq!(packages["packages"][0]["locked_version"], json!("1.0.0"));
+
+        let (outdated, code) =
+            execute(&f.paths, Command::Outdated("Avatar".to_string())).expect("outdated");
+        assert_eq!(code, EXIT_OUTDATED);
+        assert_eq!(outdated["outdated"][0]["latest_version"], json!("1.2.0"));
+
+        let err = execute(&f.paths, Command::Packages("Unknown".to_string()))
+            .expect_err("unknown project");
+        assert_eq!(
+            err.to_string(),
+            "no registered project or folder named Unknown"
+        );
+    }
+
+    #[test]
+    fn scan_finds_projects_under_a_folder() {
+        let f = fixture("cli-scan");
+
+        let (output, code) = execute(&f.paths, Command::Scan(f.root.clone())).expect("scan");
+
+        assert_eq!(code, EXIT_OK);
+        let found = output["projects"].as_array().expect("project list");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0]["path"], json!(f.root.join("Avatar")));
+    }
+
+    #[test]
+    fn parses_subcommands() {
+        assert_eq!(parse(&args(&[])), Ok(None));
+        assert_eq!(
+            parse(&args(&["projects", "add", "/tmp/p"])),
+            Ok(Some(Command::ProjectsAdd(PathBuf::from("/tmp/p"))))
+        );
+        assert_eq!(
+            parse(&args(&["outdated", "Avatar"])),
+            Ok(Some(Command::Outdated("Avatar".to_string())))
+        );
+        assert!(parse(&args(&["projects"])).is_err());
+        assert!(parse(&args(&["bogus", "x", "y"])).is_err());
+    }
+}
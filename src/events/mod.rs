@@ -35,6 +35,11 @@ async fn event_loop(action_tx: mpsc::UnboundedSender<Action>) -> Result<()> {
                             break;
                         }
                     }
+                    Some(Ok(CrosstermEvent::Resize(_, height))) => {
+                        if action_tx.send(Action::Resize { height }).is_err() {
+                            break;
+                        }
+                    }
                     Some(Ok(_)) => {}
                     Some(Err(err)) => {
                         let _ = action_tx.send(Action::TaskOutput {
@@ -1,5 +1,7 @@
 mod app;
+mod cli;
 mod events;
+mod semver;
 mod services;
 mod ui;
 
@@ -7,7 +9,7 @@ use anyhow::Result;
 use app::action::Action;
 use app::effect::Effect;
 use app::reducer::reduce;
-use app::state::AppState;
+use app::state::{AppState, ProjectDetails};
 use crossterm::event::DisableMouseCapture;
 use crossterm::execute;
 use crossterm::terminal::{
@@ -15,15 +17,25 @@ use crossterm::terminal::{
 };
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
-use services::vpm::VpmClient;
+use services::history::{HistoryWrite, HistoryWriter};
+use services::scan::ScanEvent;
+use services::vpm::{CommandBackend, CommandRequest, VpmClient};
 use std::collections::HashMap;
 use std::fs;
 use std::io;
+use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match cli::parse(&args) {
+        Ok(Some(command)) => std::process::exit(cli::run(command)),
+        Ok(None) => {}
+        Err(err) => std::process::exit(cli::usage_error(&err)),
+    }
+
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
         let _ = disable_raw_mode();
@@ -40,11 +52,20 @@ async fn main() -> Result<()> {
     let (action_tx, mut action_rx) = mpsc::unbounded_channel::<Action>();
     events::spawn_event_loop(action_tx.clone());
 
-    let vpm = VpmClient;
+    let mut backend: Arc<dyn CommandBackend> = Arc::new(VpmClient::default());
     let mut running_tokens: HashMap<u64, CancellationToken> = HashMap::new();
 
-    let mut state = AppState::default();
+    let history_tx = spawn_history_writer(action_tx.clone());
+
+    let mut state = AppState {
+        default_backup_dir: services::fs::default_backup_dir().ok(),
+        session_id: services::history::new_session_id(),
+        ..AppState::default()
+    };
     let _ = action_tx.send(Action::Init);
+    let _ = action_tx.send(Action::Resize {
+        height: terminal.size()?.height,
+    });
 
     loop {
         terminal.draw(|frame| ui::render(frame, &state))?;
@@ -61,9 +82,17 @@ async fn main() -> Result<()> {
             running_tokens.remove(&task_id);
         }
 
+        state.now = chrono::Local::now();
         let effects = reduce(&mut state, action);
         for effect in effects {
-            handle_effect(effect, &action_tx, &vpm, &mut running_tokens).await;
+            handle_effect(
+                effect,
+                &action_tx,
+                &history_tx,
+                &mut backend,
+                &mut running_tokens,
+            )
+            .await;
         }
     }
 
@@ -74,7 +103,8 @@ async fn main() -> Result<()> {
 async fn handle_effect(
     effect: Effect,
     action_tx: &mpsc::UnboundedSender<Action>,
-    vpm: &VpmClient,
+    history_tx: &mpsc::UnboundedSender<HistoryWrite>,
+    backend: &mut Arc<dyn CommandBackend>,
     running_tokens: &mut HashMap<u64, CancellationToken>,
 ) {
     match effect {
@@ -85,6 +115,9 @@ async fn handle_effect(
                 let _ = tx.send(Action::ConfigLoaded(result));
             });
         }
+        Effect::ConfigureCommandBackend(settings) => {
+            *backend = Arc::new(VpmClient::new(settings));
+        }
         Effect::SaveConfig(config) => {
             let tx = action_tx.clone();
             tokio::spawn(async move {
@@ -92,27 +125,156 @@ async fn handle_effect(
                 let _ = tx.send(Action::ConfigSaved(result));
             });
         }
-        Effect::LoadAvailablePackages => {
+        Effect::LoadAvailablePackages {
+            repo_priority,
+            disabled_repos,
+        } => {
             let tx = action_tx.clone();
             tokio::spawn(async move {
-                let result = services::fs::load_available_packages_from_vcc_cache()
-                    .map_err(|e| e.to_string());
+                let result = services::fs::load_available_packages_from_vcc_cache(
+                    &repo_priority,
+                    &disabled_repos,
+                )
+                .map_err(|e| e.to_string());
                 let _ = tx.send(Action::AvailablePackagesLoaded(result));
             });
         }
-        Effect::ScanProjectsFolder { root } => {
+        Effect::LoadRepositories => {
+            let tx = action_tx.clone();
+            tokio::spawn(async move {
+                let result = services::repos::load_user_repos().map_err(|e| e.to_string());
+                let _ = tx.send(Action::RepositoriesLoaded(result));
+            });
+        }
+        Effect::SaveRepositories { repos, removed } => {
             let tx = action_tx.clone();
             tokio::spawn(async move {
                 let result =
-                    services::fs::scan_projects_one_level(&root).map_err(|e| e.to_string());
-                let _ = tx.send(Action::FolderScanned(result));
+                    services::repos::save_user_repos(&repos, &removed).map_err(|e| e.to_string());
+                let _ = tx.send(Action::RepositoriesSaved(result));
+            });
+        }
+        Effect::ScanProjectsFolder {
+            scan_id,
+            root,
+            settings,
+        } => {
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let result = services::scan::scan_projects(&root, &settings, |event| {
+                    let action = match event {
+                        ScanEvent::Visited { visited, dir } if visited % 25 == 1 => {
+                            Action::ScanProgress {
+                                scan_id,
+                                root: root.clone(),
+                                visited,
+                                dir,
+                            }
+                        }
+                        ScanEvent::Visited { .. } => return,
+                        ScanEvent::Found(project) => Action::ProjectDiscovered {
+                            scan_id,
+                            root: root.clone(),
+                            project,
+                        },
+                    };
+                    let _ = tx.send(action);
+                })
+                .map(|found| found.len())
+                .map_err(|e| e.to_string());
+                let _ = tx.send(Action::ScanFinished {
+                    scan_id,
+                    root,
+                    result,
+                });
+            });
+        }
+        Effect::CheckProjectPaths(paths) => {
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let offline = paths.into_iter().filter(|p| !p.exists()).collect();
+                let _ = tx.send(Action::ProjectPathsChecked(offline));
+            });
+        }
+        Effect::ReadProjectDetails(paths) => {
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let details = paths
+                    .into_iter()
+                    .map(|path| ProjectDetails {
+                        unity_version: services::unity::read_project_version(&path),
+                        manifest: services::fs::read_manifest(&path).ok(),
+                        path,
+                    })
+                    .collect();
+                let _ = tx.send(Action::ProjectDetailsLoaded(details));
             });
         }
         Effect::ReadManifest { project_path } => {
             let tx = action_tx.clone();
             tokio::spawn(async move {
                 let result = services::fs::read_manifest(&project_path).map_err(|e| e.to_string());
-                let _ = tx.send(Action::ManifestLoaded(result));
+                let _ = tx.send(Action::ManifestLoaded {
+                    project_path,
+                    result,
+                });
+            });
+        }
+        Effect::BackupProject {
+            project_path,
+            settings,
+        } => {
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let created = services::backup::backup_dir(&settings)
+                    .and_then(|dir| services::backup::create_backup(&project_path, &dir));
+                let (result, skipped) = match created {
+                    Ok(created) => (Ok(created.entry), created.skipped),
+                    Err(err) => (Err(err.to_string()), Vec::new()),
+                };
+                let _ = tx.send(Action::ProjectBackedUp {
+                    project_path,
+                    result,
+                    skipped,
+                });
+            });
+        }
+        Effect::ListBackups {
+            project_path,
+            settings,
+        } => {
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let result = services::backup::backup_dir(&settings)
+                    .and_then(|dir| services::backup::list_backups(&project_path, &dir))
+                    .map_err(|e| e.to_string());
+                let _ = tx.send(Action::BackupsListed {
+                    project_path,
+                    result,
+                });
+            });
+        }
+        Effect::RestoreBackup { backup, target } => {
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let result =
+                    services::backup::restore_backup(&backup, &target).map_err(|e| e.to_string());
+                let _ = tx.send(Action::BackupRestored { backup, result });
+            });
+        }
+        Effect::OpenInUnity {
+            project_path,
+            settings,
+            editors,
+        } => {
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let result = services::unity::open_project(&project_path, &settings, &editors)
+                    .map_err(|e| e.to_string());
+                let _ = tx.send(Action::UnityOpened {
+                    project_path,
+                    result,
+                });
             });
         }
         Effect::RunVpmCommand {
@@ -121,14 +283,20 @@ async fn handle_effect(
             args,
         } => {
             let tx = action_tx.clone();
-            let client = vpm.clone();
             let token = CancellationToken::new();
             running_tokens.insert(task_id, token.clone());
+            let run = backend.run(
+                CommandRequest {
+                    task_id,
+                    label,
+                    args,
+                },
+                token,
+                tx.clone(),
+            );
 
             tokio::spawn(async move {
-                let command_result = client
-                    .run_command(task_id, label.clone(), args, token, tx.clone())
-                    .await;
+                let command_result = run.await;
 
                 if let Err(err) = command_result {
                     let _ = tx.send(Action::TaskDone {
@@ -141,6 +309,47 @@ async fn handle_effect(
                 }
             });
         }
+        Effect::AppendTaskLog {
+            session,
+            task_id,
+            line,
+        } => {
+            let _ = history_tx.send(HistoryWrite::Log {
+                session,
+                task_id,
+                line,
+            });
+        }
+        Effect::RecordTaskHistory(entry) => {
+            let _ = history_tx.send(HistoryWrite::Entry(entry));
+        }
+        Effect::LoadTaskHistory => {
+            let tx = action_tx.clone();
+            tokio::spawn(async move {
+                let result = services::history::history_dir()
+                    .and_then(|dir| services::history::load_history(&dir))
+                    .map_err(|e| e.to_string());
+                let _ = tx.send(Action::TaskHistoryLoaded(result));
+            });
+        }
+        Effect::ExportLogs { lines } => {
+            let tx = action_tx.clone();
+            tokio::spawn(async move {
+                let result = services::fs::cache_dir_path()
+                    .and_then(|dir| services::fs::export_logs(&dir.join("exports"), &lines))
+                    .map_err(|e| e.to_string());
+                let _ = tx.send(Action::LogsExported(result));
+            });
+        }
+        Effect::ReadHistoryLog { session, task_id } => {
+            let tx = action_tx.clone();
+            tokio::spawn(async move {
+                let result = services::history::history_dir()
+                    .and_then(|dir| services::history::read_task_log(&dir, &session, task_id))
+                    .map_err(|e| e.to_string());
+                let _ = tx.send(Action::HistoryLogLoaded(result));
+            });
+        }
         Effect::CancelTask { task_id } => {
             if let Some(token) = running_tokens.get(&task_id) {
                 token.cancel();
@@ -155,6 +364,34 @@ async fn handle_effect(
     }
 }
 
+// Task logs and history records are written on one blocking thread, in
+// order, flushing after each batch. A failure is reported once until a later
+// batch succeeds again.
+fn spawn_history_writer(
+    action_tx: mpsc::UnboundedSender<Action>,
+) -> mpsc::UnboundedSender<HistoryWrite> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<HistoryWrite>();
+    tokio::task::spawn_blocking(move || {
+        let mut writer = services::history::history_dir().map(HistoryWriter::new);
+        let mut failing = false;
+        while let Some(first) = rx.blocking_recv() {
+            let result = match writer.as_mut() {
+                Ok(writer) => std::iter::once(first)
+                    .chain(std::iter::from_fn(|| rx.try_recv().ok()))
+                    .try_for_each(|write| writer.write(write))
+                    .and_then(|()| writer.flush())
+                    .map_err(|e| e.to_string()),
+                Err(err) => Err(err.to_string()),
+            };
+            if result.is_err() != failing {
+                failing = result.is_err();
+                let _ = action_tx.send(Action::TaskHistorySaved(result));
+            }
+        }
+    });
+    tx
+}
+
 fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -0,0 +1,480 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Identifier {
+    Numeric(u64),
+    Alpha(String),
+}
+
+impl Ord for Identifier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Numeric(a), Self::Numeric(b)) => a.cmp(b),
+            (Self::Numeric(_), Self::Alpha(_)) => Ordering::Less,
+            (Self::Alpha(_), Self::Numeric(_)) => Ordering::Greater,
+            (Self::Alpha(a), Self::Alpha(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pre: Vec<Identifier>,
+    build: String,
+}
+
+impl Version {
+    pub fn parse(input: &str) -> Option<Self> {
+        let trimmed = input.trim();
+        let trimmed = trimmed
+            .strip_prefix('v')
+            .or_else(|| trimmed.strip_prefix('='))
+            .unwrap_or(trimmed);
+
+        let (rest, build) = match trimmed.split_once('+') {
+            Some((rest, build)) => (rest, build.to_string()),
+            None => (trimmed, String::new()),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (rest, None),
+        };
+
+        let mut parts = core.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().map(|v| v.parse().ok()).unwrap_or(Some(0))?;
+        let patch = parts.next().map(|v| v.parse().ok()).unwrap_or(Some(0))?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        let pre = match pre {
+            Some(pre) => {
+                let mut ids = Vec::new();
+                for part in pre.split('.') {
+                    if part.is_empty() {
+                        return None;
+                    }
+                    ids.push(match part.parse::<u64>() {
+                        Ok(n) => Identifier::Numeric(n),
+                        Err(_) => Identifier::Alpha(part.to_string()),
+                    });
+                }
+                ids
+            }
+            None => Vec::new(),
+        };
+
+        Some(Self {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        })
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major
+            .cmp(&other.major)
+            .then(self.minor.cmp(&other.minor))
+            .then(self.patch.cmp(&other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            let pre = self
+                .pre
+                .iter()
+                .map(|id| match id {
+                    Identifier::Numeric(n) => n.to_string(),
+                    Identifier::Alpha(s) => s.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join(".");
+            write!(f, "-{pre}")?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionBump {
+    Major,
+    Minor,
+    Patch,
+}
+
+impl VersionBump {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Major => "major",
+            Self::Minor => "minor",
+            Self::Patch => "patch",
+        }
+    }
+}
+
+pub fn bump_between(current: &str, candidate: &str) -> Option<VersionBump> {
+    let current = Version::parse(current)?;
+    let candidate = Version::parse(candidate)?;
+    if candidate <= current {
+        return None;
+    }
+    Some(if candidate.major != current.major {
+        VersionBump::Major
+    } else if candidate.minor != current.minor {
+        VersionBump::Minor
+    } else {
+        VersionBump::Patch
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+}
+
+fn version_of(major: u64, minor: u64, patch: u64) -> Version {
+    Version {
+        major,
+        minor,
+        patch,
+        pre: Vec::new(),
+        build: String::new(),
+    }
+}
+
+fn parse_partial(input: &str) -> Option<(Vec<u64>, Option<Version>)> {
+    if let Some(full) = Version::parse(input) {
+        if input.split(['-', '+']).next()?.split('.').count() == 3 {
+            let parts = vec![full.major, full.minor, full.patch];
+            return Some((parts, Some(full)));
+        }
+    }
+    let mut parts = Vec::new();
+    for part in input.trim_start_matches('v').split('.') {
+        if matches!(part, "x" | "X" | "*") {
+            break;
+        }
+        parts.push(part.parse().ok()?);
+    }
+    if parts.len() > 3 {
+        return None;
+    }
+    Some((parts, None))
+}
+
+fn comparators(token: &str) -> Option<Vec<(Op, Version)>> {
+    if matches!(token, "" | "*" | "x" | "X") {
+        return Some(Vec::new());
+    }
+    let (op, rest) = [">=", "<=", ">", "<", "=", "^", "~"]
+        .iter()
+        .find_map(|op| token.strip_prefix(op).map(|rest| (*op, rest)))
+        .unwrap_or(("", token));
+    let (parts, full) = parse_partial(rest.trim())?;
+    let get = |idx: usize| parts.get(idx).copied().unwrap_or(0);
+    let lower = full
+        .clone()
+        .unwrap_or_else(|| version_of(get(0), get(1), get(2)));
+    let next_of_partial = || match parts.len() {
+        0 => None,
+        1 => Some(version_of(get(0) + 1, 0, 0)),
+        2 => Some(version_of(get(0), get(1) + 1, 0)),
+        _ => Some(version_of(get(0), get(1), get(2) + 1)),
+    };
+
+    let mut out = Vec::new();
+    match op {
+        ">=" => out.push((Op::Ge, lower)),
+        "<" => out.push((Op::Lt, lower)),
+        ">" => match &full {
+            Some(v) => out.push((Op::Gt, v.clone())),
+            None => out.extend(next_of_partial().map(|v| (Op::Ge, v))),
+        },
+        "<=" => match &full {
+            Some(v) => out.push((Op::Le, v.clone())),
+            None => out.extend(next_of_partial().map(|v| (Op::Lt, v))),
+        },
+        "^" => {
+            let upper = if get(0) > 0 || parts.len() < 2 {
+                version_of(get(0) + 1, 0, 0)
+            } else if get(1) > 0 || parts.len() < 3 {
+                version_of(0, get(1) + 1, 0)
+            } else {
+                version_of(0, 0, get(2) + 1)
+            };
+            out.push((Op::Ge, lower));
+            out.push((Op::Lt, upper));
+        }
+        "~" => {
+            let upper = if parts.len() < 2 {
+                version_of(get(0) + 1, 0, 0)
+            } else {
+                version_of(get(0), get(1) + 1, 0)
+            };
+            out.push((Op::Ge, lower));
+            out.push((Op::Lt, upper));
+        }
+        _ => match full {
+            Some(v) => out.push((Op::Eq, v)),
+            None => {
+                out.push((Op::Ge, lower));
+                out.extend(next_of_partial().map(|v| (Op::Lt, v)));
+            }
+        },
+    }
+    Some(out)
+}
+
+fn parse_range(range: &str) -> Option<Vec<Vec<(Op, Version)>>> {
+    let mut alternatives = Vec::new();
+    for alternative in range.split("||") {
+        let mut tokens = Vec::new();
+        let mut pending_op = String::new();
+        for word in alternative.split_whitespace() {
+            if word
+                .chars()
+                .all(|c| matches!(c, '>' | '<' | '=' | '^' | '~'))
+            {
+                pending_op.push_str(word);
+            } else {
+                tokens.push(format!("{pending_op}{word}"));
+                pending_op.clear();
+            }
+        }
+
+        let mut set = Vec::new();
+        if let [low, "-", high] = tokens.iter().map(|t| t.as_str()).collect::<Vec<_>>()[..] {
+            set.extend(comparators(&format!(">={low}"))?);
+            set.extend(comparators(&format!("<={high}"))?);
+        } else {
+            for token in &tokens {
+                set.extend(comparators(token)?);
+            }
+        }
+        alternatives.push(set);
+    }
+    Some(alternatives)
+}
+
+pub fn satisfies(version: &str, range: &str) -> bool {
+    let Some(version) = Version::parse(version) else {
+        return false;
+    };
+    let Some(alternatives) = parse_range(range) else {
+        return false;
+    };
+    alternatives.iter().any(|set| {
+        set.iter().all(|(op, bound)| match op {
+            Op::Gt => version > *bound,
+            Op::Ge => version >= *bound,
+            Op::Lt => version < *bound,
+            Op::Le => version <= *bound,
+            Op::Eq => version == *bound,
+        })
+    })
+}
+
+// VPM reads a bare version in `vpmDependencies` as a minimum, not an exact pin.
+pub fn satisfies_dependency(version: &str, range: &str) -> bool {
+    let bare = !range.trim().contains(['>', '<', '=', '^', '~', '|', ' ']);
+    match Version::parse(range) {
+        Some(minimum) if bare => Version::parse(version).is_some_and(|v| v >= minimum),
+        _ => satisfies(version, range),
+    }
+}
+
+pub fn is_prerelease(version: &str) -> bool {
+    Version::parse(version)
+        .map(|v| v.is_prerelease())
+        .unwrap_or(false)
+}
+
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    match (Version::parse(a), Version::parse(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (None, None) => a.cmp(b),
+    }
+}
+
+pub fn compare_unity_versions(a: &str, b: &str) -> Ordering {
+    fn chunks(version: &str) -> Vec<Result<u64, String>> {
+        let mut chunks = Vec::new();
+        let mut chars = version.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            let digit = c.is_ascii_digit();
+            let mut chunk = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_ascii_digit() != digit {
+                    break;
+                }
+                chunk.push(c);
+                chars.next();
+            }
+            chunks.push(if digit {
+                Ok(chunk.parse().unwrap_or(u64::MAX))
+            } else {
+                Err(chunk)
+            });
+        }
+        chunks
+    }
+    chunks(a).cmp(&chunks(b))
+}
+
+pub fn sort_versions_desc(versions: &mut [String]) {
+    versions.sort_by(|a, b| compare_versions(b, a));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_numeric_components_numerically() {
+        assert_eq!(compare_versions("3.10.0", "3.9.0"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0.10", "1.0.9"), Ordering::Greater);
+    }
+
+    #[test]
+    fn orders_unity_versions_by_number_then_release_type() {
+        assert_eq!(
+            compare_unity_versions("2022.3.22f1", "2022.3.9f1"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_unity_versions("2019.4.31f1", "2022.3.6f1"),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_unity_versions("2022.3.6b2", "2022.3.6f1"),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn prereleases_sort_below_release() {
+        assert_eq!(compare_versions("1.0.0-beta.2", "1.0.0"), Ordering::Less);
+        assert_eq!(
+            compare_versions("1.0.0-beta.11", "1.0.0-beta.2"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_versions("1.0.0-alpha", "1.0.0-alpha.1"),
+            Ordering::Less
+        );
+        assert!(is_prerelease("3.5.0-beta.1"));
+        assert!(!is_prerelease("3.5.0+build.7"));
+    }
+
+    #[test]
+    fn build_metadata_is_ignored_for_precedence() {
+        assert_eq!(compare_versions("1.2.3+a", "1.2.3+b"), Ordering::Equal);
+        assert_eq!(
+            Version::parse("1.2.3-rc.1+sha.5").unwrap().to_string(),
+            "1.2.3-rc.1+sha.5"
+        );
+    }
+
+    #[test]
+    fn classifies_bumps() {
+        assert_eq!(bump_between("3.5.0", "4.0.0"), Some(VersionBump::Major));
+        assert_eq!(bump_between("3.5.0", "3.10.0"), Some(VersionBump::Minor));
+        assert_eq!(bump_between("3.5.0", "3.5.2"), Some(VersionBump::Patch));
+        assert_eq!(
+            bump_between("3.5.0-beta.1", "3.5.0"),
+            Some(VersionBump::Patch)
+        );
+        assert_eq!(bump_between("3.5.2", "3.5.2"), None);
+        assert_eq!(bump_between("3.6.0", "3.5.2"), None);
+    }
+
+    #[test]
+    fn matches_ranges() {
+        assert!(satisfies("3.5.2", ">=3.5.0"));
+        assert!(!satisfies("3.4.9", ">= 3.5.0"));
+        assert!(satisfies("1.9.0", "^1.2.3"));
+        assert!(!satisfies("2.0.0", "^1.2.3"));
+        assert!(!satisfies("0.3.0", "^0.2.1"));
+        assert!(satisfies("1.2.9", "~1.2.3"));
+        assert!(!satisfies("1.3.0", "~1.2.3"));
+        assert!(satisfies("3.10.4", "3.x"));
+        assert!(satisfies("1.5.0", "1.0.0 - 2.0.0"));
+        assert!(satisfies("0.9.0", "<1.0.0 || >=3.0.0"));
+        assert!(!satisfies("1.0.1", "1.0.0"));
+        assert!(satisfies("1.0.1", "*"));
+    }
+
+    #[test]
+    fn bare_dependency_version_is_a_minimum() {
+        assert!(satisfies_dependency("3.5.2", "3.5.0"));
+        assert!(!satisfies_dependency("3.4.0", "3.5.0"));
+        assert!(satisfies_dependency("1.1.0", "^1.0.0"));
+        assert!(satisfies_dependency("1.0.0", "1.0.0-beta.1"));
+        assert!(!satisfies_dependency("1.0.0-alpha", "1.0.0-beta.1"));
+        assert!(!satisfies_dependency("1.2.4", "=1.2.3"));
+        assert!(satisfies_dependency("1.3.0", "1.2"));
+    }
+
+    #[test]
+    fn sorts_descending_with_unparseable_last() {
+        let mut versions = vec![
+            "3.9.0".to_string(),
+            "garbage".to_string(),
+            "3.10.0-beta.1".to_string(),
+            "3.10.0".to_string(),
+        ];
+        sort_versions_desc(&mut versions);
+        assert_eq!(versions, ["3.10.0", "3.10.0-beta.1", "3.9.0", "garbage"]);
+    }
+}
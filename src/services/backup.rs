@@ -0,0 +1,332 @@
+use crate::app::state::{BackupEntry, BackupSettings};
+use anyhow::{Context, Result};
+use chrono::NaiveDateTime;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+const EXCLUDED_DIRS: [&str; 5] = ["Library", "Temp", "Logs", "obj", "UserSettings"];
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+pub fn backup_dir(settings: &BackupSettings) -> Result<PathBuf> {
+    match &settings.folder {
+        Some(folder) => Ok(folder.clone()),
+        None => super::fs::default_backup_dir(),
+    }
+}
+
+pub struct CreatedBackup {
+    pub entry: BackupEntry,
+    /// Symlinks left out because they are broken or loop back on themselves.
+    pub skipped: Vec<PathBuf>,
+}
+
+pub fn create_backup(project_path: &Path, root: &Path) -> Result<CreatedBackup> {
+    let name = project_name(project_path)?;
+    let dir = project_backup_dir(project_path, root)?;
+    let created = chrono::Local::now().naive_local();
+    let path = dir.join(format!("{name}-{}.zip", created.format(TIMESTAMP_FORMAT)));
+    if path.exists() {
+        anyhow::bail!("backup already exists: {}", path.display());
+    }
+    fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+
+    // A backup folder inside the project must not end up in its own archive.
+    let mut walk = Walk {
+        backup_dirs: [root, dir.as_path()]
+            .iter()
+            .map(|d| canonical(d))
+            .collect::<Result<_>>()?,
+        open_dirs: vec![canonical(project_path)?],
+        skipped: Vec::new(),
+    };
+    let partial = path.with_extension("zip.partial");
+    let written = write_archive(project_path, &partial, &mut walk).and_then(|()| {
+        fs::rename(&partial, &path)
+            .with_context(|| format!("failed to move backup to {}", path.display()))
+    });
+    if let Err(err) = written {
+        let _ = fs::remove_file(&partial);
+        return Err(err);
+    }
+    let size = fs::metadata(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?
+        .len();
+    Ok(CreatedBackup {
+        entry: BackupEntry {
+            created: parse_backup_name(&file_name(&path), &name).unwrap_or(created),
+            path,
+            size,
+        },
+        skipped: walk.skipped,
+    })
+}
+
+pub fn list_backups(project_path: &Path, dir: &Path) -> Result<Vec<BackupEntry>> {
+    let name = project_name(project_path)?;
+    let dir = project_backup_dir(project_path, dir)?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(&dir).with_context(|| format!("failed to read {}", dir.display()))? {
+        let entry = entry?;
+        let Some(created) = parse_backup_name(&entry.file_name().to_string_lossy(), &name) else {
+            continue;
+        };
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        backups.push(BackupEntry {
+            path: entry.path(),
+            created,
+            size: metadata.len(),
+        });
+    }
+    backups.sort_by_key(|b| std::cmp::Reverse(b.created));
+    Ok(backups)
+}
+
+pub fn restore_backup(backup: &Path, target: &Path) -> Result<PathBuf> {
+    if target.exists() {
+        anyhow::bail!("restore target already exists: {}", target.display());
+    }
+    let file =
+        File::open(backup).with_context(|| format!("failed to open {}", backup.display()))?;
+    let mut archive =
+        ZipArchive::new(file).with_context(|| format!("failed to read {}", backup.display()))?;
+    if let Err(err) = archive.extract(target) {
+        let _ = fs::remove_dir_all(target);
+        return Err(err).with_context(|| format!("failed to extract {}", backup.display()));
+    }
+    Ok(target.to_path_buf())
+}
+
+fn write_archive(project_path: &Path, archive_path: &Path, walk: &mut Walk) -> Result<()> {
+    let file = File::create(archive_path)
+        .with_context(|| format!("failed to create {}", archive_path.display()))?;
+    let mut zip = ZipWriter::new(file);
+    add_dir(&mut zip, project_path, "", true, walk)?;
+    zip.finish()
+        .with_context(|| format!("failed to finish {}", archive_path.display()))?;
+    Ok(())
+}
+
+// Symlinks are followed, so linked packages are archived by content; a link
+// that is broken or points back into a folder being archived is skipped and
+// reported instead.
+struct Walk {
+    backup_dirs: Vec<PathBuf>,
+    open_dirs: Vec<PathBuf>,
+    skipped: Vec<PathBuf>,
+}
+
+fn add_dir(
+    zip: &mut ZipWriter<File>,
+    dir: &Path,
+    prefix: &str,
+    top_level: bool,
+    walk: &mut Walk,
+) -> Result<()> {
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("failed to read {}", dir.display()))?
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let entry_name = format!("{prefix}{name}");
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(_) if entry.file_type()?.is_symlink() => {
+                walk.skipped.push(path);
+                continue;
+            }
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to read {}", path.display()))
+            }
+        };
+        if metadata.is_dir() {
+            if top_level && EXCLUDED_DIRS.contains(&name.as_str()) {
+                continue;
+            }
+            let target = canonical(&path)?;
+            if walk.backup_dirs.contains(&target) {
+                continue;
+            }
+            if walk.open_dirs.contains(&target) {
+                walk.skipped.push(path);
+                continue;
+            }
+            zip.add_directory(entry_name.as_str(), SimpleFileOptions::default())?;
+            walk.open_dirs.push(target);
+            add_dir(zip, &path, &format!("{entry_name}/"), false, walk)?;
+            walk.open_dirs.pop();
+        } else if metadata.is_file() {
+            let options = SimpleFileOptions::default()
+                .compression_method(CompressionMethod::Deflated)
+                .large_file(metadata.len() >= u32::MAX as u64);
+            zip.start_file(entry_name.as_str(), options)?;
+            let mut source =
+                File::open(&path).with_context(|| format!("failed to read {}", path.display()))?;
+            io::copy(&mut source, zip)
+                .with_context(|| format!("failed to archive {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+fn canonical(path: &Path) -> Result<PathBuf> {
+    fs::canonicalize(path).with_context(|| format!("failed to resolve {}", path.display()))
+}
+
+fn parse_backup_name(file_name: &str, project_name: &str) -> Option<NaiveDateTime> {
+    let timestamp = file_name
+        .strip_suffix(".zip")?
+        .strip_prefix(project_name)?
+        .strip_prefix('-')?;
+    NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()
+}
+
+pub fn project_backup_dir(project_path: &Path, dir: &Path) -> Result<PathBuf> {
+    let name = project_name(project_path)?;
+    Ok(dir.join(format!("{name}-{:016x}", path_hash(project_path))))
+}
+
+// FNV-1a over the path components, so the folder name stays stable across
+// builds and ignores trailing separators.
+fn path_hash(path: &Path) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    for component in path.components() {
+        for byte in component
+            .as_os_str()
+            .to_string_lossy()
+            .bytes()
+            .chain([b'/'])
+        {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+fn project_name(project_path: &Path) -> Result<String> {
+    project_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .context("project path has no folder name")
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::harness::TempDir;
+
+    #[test]
+    fn backs_up_without_caches_lists_and_restores() {
+        let temp = TempDir::new("backup");
+        let root = temp.path().to_path_buf();
+        let project = root.join("Avatar");
+        for dir in [
+            "Assets/Scenes",
+            "Packages",
+            "Library/cache",
+            "Temp",
+            "Logs",
+            "obj",
+            "UserSettings",
+            "Assets/Library",
+        ] {
+            fs::create_dir_all(project.join(dir)).expect("create dir");
+        }
+        fs::write(project.join("Assets/Scenes/Main.unity"), "scene").expect("write scene");
+        fs::write(project.join("Packages/vpm-manifest.json"), "{}").expect("write manifest");
+        fs::write(project.join("Library/cache/blob"), "x").expect("write cache");
+        fs::write(project.join("UserSettings/Layouts.dwlt"), "x").expect("write settings");
+        let backups = root.join("Backups");
+        let own = project_backup_dir(&project, &backups).expect("backup dir");
+        fs::create_dir_all(&own).expect("create backups");
+        fs::write(own.join("Avatar-Test-20250101-000000.zip"), "other").expect("write other");
+        fs::write(own.join("Avatar-20240101-000000.zip"), "old").expect("write old");
+        let namesake = root.join("Clients/Avatar");
+        fs::create_dir_all(&namesake).expect("create namesake");
+        fs::write(namesake.join("README.txt"), "client").expect("write namesake");
+
+        let created = create_backup(&project, &backups)
+            .expect("create backup")
+            .entry;
+        let namesake_backup = create_backup(&namesake, &backups)
+            .expect("backup namesake")
+            .entry;
+        let listed = list_backups(&project, &backups).expect("list backups");
+        let namesake_listed = list_backups(&namesake, &backups).expect("list namesake");
+        let trailing_slash = project_backup_dir(&root.join("Avatar/"), &backups);
+        let restored = restore_backup(&created.path, &root.join("Restored")).expect("restore");
+        let scene = fs::read_to_string(restored.join("Assets/Scenes/Main.unity"));
+        let kept_nested = restored.join("Assets/Library").is_dir();
+        let excluded = EXCLUDED_DIRS
+            .iter()
+            .filter(|dir| restored.join(dir).exists())
+            .count();
+        let again = restore_backup(&created.path, &restored);
+
+        assert!(created.size > 0);
+        assert_eq!(created.path.parent(), Some(own.as_path()));
+        assert_ne!(namesake_backup.path.parent(), Some(own.as_path()));
+        assert_eq!(namesake_listed, vec![namesake_backup]);
+        assert_eq!(trailing_slash.ok(), Some(own.clone()));
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0], created);
+        assert_eq!(listed[1].file_name(), "Avatar-20240101-000000.zip");
+        assert_eq!(listed[1].size, 3);
+        assert_eq!(scene.ok().as_deref(), Some("scene"));
+        assert!(kept_nested);
+        assert_eq!(excluded, 0);
+        assert!(again.is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follows_links_and_leaves_out_a_backup_folder_inside_the_project() {
+        use std::os::unix::fs::symlink;
+
+        let temp = TempDir::new("backup-links");
+        let root = temp.path().to_path_buf();
+        let project = root.join("World");
+        let shared = root.join("Shared/com.example.tool");
+        fs::create_dir_all(project.join("Packages")).expect("create packages");
+        fs::create_dir_all(&shared).expect("create shared package");
+        fs::write(shared.join("package.json"), "{}").expect("write package");
+        fs::write(root.join("notes.txt"), "notes").expect("write notes");
+        symlink(&shared, project.join("Packages/com.example.tool")).expect("link package");
+        symlink(root.join("notes.txt"), project.join("notes.txt")).expect("link notes");
+        symlink(root.join("missing"), project.join("broken")).expect("link missing");
+        symlink(&project, project.join("Packages/loop")).expect("link loop");
+        let backups = project.join("Backups");
+        fs::create_dir_all(&backups).expect("create backups");
+        fs::write(backups.join("older.zip"), "old").expect("write older backup");
+
+        let created = create_backup(&project, &backups).expect("create backup");
+        let archive = ZipArchive::new(File::open(&created.entry.path).expect("open backup"))
+            .expect("read backup");
+        let names = archive.file_names().collect::<Vec<_>>();
+
+        assert!(names.contains(&"Packages/com.example.tool/package.json"));
+        assert!(names.contains(&"notes.txt"));
+        assert!(!names.iter().any(|n| n.starts_with("Backups")));
+        assert_eq!(
+            created.skipped,
+            vec![project.join("Packages/loop"), project.join("broken")]
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use crate::app::state::{AppConfig, AvailablePackage, ManifestSummary, PackageInfo};
+use crate::app::state::{
+    AppConfig, AvailablePackage, LockedPackage, ManifestSummary, PackageInfo, PackageSource,
+    PackageVersion, ProjectKind,
+};
 use anyhow::{Context, Result};
 use serde_json::Value;
 use std::env;
@@ -18,12 +21,23 @@ pub fn cache_dir_path() -> Result<PathBuf> {
     Ok(home_dir()?.join(".cache").join(APP_NAME))
 }
 
+pub fn default_backup_dir() -> Result<PathBuf> {
+    Ok(home_dir()?
+        .join(".local")
+        .join("share")
+        .join(APP_NAME)
+        .join("backups"))
+}
+
 pub fn load_config() -> Result<AppConfig> {
-    let path = config_file_path()?;
+    load_config_from(&config_file_path()?)
+}
+
+pub fn load_config_from(path: &Path) -> Result<AppConfig> {
     if !path.exists() {
         return Ok(AppConfig::default());
     }
-    let raw = fs::read_to_string(&path)
+    let raw = fs::read_to_string(path)
         .with_context(|| format!("failed to read config file: {}", path.display()))?;
     let config: AppConfig = serde_json::from_str(&raw)
         .with_context(|| format!("failed to parse config file: {}", path.display()))?;
@@ -31,29 +45,56 @@ pub fn load_config() -> Result<AppConfig> {
 }
 
 pub fn save_config(config: &AppConfig) -> Result<()> {
-    let path = config_file_path()?;
+    save_config_to(&config_file_path()?, config)
+}
+
+pub fn save_config_to(path: &Path, config: &AppConfig) -> Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("failed to create config directory: {}", parent.display()))?;
     }
     let raw = serde_json::to_string_pretty(config).context("failed to serialize config")?;
-    fs::write(&path, raw).with_context(|| format!("failed to write config: {}", path.display()))?;
+    fs::write(path, raw).with_context(|| format!("failed to write config: {}", path.display()))?;
     Ok(())
 }
 
+pub fn classify_project(dir: &Path) -> Option<ProjectKind> {
+    if dir.join("Packages").join("vpm-manifest.json").is_file() {
+        return Some(ProjectKind::Vpm);
+    }
+    if !dir
+        .join("ProjectSettings")
+        .join("ProjectVersion.txt")
+        .is_file()
+    {
+        return None;
+    }
+    if dir.join("Assets").join("VRCSDK").is_dir() {
+        Some(ProjectKind::LegacySdk3)
+    } else {
+        Some(ProjectKind::Unity)
+    }
+}
+
 pub fn read_manifest(project_path: &Path) -> Result<ManifestSummary> {
     let manifest_path = project_path.join("Packages").join("vpm-manifest.json");
+    let kind = classify_project(project_path);
     if !manifest_path.exists() {
+        let message = match kind {
+            Some(ProjectKind::LegacySdk3) => {
+                "Legacy SDK3 project (Assets/VRCSDK); migrate it to VPM with [M]".to_string()
+            }
+            Some(_) => "Plain Unity project without VPM packages".to_string(),
+            None => {
+                "Not a Unity project: ProjectSettings/ProjectVersion.txt is missing".to_string()
+            }
+        };
         return Ok(ManifestSummary {
             exists: false,
+            kind,
             packages: Vec::new(),
-            message: Some(format!(
-                "{} is missing",
-                manifest_path
-                    .strip_prefix(project_path)
-                    .unwrap_or(&manifest_path)
-                    .display()
-            )),
+            locked: Vec::new(),
+            message: Some(message),
         });
     }
 
@@ -77,43 +118,93 @@ pub fn read_manifest(project_path: &Path) -> Result<ManifestSummary> {
         packages.sort_by(|a, b| a.name.cmp(&b.name));
     }
 
+    let mut locked = Vec::new();
+    if let Some(entries) = value.get("locked").and_then(|v| v.as_object()) {
+        for (name, entry) in entries {
+            let dependencies = entry
+                .get("dependencies")
+                .and_then(|v| v.as_object())
+                .map(|deps| {
+                    deps.iter()
+                        .map(|(dep, range)| {
+                            (
+                                dep.clone(),
+                                range
+                                    .as_str()
+                                    .map(|v| v.to_string())
+                                    .unwrap_or_else(|| range.to_string()),
+                            )
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            locked.push(LockedPackage {
+                name: name.clone(),
+                version: entry
+                    .get("version")
+                    .and_then(|v| v.as_str())
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| entry.to_string()),
+                dependencies,
+            });
+        }
+        locked.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
     Ok(ManifestSummary {
         exists: true,
+        kind,
         packages,
+        locked,
         message: None,
     })
 }
 
-pub fn scan_projects_one_level(root: &Path) -> Result<Vec<PathBuf>> {
-    let mut found = Vec::new();
-    for entry in fs::read_dir(root).with_context(|| format!("failed to read {}", root.display()))? {
-        let entry = entry.with_context(|| format!("failed to read entry in {}", root.display()))?;
-        let path = entry.path();
-        if !path.is_dir() {
-            continue;
-        }
-        let manifest = path.join("Packages").join("vpm-manifest.json");
-        if manifest.exists() {
-            found.push(path);
-        }
-    }
-    found.sort();
-    Ok(found)
+pub fn export_logs(dir: &Path, lines: &[String]) -> Result<PathBuf> {
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let path = dir.join(format!(
+        "logs-{}.log",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    let mut raw = lines.join("\n");
+    raw.push('\n');
+    fs::write(&path, raw).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(path)
 }
 
-pub fn load_available_packages_from_vcc_cache() -> Result<Vec<AvailablePackage>> {
-    let repos_dir = home_dir()?
+const OFFICIAL_REPO_ID: &str = "com.vrchat.repos.official";
+const CURATED_REPO_ID: &str = "com.vrchat.repos.curated";
+
+pub fn vcc_data_dir() -> Result<PathBuf> {
+    Ok(home_dir()?
         .join(".local")
         .join("share")
-        .join("VRChatCreatorCompanion")
-        .join("Repos");
+        .join("VRChatCreatorCompanion"))
+}
+
+pub fn load_available_packages_from_vcc_cache(
+    repo_priority: &[String],
+    disabled_repos: &[String],
+) -> Result<Vec<AvailablePackage>> {
+    load_available_packages_from_dir(&vcc_repos_dir()?, repo_priority, disabled_repos)
+}
+
+pub fn vcc_repos_dir() -> Result<PathBuf> {
+    Ok(vcc_data_dir()?.join("Repos"))
+}
+
+pub fn load_available_packages_from_dir(
+    repos_dir: &Path,
+    repo_priority: &[String],
+    disabled_repos: &[String],
+) -> Result<Vec<AvailablePackage>> {
     if !repos_dir.exists() {
         return Ok(Vec::new());
     }
 
     let mut newest_by_repo_id: std::collections::HashMap<String, (std::time::SystemTime, PathBuf)> =
         std::collections::HashMap::new();
-    for entry in fs::read_dir(&repos_dir)
+    for entry in fs::read_dir(repos_dir)
         .with_context(|| format!("failed to read {}", repos_dir.display()))?
     {
         let entry = entry?;
@@ -148,7 +239,13 @@ pub fn load_available_packages_from_vcc_cache() -> Result<Vec<AvailablePackage>>
         }
     }
 
-    let mut packages_by_id: std::collections::HashMap<String, AvailablePackage> =
+    let user_repos = repos_dir
+        .parent()
+        .and_then(|dir| {
+            crate::services::repos::load_user_repo_refs(&dir.join("settings.json")).ok()
+        })
+        .unwrap_or_default();
+    let mut packages_by_id: std::collections::HashMap<String, Vec<AvailablePackage>> =
         std::collections::HashMap::new();
     for (_repo_id_key, (_mtime, path)) in newest_by_repo_id {
         let raw = fs::read_to_string(&path)
@@ -159,10 +256,11 @@ pub fn load_available_packages_from_vcc_cache() -> Result<Vec<AvailablePackage>>
             Some(v) => v,
             None => continue,
         };
-        let repo_id = repo_obj
-            .get("id")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown.repo");
+        let repo_id = repo_key(&path, repo_obj, &user_repos);
+        let repo_id = repo_id.as_str();
+        if disabled_repos.iter().any(|id| id == repo_id) {
+            continue;
+        }
 
         let packages = match repo_obj.get("packages").and_then(|v| v.as_object()) {
             Some(v) => v,
@@ -180,41 +278,232 @@ pub fn load_available_packages_from_vcc_cache() -> Result<Vec<AvailablePackage>>
             if versions.is_empty() {
                 continue;
             }
-            let latest_version = versions
-                .keys()
-                .max()
-                .cloned()
+            let mut version_list = versions.keys().cloned().collect::<Vec<_>>();
+            crate::semver::sort_versions_desc(&mut version_list);
+            let version_details = version_list
+                .iter()
+                .map(|version| package_version_from_json(repo_id, version, &versions[version]))
+                .collect::<Vec<_>>();
+            let latest_stable = version_list
+                .iter()
+                .find(|v| !crate::semver::is_prerelease(v))
+                .cloned();
+            let latest_prerelease = version_list
+                .first()
+                .filter(|v| crate::semver::is_prerelease(v))
+                .cloned();
+            let latest_version = latest_stable
+                .or_else(|| latest_prerelease.clone())
                 .unwrap_or_else(|| "unknown".to_string());
             let display_name = versions
-                .values()
-                .find_map(|v| v.get("displayName").and_then(|n| n.as_str()))
+                .get(&latest_version)
+                .and_then(|v| v.get("displayName").and_then(|n| n.as_str()))
+                .or_else(|| {
+                    versions
+                        .values()
+                        .find_map(|v| v.get("displayName").and_then(|n| n.as_str()))
+                })
                 .unwrap_or(pkg_id)
                 .to_string();
 
             packages_by_id
                 .entry(pkg_id.clone())
-                .or_insert(AvailablePackage {
+                .or_default()
+                .push(AvailablePackage {
                     id: pkg_id.clone(),
                     display_name,
                     latest_version,
+                    latest_prerelease,
+                    versions: version_details,
                     repo_id: repo_id.to_string(),
+                    sources: Vec::new(),
                 });
         }
     }
 
-    let mut packages = packages_by_id.into_values().collect::<Vec<_>>();
+    let mut packages = packages_by_id
+        .into_values()
+        .filter_map(|mut copies| {
+            copies.sort_by_key(|p| repo_rank(&p.repo_id, repo_priority));
+            let sources = copies
+                .iter()
+                .map(|p| PackageSource {
+                    repo_id: p.repo_id.clone(),
+                    latest_version: p.latest_version.clone(),
+                    versions: p.versions.clone(),
+                })
+                .collect::<Vec<_>>();
+            let mut package = copies.into_iter().next()?;
+            merge_source_versions(&mut package, &sources);
+            package.sources = sources;
+            Some(package)
+        })
+        .collect::<Vec<_>>();
     packages.sort_by(|a, b| a.id.cmp(&b.id));
     Ok(packages)
 }
 
+// Every enabled repo contributes its versions; when two repos publish the same
+// version, the higher-priority copy wins.
+fn merge_source_versions(package: &mut AvailablePackage, sources: &[PackageSource]) {
+    let mut versions: Vec<PackageVersion> = Vec::new();
+    for version in sources.iter().flat_map(|s| &s.versions) {
+        if !versions.iter().any(|v| v.version == version.version) {
+            versions.push(version.clone());
+        }
+    }
+    versions.sort_by(|a, b| crate::semver::compare_versions(&b.version, &a.version));
+    let latest_stable = versions
+        .iter()
+        .find(|v| !crate::semver::is_prerelease(&v.version))
+        .map(|v| v.version.clone());
+    package.latest_prerelease = versions
+        .first()
+        .filter(|v| crate::semver::is_prerelease(&v.version))
+        .map(|v| v.version.clone());
+    if let Some(latest) = latest_stable.or_else(|| package.latest_prerelease.clone()) {
+        package.latest_version = latest;
+    }
+    package.versions = versions;
+}
+
+// The Repositories screen disables and ranks user repos by `RepoEntry::key`, so
+// their caches are keyed the same way: settings id, cache id, then URL.
+fn repo_key(
+    path: &Path,
+    repo_obj: &serde_json::Map<String, Value>,
+    user_repos: &[crate::services::repos::UserRepoRef],
+) -> String {
+    let field = |key: &str| repo_obj.get(key).and_then(|v| v.as_str());
+    match user_repos.iter().find(|r| r.local_path == path) {
+        Some(user_repo) => user_repo
+            .id
+            .as_deref()
+            .or(field("id"))
+            .unwrap_or(&user_repo.url)
+            .to_string(),
+        None => field("id")
+            .or(field("url"))
+            .unwrap_or("unknown.repo")
+            .to_string(),
+    }
+}
+
+fn repo_rank(repo_id: &str, repo_priority: &[String]) -> (usize, usize, String) {
+    let configured = repo_priority
+        .iter()
+        .position(|id| id == repo_id)
+        .unwrap_or(usize::MAX);
+    let builtin = match repo_id {
+        OFFICIAL_REPO_ID => 0,
+        CURATED_REPO_ID => 1,
+        _ => 2,
+    };
+    (configured, builtin, repo_id.to_string())
+}
+
+fn package_version_from_json(repo_id: &str, version: &str, value: &Value) -> PackageVersion {
+    let string_field = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+    };
+    let dependencies = value
+        .get("vpmDependencies")
+        .and_then(|v| v.as_object())
+        .map(|deps| {
+            deps.iter()
+                .map(|(id, range)| {
+                    (
+                        id.clone(),
+                        range
+                            .as_str()
+                            .map(|v| v.to_string())
+                            .unwrap_or_else(|| range.to_string()),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let author = match value.get("author") {
+        Some(Value::String(name)) => Some(name.clone()),
+        Some(Value::Object(obj)) => {
+            let name = obj.get("name").and_then(|v| v.as_str());
+            let email = obj.get("email").and_then(|v| v.as_str());
+            match (name, email) {
+                (Some(name), Some(email)) => Some(format!("{name} <{email}>")),
+                (Some(name), None) => Some(name.to_string()),
+                (None, Some(email)) => Some(email.to_string()),
+                (None, None) => None,
+            }
+        }
+        _ => None,
+    };
+    let legacy_folders = value
+        .get("legacyFolders")
+        .and_then(|v| v.as_object())
+        .map(|folders| folders.keys().cloned().collect())
+        .unwrap_or_default();
+    let keywords = value
+        .get("keywords")
+        .and_then(|v| v.as_array())
+        .map(|words| {
+            words
+                .iter()
+                .filter_map(|w| w.as_str().map(|w| w.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    PackageVersion {
+        version: version.to_string(),
+        repo_id: repo_id.to_string(),
+        unity: string_field("unity"),
+        vrchat_version: string_field("vrchatVersion"),
+        dependencies,
+        description: string_field("description"),
+        author,
+        changelog_url: string_field("changelogUrl"),
+        documentation_url: string_field("documentationUrl"),
+        legacy_folders,
+        keywords,
+    }
+}
+
 fn home_dir() -> Result<PathBuf> {
     let home = env::var("HOME").context("HOME is not set")?;
     Ok(PathBuf::from(home))
 }
 
+/// Expands a leading `~` to the home directory, as a shell would.
+pub fn expand_home(path: &Path) -> PathBuf {
+    match home_dir() {
+        Ok(home) => expand_home_in(path, &home),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Expands `~` and resolves the path to its canonical form so the same folder
+/// typed two ways compares equal. Paths that cannot be resolved (offline
+/// drives, folders not created yet) keep their expanded form.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let expanded = expand_home(path);
+    fs::canonicalize(&expanded).unwrap_or(expanded)
+}
+
+fn expand_home_in(path: &Path, home: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(rest) => home.join(rest),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::harness::TempDir;
 
     #[test]
     fn config_roundtrip_serialization() {
@@ -224,7 +513,9 @@ mod tests {
                 display_name: "sample".to_string(),
                 tags: vec!["vrchat".to_string(), "test".to_string()],
                 last_opened: Some("2026-02-16T00:00:00Z".to_string()),
+                pinned: true,
             }],
+            ..AppConfig::default()
         };
 
         let raw = serde_json::to_string(&input).expect("serialize config");
@@ -233,5 +524,153 @@ mod tests {
         assert_eq!(output.projects.len(), 1);
         assert_eq!(output.projects[0].display_name, "sample");
         assert_eq!(output.projects[0].tags.len(), 2);
+        assert!(output.projects[0].pinned);
+    }
+
+    #[test]
+    fn catalog_keeps_every_repo_copy_in_priority_order() {
+        let temp = TempDir::new("repos");
+        let repos_dir = temp.path().to_path_buf();
+        let repo_json = |id: &str, version: &str| {
+            format!(
+                r#"{{"repo": {{"id": "{id}", "packages": {{
+                    "com.example.tool": {{"versions": {{
+                        "{version}": {{"displayName": "Tool {id}"}}
+                    }}}}
+                }}}}}}"#
+            )
+        };
+        fs::write(
+            repos_dir.join("com.example.mirror-1.json"),
+            repo_json("com.example.mirror", "1.1.0"),
+        )
+        .expect("write mirror repo");
+        fs::write(
+            repos_dir.join("com.vrchat.repos.curated-1.json"),
+            repo_json(CURATED_REPO_ID, "1.0.0"),
+        )
+        .expect("write curated repo");
+
+        let by_default = load_available_packages_from_dir(&repos_dir, &[], &[]).expect("load");
+        let by_priority =
+            load_available_packages_from_dir(&repos_dir, &["com.example.mirror".to_string()], &[])
+                .expect("load with priority");
+        let without_mirror =
+            load_available_packages_from_dir(&repos_dir, &[], &["com.example.mirror".to_string()])
+                .expect("load without mirror");
+
+        assert_eq!(by_default.len(), 1);
+        assert_eq!(by_default[0].repo_id, CURATED_REPO_ID);
+        assert_eq!(by_default[0].sources.len(), 2);
+        assert_eq!(by_default[0].latest_version, "1.1.0");
+        assert_eq!(
+            by_default[0]
+                .versions
+                .iter()
+                .map(|v| (v.version.as_str(), v.repo_id.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("1.1.0", "com.example.mirror"), ("1.0.0", CURATED_REPO_ID)]
+        );
+        assert_eq!(by_priority[0].repo_id, "com.example.mirror");
+        assert_eq!(by_priority[0].latest_version, "1.1.0");
+        assert_eq!(by_priority[0].sources[1].repo_id, CURATED_REPO_ID);
+        assert_eq!(without_mirror[0].sources.len(), 1);
+        assert_eq!(without_mirror[0].latest_version, "1.0.0");
+    }
+
+    #[test]
+    fn expands_a_leading_tilde_only() {
+        let home = Path::new("/home/creator");
+
+        assert_eq!(
+            expand_home_in(Path::new("~/bin/vpm"), home),
+            PathBuf::from("/home/creator/bin/vpm")
+        );
+        assert_eq!(expand_home_in(Path::new("~"), home), home);
+        assert_eq!(
+            expand_home_in(Path::new("~other/vpm"), home),
+            PathBuf::from("~other/vpm")
+        );
+        assert_eq!(
+            expand_home_in(Path::new("/opt/~/vpm"), home),
+            PathBuf::from("/opt/~/vpm")
+        );
+    }
+
+    #[test]
+    fn read_manifest_includes_locked_section() {
+        let temp = TempDir::new("manifest");
+        let root = temp.path().to_path_buf();
+        let packages_dir = root.join("Packages");
+        fs::create_dir_all(&packages_dir).expect("create packages dir");
+        fs::write(
+            packages_dir.join("vpm-manifest.json"),
+            r#"{
+                "dependencies": {
+                    "com.vrchat.avatars": { "version": "3.5.0" },
+                    "nadena.dev.modular-avatar": { "version": "1.9.0" }
+                },
+                "locked": {
+                    "com.vrchat.avatars": {
+                        "version": "3.5.2",
+                        "dependencies": { "com.vrchat.base": "3.5.2" }
+                    },
+                    "com.vrchat.base": { "version": "3.5.2", "dependencies": {} },
+                    "nadena.dev.modular-avatar": { "version": "1.8.2", "dependencies": {} }
+                }
+            }"#,
+        )
+        .expect("write manifest");
+
+        let summary = read_manifest(&root).expect("read manifest");
+
+        assert_eq!(summary.packages.len(), 2);
+        assert_eq!(summary.locked.len(), 3);
+        assert_eq!(
+            summary.locked[0].dependencies.get("com.vrchat.base"),
+            Some(&"3.5.2".to_string())
+        );
+
+        let installed = summary.installed_packages();
+        let find = |name: &str| {
+            installed
+                .iter()
+                .find(|p| p.name == name)
+                .expect("installed package")
+        };
+        assert_eq!(installed.len(), 3);
+        assert!(find("com.vrchat.avatars").is_direct());
+        assert!(!find("com.vrchat.avatars").has_version_mismatch());
+        assert!(find("nadena.dev.modular-avatar").has_version_mismatch());
+        assert!(!find("com.vrchat.base").is_direct());
+        assert_eq!(
+            find("com.vrchat.base").required_by,
+            vec!["com.vrchat.avatars".to_string()]
+        );
+    }
+
+    #[test]
+    fn classifies_legacy_projects_from_their_folders() {
+        let temp = TempDir::new("classify");
+        let root = temp.path().to_path_buf();
+        let project = root.join("Legacy");
+        for dir in ["ProjectSettings", "Assets/VRCSDK"] {
+            fs::create_dir_all(project.join(dir)).expect("create dir");
+        }
+        fs::write(
+            project.join("ProjectSettings/ProjectVersion.txt"),
+            "m_EditorVersion: 2019.4.31f1\n",
+        )
+        .expect("write version");
+        fs::write(project.join("Assets/VRCSDK/version.txt"), "3").expect("write sdk");
+
+        let kind = classify_project(&project);
+        let summary = read_manifest(&project).expect("read manifest");
+        let plain = classify_project(&root);
+
+        assert_eq!(kind, Some(ProjectKind::LegacySdk3));
+        assert!(!summary.exists);
+        assert_eq!(summary.kind, Some(ProjectKind::LegacySdk3));
+        assert_eq!(plain, None);
     }
 }
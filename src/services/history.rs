@@ -0,0 +1,241 @@
+use crate::app::state::TaskHistoryEntry;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+const MAX_HISTORY_ENTRIES: usize = 500;
+
+pub fn history_dir() -> Result<PathBuf> {
+    Ok(crate::services::fs::cache_dir_path()?.join("tasks"))
+}
+
+pub fn log_file_path(dir: &Path, session: &str, task_id: u64) -> PathBuf {
+    dir.join("logs").join(format!("{session}-{task_id}.log"))
+}
+
+/// Timestamp plus process id, so two instances started in the same second
+/// still get separate sessions.
+pub fn new_session_id() -> String {
+    format!(
+        "{}-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        std::process::id()
+    )
+}
+
+pub enum HistoryWrite {
+    Log {
+        session: String,
+        task_id: u64,
+        line: String,
+    },
+    Entry(TaskHistoryEntry),
+}
+
+/// Keeps each running task's log open behind a buffer; callers flush once
+/// per batch of writes instead of reopening the file for every line.
+pub struct HistoryWriter {
+    dir: PathBuf,
+    logs: HashMap<(String, u64), BufWriter<File>>,
+}
+
+impl HistoryWriter {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            logs: HashMap::new(),
+        }
+    }
+
+    pub fn write(&mut self, write: HistoryWrite) -> Result<()> {
+        match write {
+            HistoryWrite::Log {
+                session,
+                task_id,
+                line,
+            } => {
+                let path = log_file_path(&self.dir, &session, task_id);
+                let file = match self.logs.entry((session, task_id)) {
+                    std::collections::hash_map::Entry::Occupied(file) => file.into_mut(),
+                    std::collections::hash_map::Entry::Vacant(slot) => {
+                        slot.insert(BufWriter::new(open_append(&path)?))
+                    }
+                };
+                writeln!(file, "{line}")
+                    .with_context(|| format!("failed to write {}", path.display()))
+            }
+            HistoryWrite::Entry(entry) => {
+                if entry.finished_at.is_some() {
+                    if let Some(mut file) =
+                        self.logs.remove(&(entry.session.clone(), entry.task_id))
+                    {
+                        file.flush().context("failed to flush task log")?;
+                    }
+                }
+                append_entry(&self.dir, &entry)
+            }
+        }
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        for file in self.logs.values_mut() {
+            file.flush().context("failed to flush task log")?;
+        }
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open {}", path.display()))
+}
+
+pub fn append_entry(dir: &Path, entry: &TaskHistoryEntry) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let path = dir.join("history.jsonl");
+    let raw = serde_json::to_string(entry).context("failed to serialize task history")?;
+    let mut file = open_append(&path)?;
+    writeln!(file, "{raw}").with_context(|| format!("failed to write {}", path.display()))
+}
+
+pub fn load_history(dir: &Path) -> Result<Vec<TaskHistoryEntry>> {
+    let path = dir.join("history.jsonl");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw =
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    // A task is recorded when it starts and again when it finishes; the
+    // later record replaces the earlier one in place.
+    let mut entries: Vec<TaskHistoryEntry> = Vec::new();
+    for entry in raw
+        .lines()
+        .filter_map(|line| serde_json::from_str::<TaskHistoryEntry>(line).ok())
+    {
+        match entries
+            .iter_mut()
+            .find(|e| e.session == entry.session && e.task_id == entry.task_id)
+        {
+            Some(existing) => *existing = entry,
+            None => entries.push(entry),
+        }
+    }
+
+    if entries.len() > MAX_HISTORY_ENTRIES {
+        let dropped = entries.drain(..entries.len() - MAX_HISTORY_ENTRIES);
+        for entry in dropped {
+            let _ = fs::remove_file(log_file_path(dir, &entry.session, entry.task_id));
+        }
+        let mut kept = String::new();
+        for entry in &entries {
+            kept.push_str(&serde_json::to_string(entry).context("failed to serialize history")?);
+            kept.push('\n');
+        }
+        let tmp_path = path.with_extension("jsonl.tmp");
+        fs::write(&tmp_path, kept)
+            .with_context(|| format!("failed to write {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &path)
+            .with_context(|| format!("failed to replace {}", path.display()))?;
+    }
+    Ok(entries)
+}
+
+pub fn read_task_log(dir: &Path, session: &str, task_id: u64) -> Result<Vec<String>> {
+    let path = log_file_path(dir, session, task_id);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw =
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(raw.lines().map(|l| l.to_string()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::harness::TempDir;
+    use crate::app::state::TaskState;
+    use chrono::Local;
+
+    fn entry(session: &str, task_id: u64) -> TaskHistoryEntry {
+        TaskHistoryEntry {
+            session: session.to_string(),
+            task_id,
+            label: "vpm resolve project".to_string(),
+            command_line: "vpm resolve project /p".to_string(),
+            args: vec![
+                "resolve".to_string(),
+                "project".to_string(),
+                "/p".to_string(),
+            ],
+            project_path: Some(PathBuf::from("/p")),
+            state: TaskState::Failed,
+            exit_code: Some(1),
+            error: None,
+            queued_at: Local::now(),
+            started_at: Some(Local::now()),
+            finished_at: Some(Local::now()),
+        }
+    }
+
+    #[test]
+    fn keeps_entries_and_logs_and_rotates_old_ones() {
+        let temp = TempDir::new("history");
+        let dir = temp.path().to_path_buf();
+
+        let mut writer = HistoryWriter::new(dir.clone());
+        let started = TaskHistoryEntry {
+            state: TaskState::Running,
+            exit_code: None,
+            finished_at: None,
+            ..entry("s1", 1)
+        };
+        writer
+            .write(HistoryWrite::Entry(started))
+            .expect("record start");
+        writer
+            .write(HistoryWrite::Log {
+                session: "s1".to_string(),
+                task_id: 1,
+                line: "[1:err] resolve failed".to_string(),
+            })
+            .expect("append log");
+        writer.flush().expect("flush logs");
+        let log_while_running = read_task_log(&dir, "s1", 1).expect("read log");
+        writer
+            .write(HistoryWrite::Entry(entry("s1", 1)))
+            .expect("record finish");
+        let first_load = load_history(&dir).expect("load history");
+        for task_id in 0..MAX_HISTORY_ENTRIES as u64 {
+            append_entry(&dir, &entry("s2", task_id)).expect("append entry");
+        }
+
+        assert_eq!(
+            log_while_running,
+            vec!["[1:err] resolve failed".to_string()]
+        );
+        assert!(writer.logs.is_empty());
+        assert_eq!(
+            first_load.iter().map(|e| e.state).collect::<Vec<_>>(),
+            vec![TaskState::Failed]
+        );
+
+        let loaded = load_history(&dir).expect("load history");
+        let reloaded = load_history(&dir).expect("reload history");
+        let log_after_rotation = read_task_log(&dir, "s1", 1).expect("read log");
+
+        assert_eq!(loaded.len(), MAX_HISTORY_ENTRIES);
+        assert!(loaded.iter().all(|e| e.session == "s2"));
+        assert_eq!(reloaded, loaded);
+        assert!(log_after_rotation.is_empty());
+    }
+}
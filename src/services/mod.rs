@@ -1,2 +1,7 @@
+pub mod backup;
 pub mod fs;
+pub mod history;
+pub mod repos;
+pub mod scan;
+pub mod unity;
 pub mod vpm;
@@ -0,0 +1,290 @@
+use crate::app::state::RepoEntry;
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub fn vcc_settings_path() -> Result<PathBuf> {
+    Ok(crate::services::fs::vcc_data_dir()?.join("settings.json"))
+}
+
+pub fn load_user_repos() -> Result<Vec<RepoEntry>> {
+    load_user_repos_from(&vcc_settings_path()?)
+}
+
+pub fn save_user_repos(repos: &[RepoEntry], removed: &[String]) -> Result<()> {
+    save_user_repos_to(&vcc_settings_path()?, repos, removed)
+}
+
+/// A user repo as settings.json names it, found again by its cache file.
+pub struct UserRepoRef {
+    pub local_path: PathBuf,
+    pub id: Option<String>,
+    pub url: String,
+}
+
+pub fn load_user_repo_refs(settings_path: &Path) -> Result<Vec<UserRepoRef>> {
+    let settings = read_settings(settings_path)?;
+    let Some(user_repos) = settings.get("userRepos").and_then(|v| v.as_array()) else {
+        return Ok(Vec::new());
+    };
+    Ok(user_repos
+        .iter()
+        .filter_map(|repo| {
+            let string_field = |key: &str| {
+                repo.get(key)
+                    .and_then(|v| v.as_str())
+                    .map(|v| v.to_string())
+            };
+            Some(UserRepoRef {
+                local_path: PathBuf::from(string_field("localPath")?),
+                id: string_field("id"),
+                url: string_field("url")?,
+            })
+        })
+        .collect())
+}
+
+fn read_settings(path: &Path) -> Result<Value> {
+    if !path.exists() {
+        return Ok(Value::Object(Map::new()));
+    }
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&raw).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+pub fn load_user_repos_from(settings_path: &Path) -> Result<Vec<RepoEntry>> {
+    let settings = read_settings(settings_path)?;
+    let Some(user_repos) = settings.get("userRepos").and_then(|v| v.as_array()) else {
+        return Ok(Vec::new());
+    };
+
+    let mut repos = Vec::new();
+    for repo in user_repos {
+        let string_field = |key: &str| {
+            repo.get(key)
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        };
+        let Some(url) = string_field("url") else {
+            continue;
+        };
+        let local_path = string_field("localPath").map(PathBuf::from);
+        let headers = repo
+            .get("headers")
+            .and_then(|v| v.as_object())
+            .map(|headers| {
+                headers
+                    .iter()
+                    .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .unwrap_or_default();
+
+        let cache = local_path.as_deref().and_then(read_repo_cache);
+        let id = string_field("id").or_else(|| cache.as_ref().and_then(|c| c.id.clone()));
+        let name = string_field("name")
+            .or_else(|| cache.as_ref().and_then(|c| c.name.clone()))
+            .unwrap_or_else(|| url.clone());
+
+        repos.push(RepoEntry {
+            id,
+            name,
+            url,
+            local_path,
+            headers,
+            package_count: cache.as_ref().map(|c| c.package_count),
+            cache_age_secs: cache.and_then(|c| c.age_secs),
+        });
+    }
+    Ok(repos)
+}
+
+struct RepoCache {
+    id: Option<String>,
+    name: Option<String>,
+    package_count: usize,
+    age_secs: Option<u64>,
+}
+
+fn read_repo_cache(path: &Path) -> Option<RepoCache> {
+    let raw = fs::read_to_string(path).ok()?;
+    let value: Value = serde_json::from_str(&raw).ok()?;
+    let repo = value.get("repo")?;
+    let age_secs = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .map(|age| age.as_secs());
+    Some(RepoCache {
+        id: repo
+            .get("id")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string()),
+        name: repo
+            .get("name")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string()),
+        package_count: repo
+            .get("packages")
+            .and_then(|v| v.as_object())
+            .map(|p| p.len())
+            .unwrap_or(0),
+        age_secs,
+    })
+}
+
+// Entries this list has never seen (added by `vpm add repo` since the list was
+// loaded) are kept after it; only the URLs in `removed` are dropped.
+fn save_user_repos_to(settings_path: &Path, repos: &[RepoEntry], removed: &[String]) -> Result<()> {
+    let mut settings = read_settings(settings_path)?;
+    let existing = settings
+        .get("userRepos")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut user_repos = repos
+        .iter()
+        .map(|repo| {
+            let mut entry = existing
+                .iter()
+                .find(|e| e.get("url").and_then(|v| v.as_str()) == Some(repo.url.as_str()))
+                .and_then(|e| e.as_object().cloned())
+                .unwrap_or_default();
+            entry.insert("url".to_string(), Value::String(repo.url.clone()));
+            entry.insert("name".to_string(), Value::String(repo.name.clone()));
+            if let Some(local_path) = &repo.local_path {
+                entry.insert(
+                    "localPath".to_string(),
+                    Value::String(local_path.to_string_lossy().to_string()),
+                );
+            }
+            entry.insert(
+                "headers".to_string(),
+                Value::Object(
+                    repo.headers
+                        .iter()
+                        .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+                        .collect(),
+                ),
+            );
+            Value::Object(entry)
+        })
+        .collect::<Vec<_>>();
+    user_repos.extend(
+        existing
+            .iter()
+            .filter(|e| {
+                e.get("url").and_then(|v| v.as_str()).is_some_and(|url| {
+                    !repos.iter().any(|r| r.url == url) && !removed.iter().any(|r| r == url)
+                })
+            })
+            .cloned(),
+    );
+
+    let Some(obj) = settings.as_object_mut() else {
+        anyhow::bail!("{} is not a JSON object", settings_path.display());
+    };
+    obj.insert("userRepos".to_string(), Value::Array(user_repos));
+
+    let raw = serde_json::to_string_pretty(&settings).context("failed to serialize settings")?;
+    let tmp_path = settings_path.with_extension("json.tmp");
+    fs::write(&tmp_path, raw).with_context(|| format!("failed to write {}", tmp_path.display()))?;
+    fs::rename(&tmp_path, settings_path)
+        .with_context(|| format!("failed to replace {}", settings_path.display()))?;
+
+    for entry in existing.iter().filter(|e| {
+        let url = e.get("url").and_then(|v| v.as_str());
+        removed.iter().any(|r| Some(r.as_str()) == url)
+    }) {
+        if let Some(local_path) = entry.get("localPath").and_then(|v| v.as_str()) {
+            remove_repo_cache(settings_path, Path::new(local_path))?;
+        }
+    }
+    Ok(())
+}
+
+// Only caches inside VCC's own Repos folder are ours to delete.
+fn remove_repo_cache(settings_path: &Path, cache: &Path) -> Result<()> {
+    let Some(repos_dir) = settings_path.parent().map(|dir| dir.join("Repos")) else {
+        return Ok(());
+    };
+    if cache.parent() != Some(repos_dir.as_path()) || !cache.exists() {
+        return Ok(());
+    }
+    fs::remove_file(cache).with_context(|| format!("failed to remove {}", cache.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::harness::TempDir;
+
+    #[test]
+    fn rewrites_user_repos_and_keeps_other_settings() {
+        let temp = TempDir::new("settings");
+        let dir = temp.path().to_path_buf();
+        let settings_path = dir.join("settings.json");
+        let repos_dir = dir.join("Repos");
+        fs::create_dir_all(&repos_dir).expect("create repos dir");
+        fs::write(repos_dir.join("a.json"), "{}").expect("write cache");
+        fs::write(
+            &settings_path,
+            format!(
+                r#"{{
+                    "pathToUnityHub": "/Applications/Unity Hub.app",
+                    "userRepos": [
+                        {{"url": "https://a.example/vpm.json", "name": "A", "localPath": "{}", "headers": {{}}}},
+                        {{"url": "https://b.example/vpm.json", "name": "B", "localPath": "/nowhere/b.json", "id": "com.example.b"}}
+                    ]
+                }}"#,
+                repos_dir.join("a.json").display()
+            ),
+        )
+        .expect("write settings");
+
+        let mut repos = load_user_repos_from(&settings_path).expect("load repos");
+        assert_eq!(repos.len(), 2);
+        // `vpm add repo` writes a third entry after the list was loaded.
+        let raw = fs::read_to_string(&settings_path).expect("read settings");
+        fs::write(
+            &settings_path,
+            raw.replace(
+                r#""id": "com.example.b"}"#,
+                r#""id": "com.example.b"}, {"url": "https://c.example/vpm.json", "name": "C"}"#,
+            ),
+        )
+        .expect("add entry");
+        assert_eq!(repos[1].id.as_deref(), Some("com.example.b"));
+
+        repos.swap(0, 1);
+        repos[0]
+            .headers
+            .insert("Authorization".to_string(), "Bearer x".to_string());
+        repos.truncate(1);
+        save_user_repos_to(
+            &settings_path,
+            &repos,
+            &["https://a.example/vpm.json".to_string()],
+        )
+        .expect("save repos");
+
+        let reloaded = load_user_repos_from(&settings_path).expect("reload repos");
+        let raw = fs::read_to_string(&settings_path).expect("read settings");
+
+        assert_eq!(reloaded.len(), 2);
+        assert_eq!(reloaded[0].url, "https://b.example/vpm.json");
+        assert_eq!(reloaded[1].url, "https://c.example/vpm.json");
+        assert_eq!(
+            reloaded[0].headers.get("Authorization").map(|v| v.as_str()),
+            Some("Bearer x")
+        );
+        assert!(raw.contains("pathToUnityHub"));
+        assert!(raw.contains("com.example.b"));
+        assert!(!repos_dir.join("a.json").exists());
+    }
+}
@@ -0,0 +1,149 @@
+use crate::app::state::{DiscoveredProject, ScanSettings};
+use crate::services::fs::classify_project;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanEvent {
+    Visited { visited: usize, dir: PathBuf },
+    Found(DiscoveredProject),
+}
+
+pub fn scan_projects(
+    root: &Path,
+    settings: &ScanSettings,
+    mut on_event: impl FnMut(ScanEvent),
+) -> Result<Vec<DiscoveredProject>> {
+    fs::read_dir(root).with_context(|| format!("failed to read {}", root.display()))?;
+
+    let mut found = Vec::new();
+    let mut visited = 0;
+    let mut stack = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = stack.pop() {
+        visited += 1;
+        on_event(ScanEvent::Visited {
+            visited,
+            dir: dir.clone(),
+        });
+
+        if let Some(kind) = classify_project(&dir) {
+            let project = DiscoveredProject { path: dir, kind };
+            on_event(ScanEvent::Found(project.clone()));
+            found.push(project);
+            continue;
+        }
+        if depth >= settings.max_depth {
+            continue;
+        }
+
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut children = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                !settings
+                    .ignore
+                    .iter()
+                    .any(|pattern| pattern_matches(pattern, &name))
+            })
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+        children.sort();
+        stack.extend(children.into_iter().rev().map(|child| (child, depth + 1)));
+    }
+    Ok(found)
+}
+
+fn pattern_matches(pattern: &str, name: &str) -> bool {
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skip| matches(rest, &name[skip..])),
+            Some(('?', rest)) => !name.is_empty() && matches(rest, &name[1..]),
+            Some((c, rest)) => name.first() == Some(c) && matches(rest, &name[1..]),
+        }
+    }
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    matches(&pattern, &name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::harness::TempDir;
+    use crate::app::state::ProjectKind;
+
+    fn touch(path: PathBuf) {
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dirs");
+        fs::write(path, "{}").expect("write file");
+    }
+
+    #[test]
+    fn finds_nested_legacy_and_plain_unity_projects_within_depth() {
+        let temp = TempDir::new("scan");
+        let root = temp.path().to_path_buf();
+        touch(root.join("Avatar/Packages/vpm-manifest.json"));
+        touch(root.join("Clients/Acme/World/Packages/vpm-manifest.json"));
+        touch(root.join("Clients/Acme/Old/ProjectSettings/ProjectVersion.txt"));
+        touch(root.join("Clients/Acme/Sdk3/ProjectSettings/ProjectVersion.txt"));
+        touch(root.join("Clients/Acme/Sdk3/Assets/VRCSDK/version.txt"));
+        touch(root.join("Avatar/Assets/Nested/Packages/vpm-manifest.json"));
+        touch(root.join("node_modules/pkg/Packages/vpm-manifest.json"));
+        touch(root.join("Backup-2024/Packages/vpm-manifest.json"));
+        touch(root.join("a/b/c/d/Deep/Packages/vpm-manifest.json"));
+
+        let settings = ScanSettings {
+            max_depth: 3,
+            ignore: vec!["node_modules".to_string(), "Backup-*".to_string()],
+        };
+        let mut events = Vec::new();
+        let found = scan_projects(&root, &settings, |event| events.push(event));
+        let found = found.expect("scan");
+
+        assert_eq!(
+            found,
+            vec![
+                DiscoveredProject {
+                    path: root.join("Avatar"),
+                    kind: ProjectKind::Vpm,
+                },
+                DiscoveredProject {
+                    path: root.join("Clients/Acme/Old"),
+                    kind: ProjectKind::Unity,
+                },
+                DiscoveredProject {
+                    path: root.join("Clients/Acme/Sdk3"),
+                    kind: ProjectKind::LegacySdk3,
+                },
+                DiscoveredProject {
+                    path: root.join("Clients/Acme/World"),
+                    kind: ProjectKind::Vpm,
+                },
+            ]
+        );
+        assert_eq!(
+            events
+                .iter()
+                .filter(|e| matches!(e, ScanEvent::Found(_)))
+                .count(),
+            4
+        );
+        assert!(matches!(
+            events.first(),
+            Some(ScanEvent::Visited { visited: 1, dir }) if dir == &root
+        ));
+    }
+
+    #[test]
+    fn ignore_patterns_support_wildcards() {
+        assert!(pattern_matches("Library", "Library"));
+        assert!(pattern_matches("*.bak", "Project.bak"));
+        assert!(pattern_matches("Temp?", "Temp1"));
+        assert!(!pattern_matches("Temp", "Temporary"));
+    }
+}
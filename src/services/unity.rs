@@ -0,0 +1,304 @@
+use crate::app::state::{UnityEditor, UnitySettings};
+use anyhow::{Context, Result};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+pub fn read_project_version(project_path: &Path) -> Option<String> {
+    let raw = fs::read_to_string(
+        project_path
+            .join("ProjectSettings")
+            .join("ProjectVersion.txt"),
+    )
+    .ok()?;
+    raw.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("m_EditorVersion:")
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    })
+}
+
+pub fn open_project(
+    project_path: &Path,
+    settings: &UnitySettings,
+    listed: &[UnityEditor],
+) -> Result<(String, PathBuf)> {
+    let version = read_project_version(project_path).with_context(|| {
+        format!(
+            "no editor version in {}",
+            project_path
+                .join("ProjectSettings")
+                .join("ProjectVersion.txt")
+                .display()
+        )
+    })?;
+    let editor = find_editor(&version, settings, listed)
+        .with_context(|| format!("Unity {version} is not installed"))?;
+    launch_detached(&editor, project_path)?;
+    Ok((version, editor))
+}
+
+pub fn find_editor(
+    version: &str,
+    settings: &UnitySettings,
+    listed: &[UnityEditor],
+) -> Option<PathBuf> {
+    let from_root = |root: &Path| editor_executable(&root.join(version));
+    settings
+        .editor_root
+        .as_deref()
+        .and_then(from_root)
+        .or_else(|| {
+            listed
+                .iter()
+                .filter(|e| e.version == version)
+                .find_map(|e| e.path.as_deref().and_then(listed_executable))
+        })
+        .or_else(|| hub_editor_roots().iter().find_map(|root| from_root(root)))
+}
+
+fn listed_executable(path: &Path) -> Option<PathBuf> {
+    if path.extension().is_some_and(|ext| ext == "app") {
+        let binary = path.join("Contents").join("MacOS").join("Unity");
+        return binary.is_file().then_some(binary);
+    }
+    path.is_file().then(|| path.to_path_buf())
+}
+
+fn editor_executable(install_dir: &Path) -> Option<PathBuf> {
+    [
+        install_dir.join("Editor").join("Unity.exe"),
+        install_dir
+            .join("Unity.app")
+            .join("Contents")
+            .join("MacOS")
+            .join("Unity"),
+        install_dir.join("Editor").join("Unity"),
+    ]
+    .into_iter()
+    .find(|path| path.is_file())
+}
+
+fn hub_editor_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    let hub_config = if cfg!(windows) {
+        env::var_os("APPDATA").map(|dir| PathBuf::from(dir).join("UnityHub"))
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join("Library/Application Support/UnityHub"))
+    } else {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/UnityHub"))
+    };
+    if let Some(raw) =
+        hub_config.and_then(|dir| fs::read_to_string(dir.join("secondaryInstallPath.json")).ok())
+    {
+        if let Ok(path) = serde_json::from_str::<String>(&raw) {
+            if !path.is_empty() {
+                roots.push(PathBuf::from(path));
+            }
+        }
+    }
+    if cfg!(windows) {
+        roots.push(PathBuf::from(r"C:\Program Files\Unity\Hub\Editor"));
+    } else if cfg!(target_os = "macos") {
+        roots.push(PathBuf::from("/Applications/Unity/Hub/Editor"));
+    } else if let Some(home) = env::var_os("HOME") {
+        roots.push(PathBuf::from(home).join("Unity/Hub/Editor"));
+    }
+    roots
+}
+
+fn launch_detached(editor: &Path, project_path: &Path) -> Result<()> {
+    let mut command = Command::new(editor);
+    command
+        .arg("-projectPath")
+        .arg(project_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const DETACHED_PROCESS: u32 = 0x0000_0008;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+        command.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+    }
+    let mut child = command
+        .spawn()
+        .with_context(|| format!("failed to start {}", editor.display()))?;
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+pub fn parse_installed_editors(lines: &[String]) -> Vec<UnityEditor> {
+    let mut editors = Vec::<UnityEditor>::new();
+    for line in lines {
+        let line = strip_log_prefix(line);
+        let Some(version) = line
+            .split(|c: char| c.is_whitespace() || c == '|')
+            .find(|token| is_unity_version(token))
+        else {
+            continue;
+        };
+        let start = line.find(version).unwrap_or_default();
+        if editors.iter().any(|e| e.version == version) {
+            continue;
+        }
+        let rest = line[start + version.len()..]
+            .trim_start_matches(|c: char| c.is_whitespace() || c == '|' || c == ':');
+        let rest = rest.strip_prefix("at ").unwrap_or(rest).trim();
+        editors.push(UnityEditor {
+            version: version.to_string(),
+            path: (!rest.is_empty()).then(|| PathBuf::from(rest)),
+        });
+    }
+    editors
+}
+
+fn strip_log_prefix(line: &str) -> &str {
+    let line = line.trim();
+    match line.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+        Some((_, rest)) => rest.trim_start(),
+        None => line,
+    }
+}
+
+fn is_unity_version(token: &str) -> bool {
+    let mut parts = token.splitn(3, '.');
+    let (Some(major), Some(minor), Some(patch)) = (parts.next(), parts.next(), parts.next()) else {
+        return false;
+    };
+    let Some(split) = patch.find(|c: char| !c.is_ascii_digit()) else {
+        return false;
+    };
+    let (patch_number, suffix) = patch.split_at(split);
+    let mut suffix = suffix.chars();
+    let all_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    all_digits(major)
+        && all_digits(minor)
+        && all_digits(patch_number)
+        && matches!(suffix.next(), Some('a' | 'b' | 'f' | 'p' | 'c'))
+        && all_digits(suffix.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::harness::TempDir;
+
+    #[test]
+    fn reads_editor_version_from_project_settings() {
+        let temp = TempDir::new("unity");
+        let root = temp.path().to_path_buf();
+        fs::create_dir_all(root.join("ProjectSettings")).expect("create settings");
+        fs::write(
+            root.join("ProjectSettings/ProjectVersion.txt"),
+            "m_EditorVersion: 2022.3.22f1\nm_EditorVersionWithRevision: 2022.3.22f1 (887be4894c44)\n",
+        )
+        .expect("write version");
+
+        let version = read_project_version(&root);
+        let missing = read_project_version(&root.join("Nope"));
+
+        assert_eq!(version.as_deref(), Some("2022.3.22f1"));
+        assert_eq!(missing, None);
+    }
+
+    #[test]
+    fn parses_editors_from_vpm_list_unity_output() {
+        let lines = [
+            "[12:00:00 INF] Unity Version    | Path",
+            "[12:00:00 INF] ---------------- | ----",
+            "[12:00:00 INF] 2022.3.22f1      | /Applications/Unity/Hub/Editor/2022.3.22f1/Unity.app",
+            "2019.4.31f1 at C:\\Program Files\\Unity\\Hub\\Editor\\2019.4.31f1\\Editor\\Unity.exe",
+            "[12:00:00 INF] 2022.3.22f1      | /duplicate",
+            "version 1.2.3 of vpm",
+        ]
+        .map(String::from);
+
+        let editors = parse_installed_editors(&lines);
+
+        assert_eq!(
+            editors,
+            vec![
+                UnityEditor {
+                    version: "2022.3.22f1".to_string(),
+                    path: Some(PathBuf::from(
+                        "/Applications/Unity/Hub/Editor/2022.3.22f1/Unity.app"
+                    )),
+                },
+                UnityEditor {
+                    version: "2019.4.31f1".to_string(),
+                    path: Some(PathBuf::from(
+                        "C:\\Program Files\\Unity\\Hub\\Editor\\2019.4.31f1\\Editor\\Unity.exe"
+                    )),
+                },
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn opens_project_with_matching_editor_from_configured_root() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new("editor");
+
+        let root = temp.path().to_path_buf();
+        let project = root.join("Avatar");
+        fs::create_dir_all(project.join("ProjectSettings")).expect("create settings");
+        fs::write(
+            project.join("ProjectSettings/ProjectVersion.txt"),
+            "m_EditorVersion: 2022.3.22f1\n",
+        )
+        .expect("write version");
+        let editor = root.join("Editors/2022.3.22f1/Editor/Unity");
+        let args_file = root.join("args.txt");
+        fs::create_dir_all(editor.parent().expect("parent")).expect("create editor dir");
+        fs::write(
+            &editor,
+            format!("#!/bin/sh\necho \"$@\" > '{}'\n", args_file.display()),
+        )
+        .expect("write stub editor");
+        fs::set_permissions(&editor, fs::Permissions::from_mode(0o755)).expect("chmod");
+
+        let settings = UnitySettings {
+            editor_root: Some(root.join("Editors")),
+        };
+        let listed = [UnityEditor {
+            version: "2022.3.22f1".to_string(),
+            path: Some(root.join("elsewhere/Unity")),
+        }];
+        let opened = open_project(&project, &settings, &listed);
+        let mut args = None;
+        for _ in 0..100 {
+            if let Ok(raw) = fs::read_to_string(&args_file) {
+                if !raw.is_empty() {
+                    args = Some(raw);
+                    break;
+                }
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        let missing = open_project(&root.join("Nope"), &settings, &listed);
+
+        assert_eq!(
+            opened.expect("open project"),
+            ("2022.3.22f1".to_string(), editor)
+        );
+        assert_eq!(
+            args.as_deref(),
+            Some(format!("-projectPath {}\n", project.display()).as_str())
+        );
+        assert!(missing.is_err());
+    }
+}
@@ -1,81 +1,121 @@
 use crate::app::action::{Action, OutputStream};
+use crate::app::state::VpmSettings;
 use anyhow::{Context, Result};
+use futures::future::BoxFuture;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 
-#[derive(Debug, Clone, Default)]
-pub struct VpmClient;
+#[derive(Debug, Clone)]
+pub struct CommandRequest {
+    pub task_id: u64,
+    pub label: String,
+    pub args: Vec<String>,
+}
 
-impl VpmClient {
-    pub async fn run_command(
+pub trait CommandBackend: Send + Sync {
+    fn run(
         &self,
-        task_id: u64,
-        _label: String,
-        args: Vec<String>,
+        request: CommandRequest,
         token: CancellationToken,
         action_tx: mpsc::UnboundedSender<Action>,
-    ) -> Result<()> {
-        let mut command = Command::new("vpm");
+    ) -> BoxFuture<'static, Result<()>>;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VpmClient {
+    settings: VpmSettings,
+}
+
+impl VpmClient {
+    pub fn new(settings: VpmSettings) -> Self {
+        Self { settings }
+    }
+
+    fn command(&self, args: Vec<String>) -> Command {
+        let mut command = Command::new(self.settings.program());
         command
             .args(args)
+            .envs(&self.settings.env)
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .kill_on_drop(true);
+        if let Some(dir) = self.settings.current_dir() {
+            command.current_dir(dir);
+        }
+        command
+    }
+}
 
-        let mut child = command.spawn().context("failed to spawn vpm")?;
+impl CommandBackend for VpmClient {
+    fn run(
+        &self,
+        request: CommandRequest,
+        token: CancellationToken,
+        action_tx: mpsc::UnboundedSender<Action>,
+    ) -> BoxFuture<'static, Result<()>> {
+        let program = self.settings.program();
+        let mut command = self.command(request.args);
+        let task_id = request.task_id;
+        let label = request.label;
 
-        let mut readers: Vec<JoinHandle<()>> = Vec::new();
+        Box::pin(async move {
+            let mut child = command
+                .spawn()
+                .with_context(|| format!("{label}: failed to spawn {}", program.display()))?;
 
-        if let Some(stdout) = child.stdout.take() {
-            readers.push(tokio::spawn(pipe_lines(
-                task_id,
-                OutputStream::Stdout,
-                stdout,
-                action_tx.clone(),
-            )));
-        }
+            let mut readers: Vec<JoinHandle<()>> = Vec::new();
 
-        if let Some(stderr) = child.stderr.take() {
-            readers.push(tokio::spawn(pipe_lines(
-                task_id,
-                OutputStream::Stderr,
-                stderr,
-                action_tx.clone(),
-            )));
-        }
+            if let Some(stdout) = child.stdout.take() {
+                readers.push(tokio::spawn(pipe_lines(
+                    task_id,
+                    OutputStream::Stdout,
+                    stdout,
+                    action_tx.clone(),
+                )));
+            }
 
-        let (success, cancelled, exit_code, error) = tokio::select! {
-            _ = token.cancelled() => {
-                let _ = child.kill().await;
-                match child.wait().await {
-                    Ok(status) => (false, true, status.code(), None),
-                    Err(err) => (false, true, None, Some(err.to_string())),
-                }
+            if let Some(stderr) = child.stderr.take() {
+                readers.push(tokio::spawn(pipe_lines(
+                    task_id,
+                    OutputStream::Stderr,
+                    stderr,
+                    action_tx.clone(),
+                )));
             }
-            status = child.wait() => {
-                match status {
-                    Ok(status) => (status.success(), false, status.code(), None),
-                    Err(err) => (false, false, None, Some(err.to_string())),
+
+            let (success, cancelled, exit_code, error) = tokio::select! {
+                _ = token.cancelled() => {
+                    let _ = child.kill().await;
+                    match child.wait().await {
+                        Ok(status) => (false, true, status.code(), None),
+                        Err(err) => (false, true, None, Some(err.to_string())),
+                    }
                 }
-            }
-        };
+                status = child.wait() => {
+                    match status {
+                        Ok(status) => (status.success(), false, status.code(), None),
+                        Err(err) => (false, false, None, Some(err.to_string())),
+                    }
+                }
+            };
 
-        for reader in readers {
-            let _ = reader.await;
-        }
+            for reader in readers {
+                let _ = reader.await;
+            }
 
-        let _ = action_tx.send(Action::TaskDone {
-            task_id,
-            success,
-            cancelled,
-            exit_code,
-            error,
-        });
+            let _ = action_tx.send(Action::TaskDone {
+                task_id,
+                success,
+                cancelled,
+                exit_code,
+                error,
+            });
 
-        Ok(())
+            Ok(())
+        })
     }
 }
 
@@ -94,3 +134,322 @@ async fn pipe_lines(
         });
     }
 }
+
+#[cfg(test)]
+pub mod fake {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone, Default)]
+    pub struct ScriptedRun {
+        pub output: Vec<(OutputStream, String)>,
+        pub exit_code: i32,
+        pub spawn_error: Option<String>,
+    }
+
+    impl ScriptedRun {
+        pub fn exit(exit_code: i32) -> Self {
+            Self {
+                exit_code,
+                ..Self::default()
+            }
+        }
+
+        pub fn stdout(mut self, line: &str) -> Self {
+            self.output.push((OutputStream::Stdout, line.to_string()));
+            self
+        }
+
+        pub fn stderr(mut self, line: &str) -> Self {
+            self.output.push((OutputStream::Stderr, line.to_string()));
+            self
+        }
+
+        pub fn spawn_error(message: &str) -> Self {
+            Self {
+                spawn_error: Some(message.to_string()),
+                ..Self::default()
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct Script {
+        args_prefix: Vec<String>,
+        run: ScriptedRun,
+    }
+
+    #[derive(Debug, Clone, Default)]
+    pub struct FakeBackend {
+        scripts: Arc<Mutex<Vec<Script>>>,
+        calls: Arc<Mutex<Vec<Vec<String>>>>,
+    }
+
+    impl FakeBackend {
+        pub fn script(self, args_prefix: &[&str], run: ScriptedRun) -> Self {
+            self.scripts.lock().expect("scripts lock").push(Script {
+                args_prefix: args_prefix.iter().map(|a| a.to_string()).collect(),
+                run,
+            });
+            self
+        }
+
+        pub fn calls(&self) -> Vec<Vec<String>> {
+            self.calls.lock().expect("calls lock").clone()
+        }
+
+        fn scripted(&self, args: &[String]) -> ScriptedRun {
+            self.scripts
+                .lock()
+                .expect("scripts lock")
+                .iter()
+                .find(|script| args.starts_with(&script.args_prefix))
+                .map(|script| script.run.clone())
+                .unwrap_or_default()
+        }
+    }
+
+    impl CommandBackend for FakeBackend {
+        fn run(
+            &self,
+            request: CommandRequest,
+            token: CancellationToken,
+            action_tx: mpsc::UnboundedSender<Action>,
+        ) -> BoxFuture<'static, Result<()>> {
+            self.calls
+                .lock()
+                .expect("calls lock")
+                .push(request.args.clone());
+            let run = self.scripted(&request.args);
+            let task_id = request.task_id;
+
+            Box::pin(async move {
+                if let Some(message) = run.spawn_error {
+                    anyhow::bail!(message);
+                }
+                for (stream, line) in run.output {
+                    let _ = action_tx.send(Action::TaskOutput {
+                        task_id,
+                        stream,
+                        line,
+                    });
+                }
+                let cancelled = token.is_cancelled();
+                let _ = action_tx.send(Action::TaskDone {
+                    task_id,
+                    success: !cancelled && run.exit_code == 0,
+                    cancelled,
+                    exit_code: Some(run.exit_code),
+                    error: None,
+                });
+                Ok(())
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fake::{FakeBackend, ScriptedRun};
+    use super::*;
+    use crate::app::effect::Effect;
+    use crate::app::harness::key;
+    use crate::app::harness::TempDir;
+    use crate::app::reducer::reduce;
+    use crate::app::state::{AppConfig, AppState, Screen, TaskState};
+    use crossterm::event::KeyCode;
+
+    async fn drive(
+        state: &mut AppState,
+        backend: &dyn CommandBackend,
+        mut effects: Vec<Effect>,
+    ) -> Vec<Effect> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut other = Vec::new();
+        while !effects.is_empty() {
+            for effect in std::mem::take(&mut effects) {
+                if let Effect::RunVpmCommand {
+                    task_id,
+                    label,
+                    args,
+                } = effect
+                {
+                    let request = CommandRequest {
+                        task_id,
+                        label,
+                        args,
+                    };
+                    if let Err(err) = backend
+                        .run(request, CancellationToken::new(), tx.clone())
+                        .await
+                    {
+                        let _ = tx.send(Action::TaskDone {
+                            task_id,
+                            success: false,
+                            cancelled: false,
+                            exit_code: None,
+                            error: Some(err.to_string()),
+                        });
+                    }
+                } else {
+                    other.push(effect);
+                }
+            }
+            while let Ok(action) = rx.try_recv() {
+                effects.extend(reduce(state, action));
+            }
+        }
+        other
+    }
+
+    #[tokio::test]
+    async fn version_check_runs_after_config_and_records_output() {
+        let backend = FakeBackend::default()
+            .script(&["--version"], ScriptedRun::exit(0).stdout("0.1.28"))
+            .script(
+                &["list", "unity"],
+                ScriptedRun::exit(0).stdout("2022.3.22f1 | /opt/unity/2022.3.22f1/Unity"),
+            );
+        let mut state = AppState::default();
+        let config = AppConfig {
+            vpm: VpmSettings {
+                executable: Some("/opt/vpm/vpm".into()),
+                ..VpmSettings::default()
+            },
+            ..AppConfig::default()
+        };
+
+        let effects = reduce(&mut state, Action::ConfigLoaded(Ok(config)));
+        let rest = drive(&mut state, &backend, effects).await;
+
+        assert_eq!(
+            backend.calls(),
+            vec![
+                vec!["--version".to_string()],
+                vec!["list".to_string(), "unity".to_string()],
+            ]
+        );
+        assert_eq!(state.system_checks.vpm_version.as_deref(), Some("0.1.28"));
+        assert_eq!(
+            state
+                .system_checks
+                .installed_editors
+                .as_ref()
+                .map(|editors| editors
+                    .iter()
+                    .map(|e| e.version.as_str())
+                    .collect::<Vec<_>>()),
+            Some(vec!["2022.3.22f1"])
+        );
+        assert!(rest.iter().any(|e| matches!(
+            e,
+            Effect::ConfigureCommandBackend(settings)
+                if settings.executable.as_deref() == Some(std::path::Path::new("/opt/vpm/vpm"))
+        )));
+    }
+
+    #[tokio::test]
+    async fn failed_check_reports_exit_code_and_stderr() {
+        let backend = FakeBackend::default().script(
+            &["check", "hub"],
+            ScriptedRun::exit(2).stderr("Unity Hub not found"),
+        );
+        let mut state = AppState {
+            screen: Screen::Settings,
+            ..AppState::default()
+        };
+
+        let effects = reduce(&mut state, key(KeyCode::Char('h')));
+        drive(&mut state, &backend, effects).await;
+
+        let task = state.tasks.last().expect("task recorded");
+        assert_eq!(task.state, TaskState::Failed);
+        assert_eq!(task.exit_code, Some(2));
+        assert_eq!(
+            state.system_checks.hub_check.as_deref(),
+            Some("failed (exit=2)")
+        );
+        assert!(state
+            .logs
+            .iter()
+            .any(|l| l.task_id() == Some(task.id) && l.text == "Unity Hub not found"));
+    }
+
+    #[tokio::test]
+    async fn spawn_failure_marks_task_failed() {
+        let backend = FakeBackend::default().script(
+            &["list", "repos"],
+            ScriptedRun::spawn_error("failed to spawn vpm"),
+        );
+        let mut state = AppState {
+            screen: Screen::Settings,
+            ..AppState::default()
+        };
+
+        let effects = reduce(&mut state, key(KeyCode::Char('r')));
+        drive(&mut state, &backend, effects).await;
+
+        let task = state.tasks.last().expect("task recorded");
+        assert_eq!(task.state, TaskState::Failed);
+        assert_eq!(task.error.as_deref(), Some("failed to spawn vpm"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn process_backend_uses_configured_executable_env_and_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new("vpm-stub");
+
+        let dir = temp.path().to_path_buf();
+        let stub = dir.join("vpm");
+        std::fs::write(
+            &stub,
+            "#!/bin/sh\necho \"$VCC_TUI_TEST $1\"\npwd >&2\nexit 3\n",
+        )
+        .expect("write stub");
+        std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755))
+            .expect("chmod stub");
+
+        let client = VpmClient::new(VpmSettings {
+            executable: Some(stub),
+            env: [("VCC_TUI_TEST".to_string(), "hello".to_string())].into(),
+            working_dir: Some(dir.clone()),
+        });
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let request = CommandRequest {
+            task_id: 7,
+            label: "vpm --version".to_string(),
+            args: vec!["--version".to_string()],
+        };
+        client
+            .run(request, CancellationToken::new(), tx)
+            .await
+            .expect("run stub");
+
+        let mut actions = Vec::new();
+        while let Ok(action) = rx.try_recv() {
+            actions.push(action);
+        }
+        let canonical_dir = std::fs::canonicalize(&dir).expect("canonical dir");
+
+        assert!(actions.iter().any(|a| matches!(
+            a,
+            Action::TaskOutput { stream: OutputStream::Stdout, line, .. } if line == "hello --version"
+        )));
+        assert!(actions.iter().any(|a| matches!(
+            a,
+            Action::TaskOutput { stream: OutputStream::Stderr, line, .. }
+                if std::path::Path::new(line) == canonical_dir
+        )));
+        assert!(matches!(
+            actions.last(),
+            Some(Action::TaskDone {
+                task_id: 7,
+                success: false,
+                exit_code: Some(3),
+                ..
+            })
+        ));
+    }
+}
@@ -1,8 +1,11 @@
 pub mod screens;
 
-use crate::app::state::{AppState, Screen};
+use crate::app::state::{
+    log_pane_rows, AppState, LogEntry, LogLevel, Screen, StreamFilter, HEADER_ROWS,
+    LOG_PANE_BORDER_ROWS, MIN_BODY_ROWS,
+};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::prelude::{Frame, Line};
+use ratatui::prelude::{Frame, Line, Span};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
 
@@ -10,9 +13,9 @@ pub fn render(frame: &mut Frame, state: &AppState) {
     let root = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1),
-            Constraint::Min(8),
-            Constraint::Length(10),
+            Constraint::Length(HEADER_ROWS),
+            Constraint::Min(MIN_BODY_ROWS),
+            Constraint::Length(log_pane_rows(frame.size().height) as u16 + LOG_PANE_BORDER_ROWS),
         ])
         .split(frame.size());
 
@@ -29,12 +32,47 @@ pub fn render(frame: &mut Frame, state: &AppState) {
         Screen::Add => screens::add::render(frame, state, body[1]),
         Screen::Projects => screens::projects::render(frame, state, body[1]),
         Screen::Manage => screens::manage::render(frame, state, body[1]),
+        Screen::Repos => screens::repos::render(frame, state, body[1]),
+        Screen::Tasks => screens::tasks::render(frame, state, body[1]),
         Screen::Settings => screens::settings::render(frame, state, body[1]),
     }
 
     render_logs(frame, state, root[2]);
 }
 
+pub fn log_style(entry: &LogEntry) -> Style {
+    level_style(entry.level, entry.stream.is_some())
+}
+
+pub fn level_style(level: LogLevel, task_output: bool) -> Style {
+    match (level, task_output) {
+        (LogLevel::Error, _) => Style::default().fg(Color::LightRed),
+        (LogLevel::Warn, _) => Style::default().fg(Color::LightYellow),
+        (LogLevel::Success, _) => Style::default().fg(Color::LightGreen),
+        (LogLevel::Info, true) => Style::default().fg(Color::White),
+        (LogLevel::Info, false) => Style::default().fg(Color::Gray),
+    }
+}
+
+pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
+
 fn render_header(frame: &mut Frame, state: &AppState, area: Rect) {
     let text = format!(
         "[Tab/←/→] Navigate  [q] Quit  [c] Cancel task(Settings)  Screen={:?}  Status={} ",
@@ -57,6 +95,8 @@ fn render_nav(frame: &mut Frame, state: &AppState, area: Rect) {
         (Screen::Add, "Add"),
         (Screen::Projects, "Projects"),
         (Screen::Manage, "Manage"),
+        (Screen::Repos, "Repos"),
+        (Screen::Tasks, "Tasks"),
         (Screen::Settings, "Settings"),
     ];
 
@@ -91,34 +131,340 @@ fn render_nav(frame: &mut Frame, state: &AppState, area: Rect) {
 }
 
 fn render_logs(frame: &mut Frame, state: &AppState, area: Rect) {
-    let lines = state
-        .logs
+    let logs = state.visible_logs();
+    let matches = state.log_matches();
+    let current_match = matches.get(state.log_match).copied();
+    let query = state.log_search_query.to_ascii_lowercase();
+    let rows = area.height.saturating_sub(2) as usize;
+    let width = area.width.saturating_sub(2).max(1) as usize;
+    let offset = if state.log_follow {
+        wrapped_tail_start(&logs, rows, width)
+    } else {
+        state.log_scroll_offset()
+    };
+
+    let lines = logs
         .iter()
-        .map(|l| {
-            let style = if l.text.contains(":err]") {
-                Style::default().fg(Color::LightRed)
-            } else if l.text.contains(":out]") {
-                Style::default().fg(Color::White)
-            } else if l.text.contains("Task") && l.text.contains("failed") {
-                Style::default().fg(Color::LightRed)
-            } else if l.text.contains("Task") && l.text.contains("done") {
-                Style::default().fg(Color::LightGreen)
+        .enumerate()
+        .skip(offset)
+        .take(rows)
+        .map(|(idx, l)| {
+            let highlight = if Some(idx) == current_match {
+                Style::default()
+                    .bg(Color::LightMagenta)
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::Gray)
+                Style::default().bg(Color::Yellow).fg(Color::Black)
             };
-            Line::styled(l.text.clone(), style)
+            highlighted_line(l.display(), &query, log_style(l), highlight)
         })
         .collect::<Vec<_>>();
 
+    let mut title = vec![if state.log_focus {
+        "Logs [/] search [n/N] match [t/s/e] filter [f] follow [x] export [Esc] back".to_string()
+    } else {
+        "Logs ([L] focus, Up/Down scroll)".to_string()
+    }];
+    title.push(if state.log_follow { "follow" } else { "paused" }.to_string());
+    if let Some(task_id) = state.log_filter.task_id {
+        title.push(format!("task={task_id}"));
+    }
+    if state.log_filter.stream != StreamFilter::All {
+        title.push(format!("stream={}", state.log_filter.stream.label()));
+    }
+    if let Some(level) = state.log_filter.min_level {
+        title.push(format!("level>={}", level.label()));
+    }
+    if state.log_search_mode || !state.log_search_query.is_empty() {
+        let cursor = if state.log_search_mode { "_" } else { "" };
+        let position = match current_match {
+            Some(_) => format!("{}/{}", state.log_match + 1, matches.len()),
+            None => "0/0".to_string(),
+        };
+        title.push(format!("/{}{cursor} ({position})", state.log_search_query));
+    }
+
+    let border = if state.log_focus {
+        Color::LightYellow
+    } else {
+        Color::DarkGray
+    };
     let paragraph = Paragraph::new(lines)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Logs (Up/Down scroll)")
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .title(title.join("  "))
+                .border_style(Style::default().fg(border)),
         )
-        .scroll((state.log_scroll, 0))
         .wrap(Wrap { trim: false });
 
     frame.render_widget(paragraph, area);
 }
+
+// Long lines wrap onto several rows, so following the tail has to start far
+// enough back that the newest entry is not pushed out of the pane.
+fn wrapped_tail_start(logs: &[&LogEntry], rows: usize, width: usize) -> usize {
+    let mut used = 0;
+    let mut start = logs.len();
+    for entry in logs.iter().rev() {
+        let height = Line::raw(entry.display()).width().div_ceil(width).max(1);
+        if used + height > rows && start < logs.len() {
+            break;
+        }
+        used += height;
+        start -= 1;
+    }
+    start
+}
+
+fn highlighted_line(text: String, query: &str, base: Style, highlight: Style) -> Line<'static> {
+    if query.is_empty() {
+        return Line::styled(text, base);
+    }
+    let lower = text.to_ascii_lowercase();
+    let mut spans = Vec::new();
+    let mut start = 0;
+    while let Some(pos) = lower[start..].find(query) {
+        let begin = start + pos;
+        let end = begin + query.len();
+        if begin > start {
+            spans.push(Span::styled(text[start..begin].to_string(), base));
+        }
+        spans.push(Span::styled(text[begin..end].to_string(), highlight));
+        start = end;
+    }
+    if start < text.len() {
+        spans.push(Span::styled(text[start..].to_string(), base));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::action::Action;
+    use crate::app::action::OutputStream;
+    use crate::app::harness::{
+        assert_snapshot, backup_entry, render_state, sample_state_on, version, Harness,
+    };
+    use crate::app::state::{
+        DiscoveredProject, PackageVersion, ProjectColumn, ProjectKind, ProjectScan, RepoEntry,
+        Screen, UnityEditor,
+    };
+    use chrono::{Local, TimeZone};
+    use crossterm::event::KeyCode;
+    use std::path::PathBuf;
+
+    #[test]
+    fn screens_match_snapshots() {
+        for (screen, name) in [
+            (Screen::New, "new"),
+            (Screen::Add, "add"),
+            (Screen::Projects, "projects"),
+            (Screen::Manage, "manage"),
+            (Screen::Settings, "settings"),
+        ] {
+            assert_snapshot(name, &render_state(&sample_state_on(screen)));
+        }
+    }
+
+    #[test]
+    fn version_picker_popup_matches_snapshot() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        h.state.available_packages[0].versions.insert(
+            1,
+            PackageVersion {
+                repo_id: "com.example.mirror".to_string(),
+                ..version("3.5.1")
+            },
+        );
+        h.press(KeyCode::Enter);
+
+        assert!(h.state.version_picker.is_some());
+        assert_snapshot("manage_version_picker", &h.render());
+    }
+
+    #[test]
+    fn backup_browser_popup_matches_snapshot() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        h.press(KeyCode::Char('b'));
+        h.send(Action::BackupsListed {
+            project_path: PathBuf::from("/projects/AvatarProject"),
+            result: Ok(vec![
+                backup_entry(
+                    "/backups/AvatarProject-20260102-090000.zip",
+                    "2026-01-02 09:00:00",
+                    5_242_880,
+                ),
+                backup_entry(
+                    "/backups/AvatarProject-20260101-120000.zip",
+                    "2026-01-01 12:00:00",
+                    812,
+                ),
+            ]),
+        });
+
+        assert_snapshot("manage_backups", &h.render());
+    }
+
+    #[test]
+    fn tasks_screen_matches_snapshot() {
+        let mut h = Harness::new(sample_state_on(Screen::Manage));
+        h.press(KeyCode::Char('A'));
+        h.output(1, OutputStream::Stdout, "Added com.vrchat.avatars@3.6.0");
+        h.finish(1, 0);
+        h.press(KeyCode::Char('d'));
+        h.output(
+            2,
+            OutputStream::Stderr,
+            "package is required by another package",
+        );
+        h.finish(2, 1);
+        h.state.screen = Screen::Tasks;
+        for (i, task) in h.state.tasks.iter_mut().enumerate() {
+            let start = Local.with_ymd_and_hms(2024, 5, 1, 12, i as u32, 0).unwrap();
+            task.queued_at = start;
+            task.started_at = Some(start);
+            task.finished_at = Some(start + chrono::Duration::seconds(42));
+        }
+
+        assert_snapshot("tasks", &h.render());
+    }
+
+    #[test]
+    fn focused_log_pane_with_search_matches_snapshot() {
+        let mut h = Harness::new(sample_state_on(Screen::Settings));
+        h.press(KeyCode::Char('h'));
+        for line in ["Unity Hub found", "checking editors", "no editor found"] {
+            h.output(1, OutputStream::Stdout, line);
+        }
+        h.press(KeyCode::Char('L'));
+        h.press(KeyCode::Char('s'));
+        h.press(KeyCode::Char('/'));
+        h.type_text("found");
+
+        assert_snapshot("logs_focused_search", &h.render());
+    }
+
+    #[test]
+    fn log_pane_wraps_long_lines_and_keeps_the_newest_visible() {
+        let mut h = Harness::new(sample_state_on(Screen::Settings));
+        h.press(KeyCode::Char('h'));
+        for n in 0..10 {
+            h.output(1, OutputStream::Stdout, &format!("line {n}"));
+        }
+        let long = format!("{} end-of-long-line", "resolving dependency ".repeat(8));
+        h.output(1, OutputStream::Stderr, &long);
+        h.output(1, OutputStream::Stdout, "newest line");
+
+        let screen = h.render();
+        assert!(screen.contains("end-of-long-line"));
+        assert!(screen.contains("newest line"));
+    }
+
+    #[test]
+    fn add_screen_scan_results_match_snapshot() {
+        let mut h = Harness::new(sample_state_on(Screen::Add));
+        let root = PathBuf::from("/projects");
+        h.state.project_scan = Some(ProjectScan::new(1, root.clone()));
+        for (path, kind) in [
+            ("/projects/AvatarProject", ProjectKind::Vpm),
+            ("/projects/Clients/Acme/Old", ProjectKind::Unity),
+        ] {
+            h.send(Action::ProjectDiscovered {
+                scan_id: 1,
+                root: root.clone(),
+                project: DiscoveredProject {
+                    path: PathBuf::from(path),
+                    kind,
+                },
+            });
+        }
+        h.send(Action::ScanFinished {
+            scan_id: 1,
+            root,
+            result: Ok(2),
+        });
+
+        assert_snapshot("add_scan_results", &h.render());
+    }
+
+    #[test]
+    fn repository_headers_are_masked() {
+        let mut state = sample_state_on(Screen::Repos);
+        state.repositories = vec![RepoEntry {
+            id: Some("com.example.private".to_string()),
+            name: "Private".to_string(),
+            url: "https://private.example/vpm.json".to_string(),
+            local_path: None,
+            headers: [
+                (
+                    "Authorization".to_string(),
+                    "Bearer s3cr3t-token".to_string(),
+                ),
+                ("X-Key".to_string(), "short".to_string()),
+            ]
+            .into(),
+            package_count: Some(3),
+            cache_age_secs: Some(120),
+        }];
+
+        assert_snapshot("repos_masked_headers", &render_state(&state));
+    }
+
+    #[test]
+    fn offline_project_matches_snapshot() {
+        let mut state = sample_state_on(Screen::Projects);
+        state
+            .offline_projects
+            .insert(PathBuf::from("/projects/AvatarProject"));
+
+        assert_snapshot("projects_offline", &render_state(&state));
+    }
+
+    #[test]
+    fn project_unity_versions_match_snapshot() {
+        let mut state = sample_state_on(Screen::Projects);
+        state.project_versions = [
+            (PathBuf::from("/projects/AvatarProject"), "2019.4.31f1"),
+            (PathBuf::from("/projects/WorldProject"), "2022.3.22f1"),
+        ]
+        .map(|(path, version)| (path, version.to_string()))
+        .into();
+        state.system_checks.installed_editors = Some(vec![UnityEditor {
+            version: "2022.3.22f1".to_string(),
+            path: None,
+        }]);
+
+        assert_snapshot("projects_unity_versions", &render_state(&state));
+    }
+
+    #[test]
+    fn projects_grouped_by_tag_with_tag_input_match_snapshot() {
+        let mut h = Harness::new(sample_state_on(Screen::Projects));
+        h.state.projects[0].tags = vec!["client".to_string(), "avatar".to_string()];
+        h.state.projects[1].tags = vec!["client".to_string()];
+        h.state.projects[1].pinned = true;
+        h.press(KeyCode::Char('g'));
+        h.press(KeyCode::Char('j'));
+        h.press(KeyCode::Char('t'));
+        h.type_text("a");
+
+        assert_snapshot("projects_grouped_by_tag", &h.render());
+    }
+
+    #[test]
+    fn sorted_project_table_matches_snapshot() {
+        let mut state = sample_state_on(Screen::Projects);
+        let manifest = state.selected_project_manifest.clone().expect("manifest");
+        state
+            .project_manifests
+            .insert(PathBuf::from("/projects/AvatarProject"), manifest);
+        state.projects[1].last_opened = Some("2026-02-16T00:00:00Z".to_string());
+        state.project_table.sort = Some(ProjectColumn::LastOpened);
+        state.project_table.descending = true;
+        state.project_table.hidden = vec![ProjectColumn::Unity];
+
+        assert_snapshot("projects_table_sorted", &render_state(&state));
+    }
+}
@@ -1,7 +1,7 @@
-use crate::app::state::AppState;
-use ratatui::layout::Rect;
-use ratatui::prelude::{Color, Frame, Line, Style};
-use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use crate::app::state::{AppState, ProjectScan};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::prelude::{Color, Frame, Line, Modifier, Style};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
 
 pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
     let mut lines = vec![
@@ -10,7 +10,19 @@ pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
             Style::default().fg(Color::LightGreen),
         ),
         Line::styled(
-            "[f] Add projects from folder (one-level scan)",
+            "[f] Find projects under a folder (recursive scan)",
+            Style::default().fg(Color::LightGreen),
+        ),
+        Line::styled(
+            format!(
+                "[+/-] Scan depth: {}  [i] Ignored: {}",
+                state.scan_settings.max_depth,
+                state.scan_settings.ignore.join(", ")
+            ),
+            Style::default().fg(Color::LightGreen),
+        ),
+        Line::styled(
+            "[w] Watch a folder  [W] Rescan watch folders  [j/k] Select  [x] Stop watching",
             Style::default().fg(Color::LightGreen),
         ),
         Line::from(""),
@@ -19,11 +31,27 @@ pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
             Style::default().fg(Color::Gray),
         ),
         Line::styled(
-            "Folder scan checks only direct child directories.",
+            "Folder scan also finds non-VPM Unity projects (legacy SDK3 or plain Unity).",
             Style::default().fg(Color::Gray),
         ),
     ];
 
+    if !state.watch_folders.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            "Watch folders (rescanned at startup):",
+            Style::default().fg(Color::White),
+        ));
+        for (idx, root) in state.watch_folders.iter().enumerate() {
+            let (marker, style) = if idx == state.selected_watch_folder {
+                ("▶ ", Style::default().fg(Color::Black).bg(Color::LightCyan))
+            } else {
+                ("  ", Style::default().fg(Color::Gray))
+            };
+            lines.push(Line::styled(format!("{marker}{}", root.display()), style));
+        }
+    }
+
     if state.add_project_mode {
         lines.push(Line::from(""));
         lines.push(Line::styled(
@@ -35,26 +63,140 @@ pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
         ));
     }
 
+    if state.scan_ignore_mode {
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            format!(
+                "Ignore pattern (* and ? allowed, existing pattern removes it): {} (Enter=save, Esc=cancel)",
+                state.scan_ignore_input
+            ),
+            Style::default().fg(Color::Black).bg(Color::LightYellow),
+        ));
+    }
+
     if state.add_folder_mode {
         lines.push(Line::from(""));
         lines.push(Line::styled(
             format!(
-                "Folder path input: {} (Enter=scan, Esc=cancel)",
-                state.add_folder_input
+                "{} path input: {} (Enter={}, Esc=cancel)",
+                if state.watch_folder_mode {
+                    "Watch folder"
+                } else {
+                    "Folder"
+                },
+                state.add_folder_input,
+                if state.watch_folder_mode {
+                    "watch"
+                } else {
+                    "scan"
+                }
             ),
             Style::default().fg(Color::Black).bg(Color::LightYellow),
         ));
     }
 
+    let Some(scan) = &state.project_scan else {
+        frame.render_widget(intro(lines), area);
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(lines.len() as u16 + 2),
+            Constraint::Min(3),
+        ])
+        .split(area);
+    frame.render_widget(intro(lines), chunks[0]);
+    render_scan(frame, state, scan, chunks[1]);
+}
+
+fn intro(lines: Vec<Line<'_>>) -> Paragraph<'_> {
+    Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Add")
+                .border_style(Style::default().fg(Color::LightBlue)),
+        )
+        .wrap(Wrap { trim: false })
+}
+
+fn render_scan(frame: &mut Frame, state: &AppState, scan: &ProjectScan, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(2)])
+        .split(area);
+
+    let progress = if scan.is_running() {
+        format!(
+            "Scanning... {} folder(s) visited, {} found  {}",
+            scan.visited,
+            scan.found.len(),
+            scan.current
+                .as_ref()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_default()
+        )
+    } else {
+        format!(
+            "Done: {} found  [j/k] Move  [Space] Toggle  [A] All/none  [Enter] Add chosen  [Esc] Discard",
+            scan.found.len()
+        )
+    };
     frame.render_widget(
-        Paragraph::new(lines)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("Add")
-                    .border_style(Style::default().fg(Color::LightBlue)),
-            )
-            .wrap(Wrap { trim: false }),
-        area,
+        Paragraph::new(progress).style(Style::default().fg(Color::LightYellow)),
+        chunks[0],
     );
+
+    let items = scan
+        .found
+        .iter()
+        .zip(&scan.chosen)
+        .map(|(project, chosen)| {
+            let registered = state.projects.iter().any(|p| p.path == project.path);
+            let (mark, color) = if registered {
+                ("[=]", Color::DarkGray)
+            } else if *chosen {
+                ("[x]", Color::White)
+            } else {
+                ("[ ]", Color::Gray)
+            };
+            let note = if registered { "  (already added)" } else { "" };
+            ListItem::new(format!(
+                "{mark} {:<11} {}{note}",
+                project.kind.label(),
+                project.path.display()
+            ))
+            .style(Style::default().fg(color))
+        })
+        .collect::<Vec<_>>();
+
+    let mut list_state = ListState::default().with_selected(Some(scan.selected));
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(if scan.watch {
+                    "New projects in watch folders".to_string()
+                } else {
+                    format!(
+                        "Found under {}",
+                        scan.roots
+                            .iter()
+                            .map(|r| r.display().to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                })
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::LightCyan)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    frame.render_stateful_widget(list, chunks[1], &mut list_state);
 }
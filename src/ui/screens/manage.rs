@@ -1,7 +1,7 @@
-use crate::app::state::AppState;
+use crate::app::state::{AppState, BackupBrowser, VersionPicker};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::prelude::{Color, Frame, Modifier, Style};
-use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::prelude::{Color, Frame, Line, Modifier, Style};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap};
 use std::collections::HashSet;
 
 pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
@@ -18,7 +18,7 @@ pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
             state.add_package_input
         )
     } else {
-        "[h/l] Focus Installed/Available  [j/k] Move  [+/-] Add/Remove  [u] Update selected installed  [U] Update VRChat SDK  [/] Search  [r] Reload manifest  [R] Reload available  [v] Resolve".to_string()
+        "[h/l] Focus Installed/Available  [j/k] Move  [+/-] Add/Remove  [Enter/@] Pick version  [u] Update selected installed  [A] Update all outdated  [U] Update VRChat SDK  [/] Search  [r] Reload manifest  [R] Reload available  [p] Prereleases  [I] Details  [v] Resolve  [o] Open in Unity  [b] Backups  [M] Migrate legacy".to_string()
     };
 
     frame.render_widget(
@@ -34,13 +34,281 @@ pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
         chunks[0],
     );
 
+    let body = if state.show_package_detail {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(chunks[1])
+    } else {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(100)])
+            .split(chunks[1])
+    };
+
     let panes = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(54), Constraint::Percentage(46)])
-        .split(chunks[1]);
+        .split(body[0]);
 
     render_available(frame, state, panes[0]);
     render_installed(frame, state, panes[1]);
+    if state.show_package_detail {
+        render_detail(frame, state, body[1]);
+    }
+
+    if let Some(picker) = &state.version_picker {
+        render_version_picker(frame, picker, area);
+    }
+    if let Some(browser) = &state.backup_browser {
+        render_backup_browser(frame, browser, area);
+    }
+}
+
+fn render_backup_browser(frame: &mut Frame, browser: &BackupBrowser, area: Rect) {
+    let popup = crate::ui::centered_rect(80, 70, area);
+    let items = if browser.loading {
+        vec![ListItem::new("Loading backups...").style(Style::default().fg(Color::DarkGray))]
+    } else if browser.entries.is_empty() {
+        vec![ListItem::new("No backups yet; press [n] to create one")
+            .style(Style::default().fg(Color::DarkGray))]
+    } else {
+        browser
+            .entries
+            .iter()
+            .map(|entry| {
+                ListItem::new(format!(
+                    "{}  {:>9}  {}",
+                    entry.created.format("%Y-%m-%d %H:%M:%S"),
+                    entry.size_label(),
+                    entry.file_name()
+                ))
+                .style(Style::default().fg(Color::White))
+            })
+            .collect()
+    };
+
+    let name = browser
+        .project_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut list_state = ListState::default().with_selected(
+        (!browser.loading && !browser.entries.is_empty()).then_some(browser.selected),
+    );
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    "Backups of {name}{}  [n] New backup  [Enter] Restore  [Esc] Close",
+                    if browser.busy { " (working...)" } else { "" }
+                ))
+                .border_style(Style::default().fg(Color::LightYellow)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::LightYellow)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+
+    frame.render_widget(Clear, popup);
+    frame.render_stateful_widget(list, popup, &mut list_state);
+}
+
+fn render_detail(frame: &mut Frame, state: &AppState, area: Rect) {
+    let mut lines = Vec::new();
+    let mut title = "Package Detail".to_string();
+
+    if let Some(package) = state.focused_catalog_package() {
+        let installed = state
+            .installed_packages()
+            .into_iter()
+            .find(|p| p.name == package.id)
+            .map(|p| p.version().to_string());
+        let shown = if state.manage_focus_available {
+            None
+        } else {
+            installed.as_deref().and_then(|v| package.version_info(v))
+        }
+        .or_else(|| package.version_info(package.latest(state.show_prereleases)));
+
+        title = format!("Package Detail: {}", package.display_name);
+        lines.push(Line::styled(
+            format!("{} ({})", package.id, package.repo_id),
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+        if let Some(version) = shown {
+            let field = |label: &str, value: Option<&String>| {
+                Line::styled(
+                    format!("{label}: {}", value.map(|v| v.as_str()).unwrap_or("-")),
+                    Style::default().fg(Color::White),
+                )
+            };
+            lines.push(field("Version", Some(&version.version)));
+            lines.push(field("Repository", Some(&version.repo_id)));
+            lines.push(field("Installed", installed.as_ref()));
+            lines.push(field("Description", version.description.as_ref()));
+            lines.push(field("Author", version.author.as_ref()));
+            lines.push(field("Unity", version.unity.as_ref()));
+            lines.push(field("Changelog", version.changelog_url.as_ref()));
+            lines.push(field("Docs", version.documentation_url.as_ref()));
+            if !version.keywords.is_empty() {
+                lines.push(field("Keywords", Some(&version.keywords.join(", "))));
+            }
+            if !version.legacy_folders.is_empty() {
+                lines.push(field(
+                    "Legacy folders",
+                    Some(&version.legacy_folders.join(", ")),
+                ));
+            }
+
+            if package.sources.len() > 1 {
+                lines.push(Line::styled(
+                    "Provided by:",
+                    Style::default().fg(Color::LightCyan),
+                ));
+                for (idx, source) in package.sources.iter().enumerate() {
+                    lines.push(Line::styled(
+                        format!(
+                            "  {} {} latest={} versions={}",
+                            if idx == 0 { "*" } else { " " },
+                            source.repo_id,
+                            source.latest_version,
+                            source
+                                .versions
+                                .iter()
+                                .map(|v| v.version.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                        Style::default().fg(if idx == 0 {
+                            Color::LightGreen
+                        } else {
+                            Color::Gray
+                        }),
+                    ));
+                }
+            }
+
+            let tree = state.dependency_tree(&package.id, version);
+            lines.push(Line::styled(
+                if tree.is_empty() {
+                    "Dependencies: none".to_string()
+                } else {
+                    "Dependencies (resolved against catalog):".to_string()
+                },
+                Style::default().fg(Color::LightCyan),
+            ));
+            for node in tree {
+                let resolved = match (&node.resolved_version, node.in_catalog) {
+                    (Some(v), _) => format!("-> {v}"),
+                    (None, true) => "-> no matching version".to_string(),
+                    (None, false) => "-> not in catalog".to_string(),
+                };
+                let installed = node
+                    .installed_version
+                    .as_deref()
+                    .map(|v| format!(" (installed {v})"))
+                    .unwrap_or_default();
+                let style = if node.resolved_version.is_none() {
+                    Style::default().fg(Color::LightRed)
+                } else if node.installed_version.is_some() {
+                    Style::default().fg(Color::Gray)
+                } else {
+                    Style::default().fg(Color::LightGreen)
+                };
+                lines.push(Line::styled(
+                    format!(
+                        "{}└ {} {} {resolved}{installed}",
+                        "  ".repeat(node.depth),
+                        node.id,
+                        node.range
+                    ),
+                    style,
+                ));
+            }
+        }
+    } else {
+        lines.push(Line::styled(
+            "Selected package is not in the available package catalog",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(Style::default().fg(Color::LightBlue)),
+            )
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+fn render_version_picker(frame: &mut Frame, picker: &VersionPicker, area: Rect) {
+    let popup = crate::ui::centered_rect(80, 70, area);
+    let items = picker
+        .versions
+        .iter()
+        .map(|v| {
+            let mut text = v.version.clone();
+            if picker.installed_version.as_deref() == Some(v.version.as_str()) {
+                text.push_str(" (installed)");
+            }
+            text.push_str(&format!("  repo={}", v.repo_id));
+            if let Some(unity) = &v.unity {
+                text.push_str(&format!("  unity={unity}"));
+            }
+            if let Some(vrchat) = &v.vrchat_version {
+                text.push_str(&format!("  vrchat={vrchat}"));
+            }
+            if !v.dependencies.is_empty() {
+                let deps = v
+                    .dependencies
+                    .iter()
+                    .map(|(id, range)| format!("{id}@{range}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                text.push_str(&format!("  deps: {deps}"));
+            }
+            let style = if crate::semver::is_prerelease(&v.version) {
+                Style::default().fg(Color::LightMagenta)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            ListItem::new(text).style(style)
+        })
+        .collect::<Vec<_>>();
+
+    let mut list_state = ListState::default().with_selected(Some(picker.selected));
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    "Versions of {}  [j/k] Move  [Enter] Install  [Esc] Close",
+                    picker.package_id
+                ))
+                .border_style(Style::default().fg(Color::LightYellow)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::LightYellow)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+
+    frame.render_widget(Clear, popup);
+    frame.render_stateful_widget(list, popup, &mut list_state);
 }
 
 fn render_available(frame: &mut Frame, state: &AppState, area: Rect) {
@@ -55,27 +323,35 @@ fn render_available(frame: &mut Frame, state: &AppState, area: Rect) {
             } else {
                 Style::default().fg(Color::Yellow)
             };
-            let text = format!(
+            let mut text = format!(
                 "{} [{}] {} - {} ({})",
                 if installed { "-" } else { "+" },
-                p.latest_version,
+                p.latest(state.show_prereleases),
                 p.id,
                 p.display_name,
                 p.repo_id
             );
+            if p.sources.len() > 1 {
+                text.push_str(&format!(" +{} repo(s)", p.sources.len() - 1));
+            }
             ListItem::new(text).style(base)
         })
         .collect::<Vec<_>>();
 
     let mut list_state = ListState::default().with_selected(Some(state.selected_available_package));
     let title = format!(
-        "Available Packages {}  filter='{}'",
+        "Available Packages {}  filter='{}'{}",
         if state.manage_focus_available {
             "(focus)"
         } else {
             ""
         },
-        state.available_package_search
+        state.available_package_search,
+        if state.show_prereleases {
+            "  +prereleases"
+        } else {
+            ""
+        }
     );
 
     let list = List::new(items)
@@ -104,29 +380,72 @@ fn render_installed(frame: &mut Frame, state: &AppState, area: Rect) {
     if let Some(m) = &state.selected_project_manifest {
         if !m.exists {
             items.push(
-                ListItem::new("Manifest missing: Packages/vpm-manifest.json")
-                    .style(Style::default().fg(Color::LightRed)),
+                ListItem::new(format!(
+                    "Manifest missing: Packages/vpm-manifest.json (type: {})",
+                    m.kind.map(|k| k.label()).unwrap_or("unknown")
+                ))
+                .style(Style::default().fg(Color::LightRed)),
             );
         }
         if let Some(msg) = &m.message {
             items.push(ListItem::new(msg.clone()).style(Style::default().fg(Color::Red)));
         }
-        items.extend(m.packages.iter().map(|p| {
-            ListItem::new(format!("- [{}] {}", p.version, p.name))
-                .style(Style::default().fg(Color::LightGreen))
+        items.extend(state.installed_packages().iter().map(|p| {
+            let version = if p.has_version_mismatch() {
+                format!(
+                    "{} -> {}",
+                    p.declared_version.as_deref().unwrap_or("?"),
+                    p.version()
+                )
+            } else {
+                p.version().to_string()
+            };
+            let mut text = if p.is_direct() {
+                format!("- [{version}] {}", p.name)
+            } else {
+                format!(
+                    "  [{version}] {} (via {})",
+                    p.name,
+                    p.required_by.join(", ")
+                )
+            };
+            let update = state.package_update(p);
+            if let Some(update) = &update {
+                text.push_str(&format!(
+                    "  ↑{} {}",
+                    update.bump.label(),
+                    update.latest_version
+                ));
+            }
+            let style = if p.has_version_mismatch() {
+                text.push_str("  ! declared/locked mismatch");
+                Style::default().fg(Color::LightRed)
+            } else if update.is_some() {
+                Style::default().fg(Color::LightYellow)
+            } else if p.is_direct() {
+                Style::default().fg(Color::LightGreen)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            ListItem::new(text).style(style)
         }));
     }
 
     let mut list_state = ListState::default().with_selected(Some(state.selected_manifest_package));
     let title = if let Some(project) = state.selected_project() {
+        let installed = state.installed_packages();
+        let direct = installed.iter().filter(|p| p.is_direct()).count();
         format!(
-            "Installed ({}) {}",
+            "Installed ({}) {}  direct={} transitive={}  {} update(s) available",
             if state.manage_focus_available {
                 ""
             } else {
                 "(focus)"
             },
-            project.display_name
+            project.display_name,
+            direct,
+            installed.len() - direct,
+            state.outdated_packages().len()
         )
     } else {
         "Installed".to_string()
@@ -155,8 +474,8 @@ fn render_installed(frame: &mut Frame, state: &AppState, area: Rect) {
 
 fn installed_package_ids(state: &AppState) -> HashSet<String> {
     state
-        .selected_project_manifest
-        .as_ref()
-        .map(|m| m.packages.iter().map(|p| p.name.clone()).collect())
-        .unwrap_or_default()
+        .installed_packages()
+        .into_iter()
+        .map(|p| p.name)
+        .collect()
 }
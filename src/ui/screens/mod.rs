@@ -2,4 +2,6 @@ pub mod add;
 pub mod manage;
 pub mod new;
 pub mod projects;
+pub mod repos;
 pub mod settings;
+pub mod tasks;
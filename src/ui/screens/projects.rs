@@ -1,22 +1,36 @@
-use crate::app::state::AppState;
+use crate::app::state::{AppState, ProjectColumn, ProjectMeta, TagEdit};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::prelude::{Frame, Line};
 use ratatui::style::{Color, Modifier, Style};
-use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::widgets::{
+    Block, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Table, TableState, Wrap,
+};
 
 pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .constraints([Constraint::Length(4), Constraint::Min(3)])
         .split(area);
 
     let mode = if state.search_mode {
         format!("Search: {}", state.search_query)
-    } else {
+    } else if state.rename_project_mode {
+        format!(
+            "Rename to: {} (Enter=save, Esc=cancel)",
+            state.rename_project_input
+        )
+    } else if let Some(edit) = state.tag_edit {
         format!(
-            "[/] Search  [Enter] Manage Project  [j/k] Select  query='{}'",
-            state.search_query
+            "{} tag: {}  [Tab] Complete  [Enter] Save  [Esc] Cancel  suggestions: {}",
+            match edit {
+                TagEdit::Add => "Add",
+                TagEdit::Remove => "Remove",
+            },
+            state.tag_input,
+            state.tag_suggestions().join(", ")
         )
+    } else {
+        "[/] Search  [Enter] Manage  [o] Open in Unity  [j/k] Select  [J/K] Move  [p] Pin  [r] Rename  [d] Remove  [t/T] Add/remove tag  [[/]] Tag filter  [g] Group by tag  [s/S] Sort  [1-7] Columns".to_string()
     };
 
     frame.render_widget(
@@ -32,34 +46,101 @@ pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
         chunks[0],
     );
 
-    let body = Layout::default()
+    let columns = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .constraints([Constraint::Length(18), Constraint::Min(10)])
         .split(chunks[1]);
+    render_tag_sidebar(frame, state, columns[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(8)])
+        .split(columns[1]);
+
+    let visible = state.project_table.visible_columns();
+    let selected_path = state.selected_project().map(|p| p.path.clone());
+    let mut rows = Vec::new();
+    let mut selected_idx = None;
+    let mut push_project = |rows: &mut Vec<Row<'_>>, p: &ProjectMeta, indent: &str| {
+        if selected_idx.is_none() && selected_path.as_ref() == Some(&p.path) {
+            selected_idx = Some(rows.len());
+        }
+        rows.push(project_row(state, p, &visible, indent));
+    };
+    if state.group_by_tag {
+        for (tag, members) in state.project_groups() {
+            rows.push(
+                Row::new(vec![Cell::from(format!(
+                    "# {} ({})",
+                    tag.as_deref().unwrap_or("(untagged)"),
+                    members.len()
+                ))])
+                .style(
+                    Style::default()
+                        .fg(Color::LightMagenta)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            );
+            for p in members {
+                push_project(&mut rows, p, "  ");
+            }
+        }
+    } else {
+        for p in state.filtered_projects() {
+            push_project(&mut rows, p, "");
+        }
+    }
 
-    let filtered = state.filtered_projects();
-    let items = filtered
+    let header = Row::new(
+        visible
+            .iter()
+            .map(|column| {
+                let arrow = match state.project_table.sort {
+                    Some(sorted) if sorted == *column && state.project_table.descending => " v",
+                    Some(sorted) if sorted == *column => " ^",
+                    _ => "",
+                };
+                Cell::from(format!("{}{arrow}", column.title()))
+            })
+            .collect::<Vec<_>>(),
+    )
+    .style(
+        Style::default()
+            .fg(Color::LightCyan)
+            .add_modifier(Modifier::BOLD),
+    );
+    let widths = visible
         .iter()
-        .map(|p| {
-            ListItem::new(format!("{} ({})", p.display_name, p.path.display()))
-                .style(Style::default().fg(Color::White))
+        .map(|column| match column {
+            ProjectColumn::Name => Constraint::Length(18),
+            ProjectColumn::Unity => Constraint::Length(12),
+            ProjectColumn::Sdk => Constraint::Length(6),
+            ProjectColumn::Packages => Constraint::Length(4),
+            ProjectColumn::Outdated => Constraint::Length(8),
+            ProjectColumn::LastOpened => Constraint::Length(11),
+            ProjectColumn::Path => Constraint::Min(10),
         })
         .collect::<Vec<_>>();
 
-    let selected_idx = state
-        .selected_project()
-        .and_then(|selected| {
-            filtered
-                .iter()
-                .position(|candidate| candidate.path == selected.path)
-        })
-        .unwrap_or(0);
-    let mut list_state = ListState::default().with_selected(Some(selected_idx));
-    let list = List::new(items)
+    let mut table_state = TableState::default().with_selected(selected_idx.or(Some(0)));
+    let table = Table::new(rows, widths)
+        .header(header)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Project List")
+                .title(format!(
+                    "Project List (sort: {}{})",
+                    state
+                        .project_table
+                        .sort
+                        .map(|c| c.title())
+                        .unwrap_or("manual"),
+                    if state.search_query.is_empty() {
+                        String::new()
+                    } else {
+                        format!(", query: {}", state.search_query)
+                    }
+                ))
                 .border_style(Style::default().fg(Color::Cyan)),
         )
         .highlight_style(
@@ -70,7 +151,7 @@ pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
         )
         .highlight_symbol("▶ ");
 
-    frame.render_stateful_widget(list, body[0], &mut list_state);
+    frame.render_stateful_widget(table, body[0], &mut table_state);
 
     let detail_lines = if let Some(project) = state.selected_project() {
         vec![
@@ -84,6 +165,24 @@ pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
                 format!("Path: {}", project.path.display()),
                 Style::default().fg(Color::Gray),
             ),
+            match (
+                state.project_versions.get(&project.path),
+                state.editor_installed(&project.path),
+            ) {
+                (Some(version), Some(true)) => Line::styled(
+                    format!("Unity: {version} (installed)"),
+                    Style::default().fg(Color::White),
+                ),
+                (Some(version), Some(false)) => Line::styled(
+                    format!("Unity: {version} (not installed; install it with Unity Hub)"),
+                    Style::default().fg(Color::LightYellow),
+                ),
+                (Some(version), None) => Line::styled(
+                    format!("Unity: {version} (installed editors not listed yet)"),
+                    Style::default().fg(Color::White),
+                ),
+                (None, _) => Line::styled("Unity: (unknown)", Style::default().fg(Color::DarkGray)),
+            },
             Line::styled(
                 format!("Tags: {}", project.tags.join(", ")),
                 Style::default().fg(Color::LightMagenta),
@@ -98,10 +197,17 @@ pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
                 ),
                 Style::default().fg(Color::White),
             ),
-            Line::styled(
-                "Action: Enter -> Manage Project",
-                Style::default().fg(Color::LightGreen),
-            ),
+            if state.offline_projects.contains(&project.path) {
+                Line::styled(
+                    "Status: offline (folder not reachable; kept until removed with [d])",
+                    Style::default().fg(Color::LightRed),
+                )
+            } else {
+                Line::styled(
+                    "Action: Enter -> Manage Project",
+                    Style::default().fg(Color::LightGreen),
+                )
+            },
         ]
     } else {
         vec![Line::styled(
@@ -122,3 +228,73 @@ pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
         body[1],
     );
 }
+
+fn project_row<'a>(
+    state: &AppState,
+    p: &ProjectMeta,
+    columns: &[ProjectColumn],
+    indent: &str,
+) -> Row<'a> {
+    let cells = columns
+        .iter()
+        .map(|column| {
+            let text = state.project_cell(p, *column);
+            if *column != ProjectColumn::Name {
+                return Cell::from(text);
+            }
+            let offline = if state.offline_projects.contains(&p.path) {
+                "[offline] "
+            } else {
+                ""
+            };
+            let pin = if p.pinned { "* " } else { "" };
+            Cell::from(format!("{indent}{pin}{offline}{text}"))
+        })
+        .collect::<Vec<_>>();
+    let color = if state.offline_projects.contains(&p.path) {
+        Color::LightRed
+    } else if state.editor_installed(&p.path) == Some(false) {
+        Color::LightYellow
+    } else {
+        Color::White
+    };
+    Row::new(cells).style(Style::default().fg(color))
+}
+
+fn render_tag_sidebar(frame: &mut Frame, state: &AppState, area: Rect) {
+    let mut entries = vec![(None, state.projects.len())];
+    entries.extend(state.all_tags().into_iter().map(|tag| {
+        let count = state
+            .projects
+            .iter()
+            .filter(|p| p.tags.contains(&tag))
+            .count();
+        (Some(tag), count)
+    }));
+    let selected = entries
+        .iter()
+        .position(|(tag, _)| tag == &state.tag_filter)
+        .unwrap_or(0);
+    let items = entries
+        .into_iter()
+        .map(|(tag, count)| {
+            ListItem::new(format!("{} ({count})", tag.as_deref().unwrap_or("All")))
+                .style(Style::default().fg(Color::LightMagenta))
+        })
+        .collect::<Vec<_>>();
+    let mut list_state = ListState::default().with_selected(Some(selected));
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Tags")
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::LightMagenta)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        );
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
@@ -0,0 +1,160 @@
+use crate::app::state::AppState;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::prelude::{Frame, Line};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+
+pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(3),
+            Constraint::Length(7),
+        ])
+        .split(area);
+
+    let mode = if state.repo_header_mode {
+        format!(
+            "Header (Name: value, empty value removes): {} (Enter=save, Esc=cancel)",
+            state.repo_header_input
+        )
+    } else if state.add_repo_mode {
+        format!(
+            "Repo URL input: {} (Enter=add, Esc=cancel)",
+            state.add_repo_input
+        )
+    } else {
+        "[j/k] Select  [J/K] Move down/up  [Space] Enable/disable  [d] Remove  [e] Set header  [E] Clear headers  [a] Add  [r] Reload".to_string()
+    };
+
+    frame.render_widget(
+        Paragraph::new(mode)
+            .style(Style::default().fg(Color::White))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Repositories")
+                    .border_style(Style::default().fg(Color::LightBlue)),
+            )
+            .wrap(Wrap { trim: true }),
+        chunks[0],
+    );
+
+    let items = state
+        .repositories
+        .iter()
+        .map(|repo| {
+            let disabled = state.is_repo_disabled(repo);
+            let text = format!(
+                "[{}] {}  {}  packages={}  cache={}{}",
+                if disabled { " " } else { "x" },
+                repo.name,
+                repo.url,
+                repo.package_count
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| "?".to_string()),
+                repo.cache_age_secs
+                    .map(format_age)
+                    .unwrap_or_else(|| "missing".to_string()),
+                if repo.headers.is_empty() {
+                    String::new()
+                } else {
+                    format!("  headers={}", repo.headers.len())
+                }
+            );
+            ListItem::new(text).style(if disabled {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(Color::White)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut list_state = ListState::default().with_selected(Some(state.selected_repository));
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    "User repositories ({}) - VCC settings.json",
+                    state.repositories.len()
+                ))
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::LightCyan)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+
+    frame.render_stateful_widget(list, chunks[1], &mut list_state);
+
+    let detail_lines = if let Some(repo) = state.selected_repository() {
+        let mut lines = vec![
+            Line::styled(
+                format!("ID: {}", repo.id.as_deref().unwrap_or("(unknown)")),
+                Style::default().fg(Color::LightYellow),
+            ),
+            Line::styled(
+                format!(
+                    "Cache: {}",
+                    repo.local_path
+                        .as_ref()
+                        .map(|p| p.display().to_string())
+                        .unwrap_or_else(|| "(none)".to_string())
+                ),
+                Style::default().fg(Color::Gray),
+            ),
+        ];
+        if repo.headers.is_empty() {
+            lines.push(Line::styled(
+                "Headers: (none)",
+                Style::default().fg(Color::Gray),
+            ));
+        }
+        for (name, value) in &repo.headers {
+            lines.push(Line::styled(
+                format!("Header {name}: {}", mask_header_value(value)),
+                Style::default().fg(Color::LightMagenta),
+            ));
+        }
+        lines
+    } else {
+        vec![Line::styled(
+            "No user repositories in VCC settings",
+            Style::default().fg(Color::DarkGray),
+        )]
+    };
+
+    frame.render_widget(
+        Paragraph::new(detail_lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Detail")
+                    .border_style(Style::default().fg(Color::LightBlue)),
+            )
+            .wrap(Wrap { trim: false }),
+        chunks[2],
+    );
+}
+
+fn format_age(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86_399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
+
+fn mask_header_value(value: &str) -> String {
+    if value.chars().count() <= 8 {
+        return "•••".to_string();
+    }
+    let shown = value.chars().take(4).collect::<String>();
+    format!("{shown}•••")
+}
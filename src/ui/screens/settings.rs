@@ -28,10 +28,14 @@ pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
             Style::default().fg(Color::White),
         ),
         Line::styled(
-            "[c] cancel latest running task",
+            "[c] cancel latest running/queued task",
             Style::default().fg(Color::LightRed),
         ),
         Line::from(""),
+        Line::styled(
+            format!("vpm executable: {}", state.vpm_settings.program().display()),
+            Style::default().fg(Color::Yellow),
+        ),
         Line::styled(
             format!(
                 "vpm --version: {}",
@@ -65,12 +69,51 @@ pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
             ),
             Style::default().fg(Color::Yellow),
         ),
+        Line::styled(
+            format!(
+                "unity editor root: {}",
+                state
+                    .unity_settings
+                    .editor_root
+                    .as_ref()
+                    .map(|root| root.display().to_string())
+                    .unwrap_or_else(|| "(Unity Hub locations)".to_string())
+            ),
+            Style::default().fg(Color::Yellow),
+        ),
+        Line::styled(
+            format!(
+                "backup folder: {}",
+                match (&state.backup_settings.folder, &state.default_backup_dir) {
+                    (Some(folder), _) => folder.display().to_string(),
+                    (None, Some(default)) => format!("{} (default)", default.display()),
+                    (None, None) => "(default location unavailable)".to_string(),
+                }
+            ),
+            Style::default().fg(Color::Yellow),
+        ),
+        Line::styled(
+            format!(
+                "unity editors: {}",
+                match &state.system_checks.installed_editors {
+                    Some(editors) if editors.is_empty() => "(none found)".to_string(),
+                    Some(editors) => editors
+                        .iter()
+                        .map(|e| e.version.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    None => "(not listed yet)".to_string(),
+                }
+            ),
+            Style::default().fg(Color::Yellow),
+        ),
         Line::from(""),
         Line::styled("Recent tasks:", Style::default().fg(Color::LightCyan)),
     ];
 
     for task in state.tasks.iter().rev().take(8) {
         let (status, color) = match task.state {
+            TaskState::Queued => ("queued", Color::Gray),
             TaskState::Running => ("running", Color::LightBlue),
             TaskState::Success => ("success", Color::LightGreen),
             TaskState::Failed => ("failed", Color::LightRed),
@@ -0,0 +1,289 @@
+use crate::app::state::{AppState, LogLevel, TaskRecord, TaskState};
+use crate::ui::{level_style, log_style};
+use chrono::{DateTime, Local};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::prelude::{Frame, Line};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+
+pub fn render(frame: &mut Frame, state: &AppState, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .split(area);
+
+    frame.render_widget(
+        Paragraph::new(if state.show_task_history {
+            "[H] Current session  [[/]] Newer/older  [j/k] Select  [J/K] Scroll  [G] Newest  [r] Rerun"
+        } else {
+            "[j/k] Select  [J/K] Scroll output  [G] Newest  [c] Cancel task  [r] Rerun  [H] Past sessions"
+        })
+        .style(Style::default().fg(Color::White))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Tasks")
+                .border_style(Style::default().fg(Color::LightBlue)),
+        )
+        .wrap(Wrap { trim: true }),
+        chunks[0],
+    );
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+
+    if state.show_task_history {
+        render_history(frame, state, body[0], body[1]);
+        return;
+    }
+
+    let now = Local::now();
+    let items = state
+        .tasks_newest_first()
+        .into_iter()
+        .map(|task| {
+            let (status, color) = state_label(task.state);
+            let project = task
+                .project_path
+                .as_deref()
+                .map(|path| format!("[{}] ", state.project_name_for(path)))
+                .unwrap_or_default();
+            ListItem::new(format!(
+                "#{} {:<9} {}  {:>6}  {:<7} {}{}",
+                task.id,
+                status,
+                task.started_at.unwrap_or(task.queued_at).format("%H:%M:%S"),
+                task.duration_secs(now)
+                    .map(format_duration)
+                    .unwrap_or_else(|| "-".to_string()),
+                task.exit_code
+                    .map(|code| format!("exit={code}"))
+                    .unwrap_or_default(),
+                project,
+                task.label
+            ))
+            .style(Style::default().fg(color))
+        })
+        .collect::<Vec<_>>();
+
+    let mut list_state = ListState::default().with_selected(Some(state.selected_task));
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Tasks ({})", state.tasks.len()))
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::LightCyan)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    frame.render_stateful_widget(list, body[0], &mut list_state);
+
+    let (title, lines) = match state.selected_task() {
+        Some(task) => (
+            format!("Task #{} output", task.id),
+            detail_lines(state, task),
+        ),
+        None => (
+            "Task output".to_string(),
+            vec![Line::styled(
+                "No tasks have run yet",
+                Style::default().fg(Color::DarkGray),
+            )],
+        ),
+    };
+    render_output(frame, state, title, lines, body[1]);
+}
+
+fn render_history(frame: &mut Frame, state: &AppState, list_area: Rect, log_area: Rect) {
+    let sessions = state.history_sessions();
+    let entries = state.history_session_entries();
+    let items = entries
+        .iter()
+        .map(|entry| {
+            let (status, color) = state_label(entry.state);
+            let project = entry
+                .project_path
+                .as_deref()
+                .map(|path| format!("[{}] ", state.project_name_for(path)))
+                .unwrap_or_default();
+            ListItem::new(format!(
+                "#{} {:<9} {}  {:<7} {}{}",
+                entry.task_id,
+                status,
+                entry
+                    .started_at
+                    .unwrap_or(entry.queued_at)
+                    .format("%H:%M:%S"),
+                entry
+                    .exit_code
+                    .map(|code| format!("exit={code}"))
+                    .unwrap_or_default(),
+                project,
+                entry.label
+            ))
+            .style(Style::default().fg(color))
+        })
+        .collect::<Vec<_>>();
+
+    let title = match sessions.get(state.selected_history_session) {
+        Some(session) => format!(
+            "Session {session} ({}/{})",
+            state.selected_history_session + 1,
+            sessions.len()
+        ),
+        None => "No recorded sessions".to_string(),
+    };
+    let mut list_state = ListState::default().with_selected(Some(state.selected_history_task));
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::LightCyan)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    frame.render_stateful_widget(list, list_area, &mut list_state);
+
+    let mut lines = Vec::new();
+    if let Some(entry) = state.selected_history_entry() {
+        lines.push(Line::styled(
+            entry.command_line.as_str(),
+            Style::default().fg(Color::LightYellow),
+        ));
+        lines.push(Line::styled(
+            format!(
+                "Started {}  Finished {}",
+                entry
+                    .started_at
+                    .map(format_clock)
+                    .unwrap_or_else(|| "-".to_string()),
+                entry
+                    .finished_at
+                    .map(format_clock)
+                    .unwrap_or_else(|| "-".to_string())
+            ),
+            Style::default().fg(Color::Gray),
+        ));
+        if let Some(err) = &entry.error {
+            lines.push(Line::styled(
+                format!("Error: {err}"),
+                Style::default().fg(Color::LightRed),
+            ));
+        }
+        lines.push(Line::from(""));
+        lines.extend(state.history_log.iter().map(|l| {
+            let level = LogLevel::from_export_line(l).unwrap_or(LogLevel::Info);
+            Line::styled(l.as_str(), level_style(level, true))
+        }));
+    }
+    render_output(frame, state, "Recorded output".to_string(), lines, log_area);
+}
+
+// The pane sticks to the newest line unless scrolled back with J/K.
+fn render_output(
+    frame: &mut Frame,
+    state: &AppState,
+    mut title: String,
+    lines: Vec<Line>,
+    area: Rect,
+) {
+    let visible = area.height.saturating_sub(2) as usize;
+    let bottom = lines.len().saturating_sub(visible);
+    let scroll = bottom.saturating_sub(state.task_output_scroll);
+    if scroll < bottom {
+        title.push_str(&format!(" (scrolled {} up, [G] newest)", bottom - scroll));
+    }
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(Style::default().fg(Color::LightBlue)),
+            )
+            .scroll((scroll as u16, 0)),
+        area,
+    );
+}
+
+fn detail_lines<'a>(state: &'a AppState, task: &'a TaskRecord) -> Vec<Line<'a>> {
+    let meta = Style::default().fg(Color::Gray);
+    let mut lines = vec![
+        Line::styled(
+            format!("vpm {}", task.args.join(" ")),
+            Style::default().fg(Color::LightYellow),
+        ),
+        Line::styled(
+            format!(
+                "Project: {}",
+                task.project_path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "(none)".to_string())
+            ),
+            meta,
+        ),
+        Line::styled(
+            format!(
+                "Queued {}  Started {}  Finished {}",
+                format_clock(task.queued_at),
+                task.started_at
+                    .map(format_clock)
+                    .unwrap_or_else(|| "-".to_string()),
+                task.finished_at
+                    .map(format_clock)
+                    .unwrap_or_else(|| "-".to_string())
+            ),
+            meta,
+        ),
+    ];
+    if let Some(err) = &task.error {
+        lines.push(Line::styled(
+            format!("Error: {err}"),
+            Style::default().fg(Color::LightRed),
+        ));
+    }
+    lines.push(Line::from(""));
+    lines.extend(
+        state
+            .task_logs(task.id)
+            .into_iter()
+            .map(|l| Line::styled(l.display(), log_style(l))),
+    );
+    lines
+}
+
+fn state_label(state: TaskState) -> (&'static str, Color) {
+    match state {
+        TaskState::Queued => ("queued", Color::Gray),
+        TaskState::Running => ("running", Color::LightBlue),
+        TaskState::Success => ("success", Color::LightGreen),
+        TaskState::Failed => ("failed", Color::LightRed),
+        TaskState::Cancelled => ("cancelled", Color::LightMagenta),
+    }
+}
+
+fn format_clock(time: DateTime<Local>) -> String {
+    time.format("%m-%d %H:%M:%S").to_string()
+}
+
+fn format_duration(secs: i64) -> String {
+    if secs < 60 {
+        format!("{secs}s")
+    } else {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    }
+}